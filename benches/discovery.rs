@@ -0,0 +1,59 @@
+//! Compare the time taken by unfiltered and filtered app discovery.
+//!
+//! Run with:
+//!
+//! ```shell
+//! $ cargo bench --bench discovery
+//! ```
+//!
+//! Results depend on which Wolfram apps are installed on the machine running
+//! the benchmark.
+
+use std::time::{Duration, Instant};
+
+use wolfram_app_discovery::{discover, discover_with_filter, Filter, WolframAppType};
+
+const ITERATIONS: u32 = 20;
+
+fn main() {
+    // Discovery results are not cached, but warm up the filesystem caches so
+    // that the first measurement isn't an outlier.
+    let _ = discover();
+
+    let all = bench("discover()", || discover().len());
+
+    for app_type in [WolframAppType::Mathematica, WolframAppType::Engine] {
+        let filter = Filter {
+            app_types: Some(vec![app_type.clone()]),
+            min_version: None,
+            kernel_system_id: None,
+            requires_front_end: false,
+            requires_activation: false,
+            wstp_sdk_system_id: None,
+        };
+
+        let filtered = bench(&format!("discover_with_filter({app_type:?})"), || {
+            discover_with_filter(&filter).len()
+        });
+
+        println!(
+            "    {:.2}x the time of unfiltered discovery",
+            filtered.as_secs_f64() / all.as_secs_f64()
+        );
+    }
+}
+
+/// Run `f` [`ITERATIONS`] times and print the mean time taken.
+fn bench(name: &str, mut f: impl FnMut() -> usize) -> Duration {
+    let mut apps = 0;
+
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        apps = f();
+    }
+    let mean = start.elapsed() / ITERATIONS;
+
+    println!("{name:<45} {mean:>12.2?}/iter ({apps} apps)");
+
+    mean
+}
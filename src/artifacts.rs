@@ -0,0 +1,428 @@
+//! File names of the artifacts provided by a Wolfram installation.
+//!
+//! The functions in this module return the platform-specific name of a file
+//! (e.g. `"wstp64i4s.lib"`), not its location. Use the methods on
+//! [`WolframApp`][crate::WolframApp] and [`WstpSdk`][crate::WstpSdk] to locate
+//! these files within a particular installation.
+//!
+//! These functions are provided so that programs that assemble paths to Wolfram
+//! resources themselves can share the naming rules used by
+//! `wolfram-app-discovery`.
+
+use crate::{os::OperatingSystem, Error, SystemID};
+
+//======================================
+// WSTP
+//======================================
+
+/// The WSTP library interface version assumed by [`wstp_static_library_name()`]
+/// and [`wstp_shared_library_name()`].
+///
+/// Use [`WstpSdk::wstp_interface()`][crate::WstpSdk::wstp_interface] to get the
+/// interface version of a particular WSTP SDK.
+pub const DEFAULT_WSTP_INTERFACE: u32 = 4;
+
+/// File name of the [WSTP](https://reference.wolfram.com/language/guide/WSTPAPI.html)
+/// static library for `system_id`.
+///
+/// This is the file name for [`DEFAULT_WSTP_INTERFACE`]. Use
+/// [`wstp_static_library_name_for_interface()`] to get the file name for a
+/// different WSTP interface version.
+///
+/// ```
+/// use wolfram_app_discovery::{artifacts, SystemID};
+///
+/// assert_eq!(
+///     artifacts::wstp_static_library_name(SystemID::Linux_x86_64).unwrap(),
+///     "libWSTP64i4.a"
+/// );
+/// ```
+pub fn wstp_static_library_name(system_id: SystemID) -> Result<&'static str, Error> {
+    let name = match system_id {
+        SystemID::MacOSX_x86_64 | SystemID::MacOSX_ARM64 => "libWSTPi4.a",
+        SystemID::Windows_x86_64 | SystemID::Windows_ARM64 => "wstp64i4s.lib",
+        SystemID::Windows | SystemID::Windows_ARM => "wstp32i4s.lib",
+        SystemID::Linux_x86_64 | SystemID::Linux_ARM64 => "libWSTP64i4.a",
+        SystemID::Linux | SystemID::Linux_ARM => "libWSTP32i4.a",
+        SystemID::iOS_ARM64 | SystemID::Android => {
+            return Err(unknown_name("WSTP static library", system_id));
+        },
+    };
+
+    Ok(name)
+}
+
+/// File name of the [WSTP](https://reference.wolfram.com/language/guide/WSTPAPI.html)
+/// static library for `system_id` and WSTP library `interface` version.
+///
+/// ```
+/// use wolfram_app_discovery::{artifacts, SystemID};
+///
+/// assert_eq!(
+///     artifacts::wstp_static_library_name_for_interface(SystemID::Windows_x86_64, 5)
+///         .unwrap(),
+///     "wstp64i5s.lib"
+/// );
+/// ```
+pub fn wstp_static_library_name_for_interface(
+    system_id: SystemID,
+    interface: u32,
+) -> Result<String, Error> {
+    let name = match system_id {
+        SystemID::MacOSX_x86_64 | SystemID::MacOSX_ARM64 => {
+            format!("libWSTPi{interface}.a")
+        },
+        SystemID::Windows_x86_64 | SystemID::Windows_ARM64 => {
+            format!("wstp64i{interface}s.lib")
+        },
+        SystemID::Windows | SystemID::Windows_ARM => format!("wstp32i{interface}s.lib"),
+        SystemID::Linux_x86_64 | SystemID::Linux_ARM64 => {
+            format!("libWSTP64i{interface}.a")
+        },
+        SystemID::Linux | SystemID::Linux_ARM => format!("libWSTP32i{interface}.a"),
+        SystemID::iOS_ARM64 | SystemID::Android => {
+            return Err(unknown_name("WSTP static library", system_id));
+        },
+    };
+
+    Ok(name)
+}
+
+/// File name of the [WSTP](https://reference.wolfram.com/language/guide/WSTPAPI.html)
+/// shared (dynamic) library for `system_id`.
+///
+/// On macOS, the WSTP shared library is distributed as the `wstp.framework`
+/// framework bundle.
+///
+/// This is the file name for [`DEFAULT_WSTP_INTERFACE`]. Use
+/// [`wstp_shared_library_name_for_interface()`] to get the file name for a
+/// different WSTP interface version.
+pub fn wstp_shared_library_name(system_id: SystemID) -> Result<&'static str, Error> {
+    let name = match system_id {
+        SystemID::MacOSX_x86_64 | SystemID::MacOSX_ARM64 => "wstp.framework",
+        SystemID::Windows_x86_64 | SystemID::Windows_ARM64 => "wstp64i4.dll",
+        SystemID::Windows | SystemID::Windows_ARM => "wstp32i4.dll",
+        SystemID::Linux_x86_64 | SystemID::Linux_ARM64 => "libWSTP64i4.so",
+        SystemID::Linux | SystemID::Linux_ARM => "libWSTP32i4.so",
+        SystemID::iOS_ARM64 | SystemID::Android => {
+            return Err(unknown_name("WSTP shared library", system_id));
+        },
+    };
+
+    Ok(name)
+}
+
+/// File name of the [WSTP](https://reference.wolfram.com/language/guide/WSTPAPI.html)
+/// shared (dynamic) library for `system_id` and WSTP library `interface`
+/// version.
+pub fn wstp_shared_library_name_for_interface(
+    system_id: SystemID,
+    interface: u32,
+) -> Result<String, Error> {
+    let name = match system_id {
+        SystemID::MacOSX_x86_64 | SystemID::MacOSX_ARM64 => "wstp.framework".to_owned(),
+        SystemID::Windows_x86_64 | SystemID::Windows_ARM64 => {
+            format!("wstp64i{interface}.dll")
+        },
+        SystemID::Windows | SystemID::Windows_ARM => format!("wstp32i{interface}.dll"),
+        SystemID::Linux_x86_64 | SystemID::Linux_ARM64 => {
+            format!("libWSTP64i{interface}.so")
+        },
+        SystemID::Linux | SystemID::Linux_ARM => format!("libWSTP32i{interface}.so"),
+        SystemID::iOS_ARM64 | SystemID::Android => {
+            return Err(unknown_name("WSTP shared library", system_id));
+        },
+    };
+
+    Ok(name)
+}
+
+/// File name of the legacy
+/// [MathLink](https://reference.wolfram.com/language/guide/WSTPAPI.html)
+/// static library for `system_id`.
+///
+/// ```
+/// use wolfram_app_discovery::{artifacts, SystemID};
+///
+/// assert_eq!(
+///     artifacts::mathlink_static_library_name(SystemID::Linux_x86_64).unwrap(),
+///     "libML64i4.a"
+/// );
+/// ```
+pub fn mathlink_static_library_name(system_id: SystemID) -> Result<&'static str, Error> {
+    let name = match system_id {
+        SystemID::MacOSX_x86_64 | SystemID::MacOSX_ARM64 => "libMLi4.a",
+        SystemID::Windows_x86_64 | SystemID::Windows_ARM64 => "ml64i4s.lib",
+        SystemID::Windows | SystemID::Windows_ARM => "ml32i4s.lib",
+        SystemID::Linux_x86_64 | SystemID::Linux_ARM64 => "libML64i4.a",
+        SystemID::Linux | SystemID::Linux_ARM => "libML32i4.a",
+        SystemID::iOS_ARM64 | SystemID::Android => {
+            return Err(unknown_name("MathLink static library", system_id));
+        },
+    };
+
+    Ok(name)
+}
+
+/// File name of the
+/// [`wsprep`](https://reference.wolfram.com/language/ref/program/wsprep.html)
+/// template preprocessor executable for `system_id`.
+pub fn wsprep_executable_name(system_id: SystemID) -> Result<&'static str, Error> {
+    let name = match system_id.operating_system() {
+        OperatingSystem::MacOS | OperatingSystem::Linux => "wsprep",
+        OperatingSystem::Windows => "wsprep.exe",
+        OperatingSystem::Other => {
+            return Err(unknown_name("wsprep executable", system_id));
+        },
+    };
+
+    Ok(name)
+}
+
+/// File names of the
+/// [`wscc`](https://reference.wolfram.com/language/ref/program/wscc.html)
+/// compiler driver script for `system_id`, in order of preference.
+///
+/// Older WSTP SDKs provide the compiler driver under its MathLink name,
+/// `mcc`.
+pub fn wscc_script_names(system_id: SystemID) -> Result<&'static [&'static str], Error> {
+    let names: &[&str] = match system_id.operating_system() {
+        OperatingSystem::MacOS | OperatingSystem::Linux => &["wscc", "mcc"],
+        OperatingSystem::Windows | OperatingSystem::Other => {
+            return Err(unknown_name("wscc compiler driver", system_id));
+        },
+    };
+
+    Ok(names)
+}
+
+/// Linker flags for the system libraries required when linking the
+/// [WSTP](https://reference.wolfram.com/language/guide/WSTPAPI.html) static
+/// library for `system_id`.
+///
+/// ```
+/// use wolfram_app_discovery::{artifacts, SystemID};
+///
+/// assert_eq!(
+///     artifacts::wstp_static_library_link_flags(SystemID::MacOSX_ARM64).unwrap(),
+///     ["-lc++", "-framework", "Foundation"]
+/// );
+/// ```
+pub fn wstp_static_library_link_flags(
+    system_id: SystemID,
+) -> Result<&'static [&'static str], Error> {
+    let flags: &[&str] = match system_id.operating_system() {
+        OperatingSystem::MacOS => &["-lc++", "-framework", "Foundation"],
+        OperatingSystem::Linux => {
+            &["-lm", "-lpthread", "-lrt", "-lstdc++", "-ldl", "-luuid"]
+        },
+        // TODO: Determine the system libraries required on Windows.
+        OperatingSystem::Windows => &[],
+        OperatingSystem::Other => {
+            return Err(Error::other(format!(
+                "no known WSTP static library link flags for SystemID: {system_id}"
+            )));
+        },
+    };
+
+    Ok(flags)
+}
+
+//======================================
+// Runtime libraries
+//======================================
+
+/// File name of the `WolframRTL` runtime shared (dynamic) library for
+/// `system_id`.
+///
+/// ```
+/// use wolfram_app_discovery::{artifacts, SystemID};
+///
+/// assert_eq!(
+///     artifacts::wolfram_rtl_shared_library_name(SystemID::Linux_x86_64).unwrap(),
+///     "libWolframRTL.so"
+/// );
+/// ```
+pub fn wolfram_rtl_shared_library_name(
+    system_id: SystemID,
+) -> Result<&'static str, Error> {
+    let name = match system_id.operating_system() {
+        OperatingSystem::MacOS => "libWolframRTL.dylib",
+        OperatingSystem::Windows => "WolframRTL.dll",
+        OperatingSystem::Linux => "libWolframRTL.so",
+        OperatingSystem::Other => {
+            return Err(unknown_name("WolframRTL shared library", system_id));
+        },
+    };
+
+    Ok(name)
+}
+
+/// File name of the minimal `WolframRTL` runtime static library for
+/// `system_id`.
+pub fn wolfram_rtl_static_library_name(
+    system_id: SystemID,
+) -> Result<&'static str, Error> {
+    let name = match system_id.operating_system() {
+        OperatingSystem::MacOS | OperatingSystem::Linux => {
+            "libWolframRTL_Static_Minimal.a"
+        },
+        OperatingSystem::Windows => "WolframRTL_Static_Minimal.lib",
+        OperatingSystem::Other => {
+            return Err(unknown_name("WolframRTL static library", system_id));
+        },
+    };
+
+    Ok(name)
+}
+
+/// File name of the `WolframCompileLibrary` shared (dynamic) library for
+/// `system_id`.
+pub fn wolfram_compile_library_name(system_id: SystemID) -> Result<&'static str, Error> {
+    let name = match system_id.operating_system() {
+        OperatingSystem::MacOS => "libWolframCompileLibrary.dylib",
+        OperatingSystem::Windows => "WolframCompileLibrary.dll",
+        OperatingSystem::Linux => "libWolframCompileLibrary.so",
+        OperatingSystem::Other => {
+            return Err(unknown_name("WolframCompileLibrary library", system_id));
+        },
+    };
+
+    Ok(name)
+}
+
+//======================================
+// Executables
+//======================================
+
+/// File name of the
+/// [`WolframKernel`](https://reference.wolfram.com/language/ref/program/WolframKernel.html)
+/// executable for `system_id`.
+pub fn kernel_executable_name(system_id: SystemID) -> Result<&'static str, Error> {
+    let name = match system_id.operating_system() {
+        OperatingSystem::MacOS | OperatingSystem::Linux => "WolframKernel",
+        OperatingSystem::Windows => "WolframKernel.exe",
+        OperatingSystem::Other => {
+            return Err(unknown_name("WolframKernel executable", system_id));
+        },
+    };
+
+    Ok(name)
+}
+
+/// File name of the
+/// [`wolframscript`](https://reference.wolfram.com/language/ref/program/wolframscript.html)
+/// executable for `system_id`.
+pub fn wolframscript_executable_name(system_id: SystemID) -> Result<&'static str, Error> {
+    let name = match system_id.operating_system() {
+        OperatingSystem::MacOS | OperatingSystem::Linux => "wolframscript",
+        OperatingSystem::Windows => "wolframscript.exe",
+        OperatingSystem::Other => {
+            return Err(unknown_name("wolframscript executable", system_id));
+        },
+    };
+
+    Ok(name)
+}
+
+/// File name of the [Wolfram Player](https://www.wolfram.com/player/)
+/// executable for `system_id`.
+pub fn player_executable_name(system_id: SystemID) -> Result<&'static str, Error> {
+    let name = match system_id.operating_system() {
+        OperatingSystem::MacOS | OperatingSystem::Linux => "WolframPlayer",
+        OperatingSystem::Windows => "WolframPlayer.exe",
+        OperatingSystem::Other => {
+            return Err(unknown_name("Wolfram Player executable", system_id));
+        },
+    };
+
+    Ok(name)
+}
+
+//======================================
+// Helpers
+//======================================
+
+/// Parse the WSTP library interface and revision numbers from the contents of
+/// a `wstp.h` header file.
+///
+/// These are the values of the `WSINTERFACE` and `WSREVISION` macros.
+pub(crate) fn parse_wstp_h_version(contents: &str) -> (Option<u32>, Option<u32>) {
+    let mut interface = None;
+    let mut revision = None;
+
+    for line in contents.lines() {
+        let mut words = line.split_whitespace();
+
+        if words.next() != Some("#define") {
+            continue;
+        }
+
+        let (Some(name), Some(value)) = (words.next(), words.next()) else {
+            continue;
+        };
+
+        let slot = match name {
+            "WSINTERFACE" => &mut interface,
+            "WSREVISION" => &mut revision,
+            _ => continue,
+        };
+
+        if slot.is_none() {
+            *slot = value.parse().ok();
+        }
+    }
+
+    (interface, revision)
+}
+
+fn unknown_name(artifact: &str, system_id: SystemID) -> Error {
+    Error::other(format!(
+        "no known {artifact} file name for SystemID: {system_id}"
+    ))
+}
+
+//======================================
+// Tests
+//======================================
+
+#[test]
+fn test_wstp_library_names_for_interface() {
+    for system_id in [
+        SystemID::MacOSX_x86_64,
+        SystemID::MacOSX_ARM64,
+        SystemID::Windows_x86_64,
+        SystemID::Windows_ARM64,
+        SystemID::Windows,
+        SystemID::Windows_ARM,
+        SystemID::Linux_x86_64,
+        SystemID::Linux_ARM64,
+        SystemID::Linux,
+        SystemID::Linux_ARM,
+    ] {
+        assert_eq!(
+            wstp_static_library_name_for_interface(system_id, DEFAULT_WSTP_INTERFACE)
+                .unwrap(),
+            wstp_static_library_name(system_id).unwrap()
+        );
+        assert_eq!(
+            wstp_shared_library_name_for_interface(system_id, DEFAULT_WSTP_INTERFACE)
+                .unwrap(),
+            wstp_shared_library_name(system_id).unwrap()
+        );
+    }
+}
+
+#[test]
+fn test_parse_wstp_h_version() {
+    let contents = "\
+#ifndef WSINTERFACE
+#define WSINTERFACE 4
+#define WSREVISION 47
+#endif
+#define WSAPIREVISION WSREVISION
+";
+
+    assert_eq!(parse_wstp_h_version(contents), (Some(4), Some(47)));
+    assert_eq!(parse_wstp_h_version("int x;"), (None, None));
+}
@@ -0,0 +1,160 @@
+//! CMake package configuration file generation.
+//!
+//! The generated file can be loaded using `find_package(Wolfram CONFIG)` or
+//! `include()`, and defines variables and imported targets for the selected
+//! Wolfram app.
+
+use std::{
+    io::{self, Write},
+    path::{Path, PathBuf},
+};
+
+/// Locations written to a CMake configuration file.
+pub struct CMakeConfig {
+    pub app_directory: PathBuf,
+    pub kernel: PathBuf,
+    pub wstp_include_dir: PathBuf,
+    pub wstp_library: PathBuf,
+    /// System libraries required when linking [`CMakeConfig::wstp_library`],
+    /// as returned by [`artifacts::wstp_static_library_link_flags()`].
+    ///
+    /// [`artifacts::wstp_static_library_link_flags()`]: wolfram_app_discovery::artifacts::wstp_static_library_link_flags
+    pub wstp_link_flags: &'static [&'static str],
+    pub library_link_include_dir: PathBuf,
+}
+
+impl CMakeConfig {
+    pub fn write(&self, fmt: &mut dyn Write) -> io::Result<()> {
+        let CMakeConfig {
+            app_directory,
+            kernel,
+            wstp_include_dir,
+            wstp_library,
+            wstp_link_flags,
+            library_link_include_dir,
+        } = self;
+
+        writeln!(
+            fmt,
+            "# Generated by wolfram-app-discovery {}. Do not edit.",
+            env!("CARGO_PKG_VERSION")
+        )?;
+        writeln!(fmt)?;
+
+        writeln!(fmt, "set(WOLFRAM_APP_DIRECTORY {})", quoted(app_directory))?;
+        writeln!(fmt, "set(WOLFRAM_KERNEL {})", quoted(kernel))?;
+        writeln!(fmt, "set(WSTP_INCLUDE_DIR {})", quoted(wstp_include_dir))?;
+        writeln!(fmt, "set(WSTP_LIBRARY {})", quoted(wstp_library))?;
+        writeln!(
+            fmt,
+            "set(WSTP_LINK_LIBRARIES \"{}\")",
+            link_libraries(wstp_link_flags).join(";")
+        )?;
+        writeln!(
+            fmt,
+            "set(WOLFRAM_LIBRARYLINK_INCLUDE_DIR {})",
+            quoted(library_link_include_dir)
+        )?;
+
+        write!(fmt, "{}", IMPORTED_TARGETS)
+    }
+}
+
+/// Imported targets defined in terms of the variables set above.
+const IMPORTED_TARGETS: &str = r#"
+if(NOT TARGET Wolfram::Kernel)
+    add_executable(Wolfram::Kernel IMPORTED)
+    set_target_properties(Wolfram::Kernel PROPERTIES
+        IMPORTED_LOCATION "${WOLFRAM_KERNEL}"
+    )
+endif()
+
+if(NOT TARGET Wolfram::WSTP)
+    add_library(Wolfram::WSTP STATIC IMPORTED)
+    set_target_properties(Wolfram::WSTP PROPERTIES
+        IMPORTED_LOCATION "${WSTP_LIBRARY}"
+        INTERFACE_INCLUDE_DIRECTORIES "${WSTP_INCLUDE_DIR}"
+        INTERFACE_LINK_LIBRARIES "${WSTP_LINK_LIBRARIES}"
+    )
+endif()
+
+if(NOT TARGET Wolfram::LibraryLink)
+    add_library(Wolfram::LibraryLink INTERFACE IMPORTED)
+    set_target_properties(Wolfram::LibraryLink PROPERTIES
+        INTERFACE_INCLUDE_DIRECTORIES "${WOLFRAM_LIBRARYLINK_INCLUDE_DIR}"
+    )
+endif()
+"#;
+
+/// Convert linker flags into CMake link items.
+///
+/// A `-framework <name>` flag pair is kept together as a single item, so that
+/// CMake does not treat `<name>` as a separate library.
+fn link_libraries(flags: &[&str]) -> Vec<String> {
+    let mut items = Vec::new();
+    let mut flags = flags.iter();
+
+    while let Some(flag) = flags.next() {
+        match *flag {
+            "-framework" => match flags.next() {
+                Some(name) => items.push(format!("-framework {name}")),
+                None => items.push(flag.to_string()),
+            },
+            _ => items.push(flag.to_string()),
+        }
+    }
+
+    items
+}
+
+/// Format `path` as a CMake quoted argument.
+fn quoted(path: &Path) -> String {
+    let mut quoted = String::from("\"");
+
+    for char in path.display().to_string().chars() {
+        // Escape characters that have a special meaning in quoted arguments,
+        // including the `\` separator in Windows paths.
+        if let '\\' | '"' | '$' = char {
+            quoted.push('\\');
+        }
+        quoted.push(char);
+    }
+
+    quoted.push('"');
+    quoted
+}
+
+//======================================
+// Tests
+//======================================
+
+#[test]
+fn test_cmake_config() {
+    use wolfram_app_discovery::{artifacts, SystemID};
+
+    let config = |system_id| CMakeConfig {
+        app_directory: PathBuf::from("/opt/Wolfram"),
+        kernel: PathBuf::from("/opt/Wolfram/Executables/WolframKernel"),
+        wstp_include_dir: PathBuf::from("/opt/Wolfram/WSTP"),
+        wstp_library: PathBuf::from("/opt/Wolfram/WSTP/libWSTP64i4.a"),
+        wstp_link_flags: artifacts::wstp_static_library_link_flags(system_id).unwrap(),
+        library_link_include_dir: PathBuf::from("/opt/Wolfram/LibraryLink"),
+    };
+
+    let write = |config: CMakeConfig| {
+        let mut contents = Vec::new();
+        config.write(&mut contents).unwrap();
+        String::from_utf8(contents).unwrap()
+    };
+
+    let linux = write(config(SystemID::Linux_x86_64));
+
+    assert!(linux.contains(
+        "set(WSTP_LINK_LIBRARIES \"-lm;-lpthread;-lrt;-lstdc++;-ldl;-luuid\")"
+    ));
+    assert!(linux.contains("INTERFACE_LINK_LIBRARIES \"${WSTP_LINK_LIBRARIES}\""));
+
+    let macos = write(config(SystemID::MacOSX_ARM64));
+
+    assert!(macos.contains("set(WSTP_LINK_LIBRARIES \"-lc++;-framework Foundation\")"));
+}
@@ -0,0 +1,447 @@
+//! Minimal JSON values, used by `--error-format json` and the `serve` protocol.
+
+use std::fmt::{self, Display};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Null,
+    Bool(bool),
+    /// A number, stored as it was written so that it can be echoed back
+    /// unchanged (e.g. a JSON-RPC request `id`).
+    Number(String),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// Construct a [`Value::Object`] from `(key, value)` pairs.
+    pub fn object<'k>(fields: impl IntoIterator<Item = (&'k str, Value)>) -> Value {
+        Value::Object(
+            fields
+                .into_iter()
+                .map(|(key, value)| (key.to_owned(), value))
+                .collect(),
+        )
+    }
+
+    /// Construct a [`Value::String`], or [`Value::Null`] if `value` is `None`.
+    pub fn string_or_null(value: Option<&str>) -> Value {
+        match value {
+            Some(value) => Value::String(value.to_owned()),
+            None => Value::Null,
+        }
+    }
+
+    /// Get the value of the field `key`, if this is an object.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(fields) => fields
+                .iter()
+                .find(|(field, _)| field == key)
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+}
+
+//======================================
+// Parsing
+//======================================
+
+/// Parse a single JSON value, surrounded by optional whitespace.
+pub fn parse(input: &str) -> Result<Value, String> {
+    let mut parser = Parser {
+        input: input.as_bytes(),
+        offset: 0,
+    };
+
+    let value = parser.value()?;
+
+    parser.skip_whitespace();
+
+    if parser.offset != parser.input.len() {
+        return Err(parser.error("unexpected trailing characters"));
+    }
+
+    Ok(value)
+}
+
+struct Parser<'i> {
+    input: &'i [u8],
+    offset: usize,
+}
+
+impl<'i> Parser<'i> {
+    fn value(&mut self) -> Result<Value, String> {
+        self.skip_whitespace();
+
+        match self.peek() {
+            Some(b'n') => self.keyword("null", Value::Null),
+            Some(b't') => self.keyword("true", Value::Bool(true)),
+            Some(b'f') => self.keyword("false", Value::Bool(false)),
+            Some(b'"') => Ok(Value::String(self.string()?)),
+            Some(b'[') => self.array(),
+            Some(b'{') => self.object(),
+            Some(b'-' | b'0'..=b'9') => self.number(),
+            Some(_) => Err(self.error("unexpected character")),
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    fn keyword(&mut self, keyword: &str, value: Value) -> Result<Value, String> {
+        if !self.input[self.offset..].starts_with(keyword.as_bytes()) {
+            return Err(self.error("unexpected character"));
+        }
+
+        self.offset += keyword.len();
+
+        Ok(value)
+    }
+
+    /// Parse a number, which must match the grammar in
+    /// [RFC 8259](https://www.rfc-editor.org/rfc/rfc8259#section-6):
+    ///
+    /// ```text
+    /// number = [ "-" ] int [ "." 1*DIGIT ] [ ( "e" / "E" ) [ "-" / "+" ] 1*DIGIT ]
+    /// int    = "0" / ( %x31-39 *DIGIT )
+    /// ```
+    fn number(&mut self) -> Result<Value, String> {
+        let start = self.offset;
+
+        if self.peek() == Some(b'-') {
+            self.offset += 1;
+        }
+
+        match self.next() {
+            Some(b'0') => (),
+            Some(b'1'..=b'9') => self.digits(),
+            _ => return Err(self.error("invalid number")),
+        }
+
+        if self.peek() == Some(b'.') {
+            self.offset += 1;
+            self.expect_digits()?;
+        }
+
+        if let Some(b'e' | b'E') = self.peek() {
+            self.offset += 1;
+
+            if let Some(b'-' | b'+') = self.peek() {
+                self.offset += 1;
+            }
+
+            self.expect_digits()?;
+        }
+
+        let number = String::from_utf8_lossy(&self.input[start..self.offset]);
+
+        Ok(Value::Number(number.into_owned()))
+    }
+
+    /// Skip zero or more ASCII digits.
+    fn digits(&mut self) {
+        while let Some(b'0'..=b'9') = self.peek() {
+            self.offset += 1;
+        }
+    }
+
+    /// Skip one or more ASCII digits.
+    fn expect_digits(&mut self) -> Result<(), String> {
+        match self.peek() {
+            Some(b'0'..=b'9') => {
+                self.digits();
+                Ok(())
+            },
+            _ => Err(self.error("invalid number")),
+        }
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        self.expect(b'"')?;
+
+        let mut bytes = Vec::new();
+
+        loop {
+            match self.next() {
+                Some(b'"') => break,
+                Some(b'\\') => {
+                    let escaped = match self.next() {
+                        Some(b'"') => '"',
+                        Some(b'\\') => '\\',
+                        Some(b'/') => '/',
+                        Some(b'b') => '\u{8}',
+                        Some(b'f') => '\u{c}',
+                        Some(b'n') => '\n',
+                        Some(b'r') => '\r',
+                        Some(b't') => '\t',
+                        Some(b'u') => self.unicode_escape()?,
+                        _ => return Err(self.error("invalid string escape")),
+                    };
+
+                    let mut buffer = [0; 4];
+                    bytes.extend_from_slice(escaped.encode_utf8(&mut buffer).as_bytes());
+                },
+                Some(byte) => bytes.push(byte),
+                None => return Err(self.error("unterminated string")),
+            }
+        }
+
+        String::from_utf8(bytes).map_err(|_| self.error("invalid UTF-8 in string"))
+    }
+
+    /// Parse the `XXXX` following `\u`, including a trailing low surrogate
+    /// escape if `XXXX` is a high surrogate.
+    fn unicode_escape(&mut self) -> Result<char, String> {
+        let high = self.hex4()?;
+
+        let code = if (0xD800..0xDC00).contains(&high) {
+            self.expect(b'\\')?;
+            self.expect(b'u')?;
+            let low = self.hex4()?;
+
+            if !(0xDC00..0xE000).contains(&low) {
+                return Err(self.error("invalid unicode escape"));
+            }
+
+            0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+        } else {
+            high
+        };
+
+        char::from_u32(code).ok_or_else(|| self.error("invalid unicode escape"))
+    }
+
+    fn hex4(&mut self) -> Result<u32, String> {
+        let digits = self
+            .input
+            .get(self.offset..self.offset + 4)
+            .and_then(|digits| std::str::from_utf8(digits).ok())
+            .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+            .ok_or_else(|| self.error("invalid unicode escape"))?;
+
+        self.offset += 4;
+
+        Ok(digits)
+    }
+
+    fn array(&mut self) -> Result<Value, String> {
+        self.expect(b'[')?;
+
+        let mut elements = Vec::new();
+
+        self.skip_whitespace();
+        if self.peek() == Some(b']') {
+            self.offset += 1;
+            return Ok(Value::Array(elements));
+        }
+
+        loop {
+            elements.push(self.value()?);
+
+            self.skip_whitespace();
+            match self.next() {
+                Some(b',') => continue,
+                Some(b']') => return Ok(Value::Array(elements)),
+                _ => return Err(self.error("expected ',' or ']'")),
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<Value, String> {
+        self.expect(b'{')?;
+
+        let mut fields = Vec::new();
+
+        self.skip_whitespace();
+        if self.peek() == Some(b'}') {
+            self.offset += 1;
+            return Ok(Value::Object(fields));
+        }
+
+        loop {
+            self.skip_whitespace();
+            let key = self.string()?;
+
+            self.skip_whitespace();
+            self.expect(b':')?;
+
+            fields.push((key, self.value()?));
+
+            self.skip_whitespace();
+            match self.next() {
+                Some(b',') => continue,
+                Some(b'}') => return Ok(Value::Object(fields)),
+                _ => return Err(self.error("expected ',' or '}'")),
+            }
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(b' ' | b'\t' | b'\n' | b'\r') = self.peek() {
+            self.offset += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: u8) -> Result<(), String> {
+        match self.next() {
+            Some(byte) if byte == expected => Ok(()),
+            _ => Err(self.error(&format!("expected '{}'", char::from(expected)))),
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.offset).copied()
+    }
+
+    fn next(&mut self) -> Option<u8> {
+        let byte = self.peek()?;
+        self.offset += 1;
+        Some(byte)
+    }
+
+    fn error(&self, message: &str) -> String {
+        format!("{message} at byte {}", self.offset)
+    }
+}
+
+//======================================
+// Formatting
+//======================================
+
+impl Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Null => write!(f, "null"),
+            Value::Bool(value) => write!(f, "{value}"),
+            Value::Number(number) => write!(f, "{number}"),
+            Value::String(value) => write_string(f, value),
+            Value::Array(elements) => {
+                write!(f, "[")?;
+                for (index, element) in elements.iter().enumerate() {
+                    if index != 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{element}")?;
+                }
+                write!(f, "]")
+            },
+            Value::Object(fields) => {
+                write!(f, "{{")?;
+                for (index, (key, value)) in fields.iter().enumerate() {
+                    if index != 0 {
+                        write!(f, ",")?;
+                    }
+                    write_string(f, key)?;
+                    write!(f, ":{value}")?;
+                }
+                write!(f, "}}")
+            },
+        }
+    }
+}
+
+/// Write `value` as a JSON string literal.
+fn write_string(f: &mut fmt::Formatter, value: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for char in value.chars() {
+        match char {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            '\r' => write!(f, "\\r")?,
+            '\t' => write!(f, "\\t")?,
+            char if u32::from(char) < 0x20 => write!(f, "\\u{:04x}", u32::from(char))?,
+            char => write!(f, "{char}")?,
+        }
+    }
+    write!(f, "\"")
+}
+
+//======================================
+// Tests
+//======================================
+
+#[test]
+fn test_parse_numbers() {
+    for number in [
+        "0", "-0", "12", "-12", "1.5", "0.25", "1e5", "1E+5", "-1.5e-10",
+    ] {
+        assert_eq!(
+            parse(number),
+            Ok(Value::Number(number.to_owned())),
+            "{number}"
+        );
+    }
+
+    for number in [
+        "--", "-", "1e", "1e+", "1.", ".5", "01", "+1", "1.5.2", "1-2",
+    ] {
+        assert!(parse(number).is_err(), "{number}");
+    }
+}
+
+#[test]
+fn test_parse_strings() {
+    assert_eq!(
+        parse(r#""a\"b\\c\/d\b\f\n\r\t""#),
+        Ok(Value::String("a\"b\\c/d\u{8}\u{c}\n\r\t".to_owned()))
+    );
+    assert_eq!(parse(r#""\u00e9""#), Ok(Value::String("é".to_owned())));
+
+    // Surrogate pairs.
+    assert_eq!(
+        parse(r#""\ud83d\ude00""#),
+        Ok(Value::String("😀".to_owned()))
+    );
+    assert!(parse(r#""\ud83d\u0041""#).is_err());
+    assert!(parse(r#""\ud83d""#).is_err());
+    assert!(parse(r#""\ud83dA""#).is_err());
+    assert!(parse(r#""\ude00""#).is_err());
+
+    assert!(parse(r#""\x""#).is_err());
+    assert!(parse(r#""\u12""#).is_err());
+    assert!(parse(r#""unterminated"#).is_err());
+
+    // Strings round trip through Display.
+    let value = Value::String("quote \" backslash \\ newline \n nul \u{0}".to_owned());
+    assert_eq!(parse(&value.to_string()), Ok(value));
+}
+
+#[test]
+fn test_parse_nesting() {
+    let value = parse(r#" { "a" : [1, {"b": [null, true, false]}, []], "c": {} } "#);
+
+    assert_eq!(
+        value,
+        Ok(Value::object([
+            (
+                "a",
+                Value::Array(vec![
+                    Value::Number("1".to_owned()),
+                    Value::object([(
+                        "b",
+                        Value::Array(vec![
+                            Value::Null,
+                            Value::Bool(true),
+                            Value::Bool(false)
+                        ])
+                    )]),
+                    Value::Array(vec![]),
+                ])
+            ),
+            ("c", Value::Object(vec![])),
+        ]))
+    );
+
+    assert_eq!(
+        value.unwrap().to_string(),
+        r#"{"a":[1,{"b":[null,true,false]},[]],"c":{}}"#
+    );
+
+    assert!(parse("[1, 2").is_err());
+    assert!(parse("[1 2]").is_err());
+    assert!(parse(r#"{"a" 1}"#).is_err());
+    assert!(parse(r#"{"a": 1,}"#).is_err());
+    assert!(parse("[] []").is_err());
+}
@@ -1,19 +1,69 @@
+mod cmake;
+mod json;
 mod output;
+mod serve;
 
 
-use std::path::PathBuf;
+use std::{
+    ffi::OsString,
+    io::Write,
+    net::SocketAddr,
+    path::PathBuf,
+    process::{self, ExitCode},
+    time::Duration,
+};
 
-use clap::Parser;
+use clap::{CommandFactory, Parser};
 
-use wolfram_app_discovery::{self as wad, Filter, WolframApp, WolframAppType};
+use wolfram_app_discovery::{
+    self as wad, artifacts, build_scripts,
+    config::env_vars,
+    license::{self, LicenseServerSource},
+    pkg_config,
+    project_config::ProjectConfig,
+    property::Property,
+    verify::ComponentStatus,
+    DiscoveryOptions, ErrorCode, Filter, SystemID, WolframApp, WolframAppType,
+    WolframVersion,
+};
 
-use self::output::{Property, PropertyValue};
+use self::output::PropertyValue;
+
+/// Maximum time `doctor` waits when connecting to a license server, if
+/// `--timeout` is not specified.
+const LICENSE_SERVER_TIMEOUT: Duration = Duration::from_secs(5);
+
+// Exit codes. These are part of the stable command-line interface, and are
+// documented on `Args`.
+const EXIT_FAILURE: u8 = 1;
+const EXIT_NO_APPS_FOUND: u8 = 2;
+const EXIT_FILTER_MISMATCH: u8 = 3;
+const EXIT_INVALID_LAYOUT: u8 = 4;
+const EXIT_UNSUPPORTED_PLATFORM: u8 = 5;
+const EXIT_USAGE: u8 = 64;
 
 /// Find local installations of the Wolfram Language and Wolfram apps.
+///
+/// Exit status:
+///
+///   0   success
+///   1   other error, or some checks or values failed
+///   2   no Wolfram app (or app component) could be found
+///   3   the app specified by an environment variable does not match the
+///       required filter
+///   4   a file or directory is missing from the expected location within an
+///       app
+///   5   the operation is not supported on this platform
+///   64  invalid command-line arguments
 #[derive(Parser, Debug)]
+#[command(verbatim_doc_comment)]
 struct Args {
     #[clap(subcommand)]
     command: Command,
+
+    /// Format used to write errors to stderr.
+    #[arg(long, global = true, value_enum, default_value = "text")]
+    error_format: ErrorFormat,
 }
 
 #[derive(Parser, Debug)]
@@ -26,6 +76,9 @@ enum Command {
         #[clap(flatten)]
         discovery: DiscoveryOpts,
 
+        #[clap(flatten)]
+        system_id: SystemIdOpts,
+
         #[clap(flatten)]
         output: SingleOutputOpts,
     },
@@ -35,20 +88,302 @@ enum Command {
         #[clap(flatten)]
         discovery: DiscoveryOpts,
 
+        #[clap(flatten)]
+        system_id: SystemIdOpts,
+
         #[clap(flatten)]
         output: OutputOpts,
+
+        /// Instead of listing apps, report suspected duplicate app
+        /// registrations, and registrations whose app directory no longer
+        /// exists.
+        #[arg(long, conflicts_with_all = ["format", "properties", "all_properties", "print0", "system_id"])]
+        duplicates: bool,
     },
     /// Print information about a specified Wolfram application.
     #[clap(display_order(3))]
     Inspect {
-        app_dir: PathBuf,
+        /// Application directory, `$InstallationDirectory`, or a path inside
+        /// the application, e.g. the WolframKernel executable.
+        #[arg(value_hint = clap::ValueHint::AnyPath)]
+        path: PathBuf,
 
         #[clap(flatten)]
         opts: SingleOutputOpts,
 
+        #[clap(flatten)]
+        execution: ExecutionOpts,
+
         #[clap(flatten)]
         debug: Debug,
     },
+    /// Compare two Wolfram applications, printing the values that differ.
+    ///
+    /// Each value that differs is printed twice: prefixed by `-` for the first
+    /// app, and by `+` for the second app. This includes every property (see
+    /// `--property`), the WSTP SDKs and kernel binaries provided, and whether
+    /// each component checked by `doctor` is present.
+    #[clap(display_order(15))]
+    Compare {
+        /// The first app. Accepts the same paths as `inspect`.
+        #[arg(value_hint = clap::ValueHint::AnyPath)]
+        a: PathBuf,
+
+        /// The second app. Accepts the same paths as `inspect`.
+        #[arg(value_hint = clap::ValueHint::AnyPath)]
+        b: PathBuf,
+
+        /// Also print the values that are the same, prefixed by a space.
+        #[arg(long)]
+        all: bool,
+    },
+    /// Inspect, clear, or refresh the persistent discovery cache.
+    ///
+    /// The cache is used by commands run with `--use-cache`. It is not updated
+    /// automatically when Wolfram apps are installed or removed; run
+    /// `cache refresh` to rescan the system.
+    #[clap(display_order(16))]
+    Cache {
+        action: CacheAction,
+
+        #[clap(flatten)]
+        discovery: DiscoveryOpts,
+    },
+    /// Select the Wolfram app used in the current directory tree.
+    ///
+    /// Writes a `wolfram-app.toml` file into the current directory, replacing
+    /// any existing file. The app it specifies is used by `default` and by
+    /// programs that use the `wolfram-app-discovery` library in this directory
+    /// and its subdirectories, unless `WOLFRAM_APP_DIRECTORY` is set.
+    ///
+    /// If `--app-dir` is not specified, the best installed app matching the
+    /// filters is pinned, i.e. the first app printed by `list`.
+    #[clap(display_order(17))]
+    Pin {
+        /// Application directory, `$InstallationDirectory`, or a path inside
+        /// the application to pin.
+        #[arg(
+            long,
+            value_name = "PATH",
+            value_hint = clap::ValueHint::AnyPath,
+            conflicts_with_all = ["app_types", "system_id", "min_version"]
+        )]
+        app_dir: Option<PathBuf>,
+
+        #[clap(flatten)]
+        discovery: DiscoveryOpts,
+
+        #[clap(flatten)]
+        system_id: SystemIdOpts,
+
+        #[clap(flatten)]
+        version: VersionOpts,
+    },
+    /// Check the default Wolfram app and its configuration for common problems.
+    ///
+    /// This checks that a default app can be located, and that each configured
+    /// MathLM network license server is reachable.
+    #[clap(display_order(4))]
+    Doctor {
+        #[clap(flatten)]
+        discovery: DiscoveryOpts,
+    },
+    /// Suggest how to remove app registrations whose app directory no longer
+    /// exists.
+    ///
+    /// By default, the commands that would remove each orphaned registration
+    /// are printed, but not run.
+    #[clap(display_order(5))]
+    Clean {
+        #[clap(flatten)]
+        discovery: DiscoveryOpts,
+
+        /// Only print the commands that would remove each orphaned
+        /// registration. This is the default.
+        #[arg(long, conflicts_with = "apply")]
+        dry_run: bool,
+
+        /// Run the commands that remove each orphaned registration, after
+        /// asking for confirmation.
+        #[arg(long)]
+        apply: bool,
+
+        /// Don't ask for confirmation before removing registrations.
+        #[arg(long, requires = "apply")]
+        yes: bool,
+    },
+    /// Print the location of a single development resource.
+    ///
+    /// Environment variables like `WOLFRAM_LIBRARY_LINK_C_INCLUDES_DIRECTORY`
+    /// are checked before the default Wolfram app, using the same logic as
+    /// the `build_scripts` Rust API. The path is written without a trailing
+    /// newline, for use in Makefiles and CMake scripts.
+    #[clap(display_order(6))]
+    Locate {
+        /// The resource to locate.
+        #[arg(value_enum)]
+        resource: Resource,
+
+        #[clap(flatten)]
+        discovery: DiscoveryOpts,
+    },
+    /// Print the values build scripts need, in `KEY=VALUE` form.
+    ///
+    /// This is intended for non-Rust build systems. Each value is located
+    /// using the same logic as the `build_scripts` Rust API, so environment
+    /// variables like `WSTP_COMPILER_ADDITIONS_DIRECTORY` are respected.
+    ///
+    /// Values that could not be located are reported on stderr, and cause
+    /// this command to exit with a non-zero status.
+    #[clap(display_order(7))]
+    Config {
+        #[clap(flatten)]
+        discovery: DiscoveryOpts,
+    },
+    /// Write a CMake package configuration file for the default Wolfram app.
+    ///
+    /// The file sets the `WOLFRAM_KERNEL`, `WSTP_INCLUDE_DIR`, `WSTP_LIBRARY`,
+    /// and `WOLFRAM_LIBRARYLINK_INCLUDE_DIR` variables, and defines the
+    /// `Wolfram::Kernel`, `Wolfram::WSTP`, and `Wolfram::LibraryLink` imported
+    /// targets. Values are located using the same logic as the `config`
+    /// subcommand.
+    ///
+    /// If any value could not be located, the errors are reported on stderr
+    /// and no file is written.
+    #[clap(display_order(13))]
+    CmakeConfig {
+        /// File to write, e.g. `WolframConfig.cmake`. If not specified, the
+        /// file contents are written to stdout.
+        #[arg(long, value_name = "PATH", value_hint = clap::ValueHint::FilePath)]
+        out: Option<PathBuf>,
+
+        #[clap(flatten)]
+        discovery: DiscoveryOpts,
+    },
+    /// Write pkg-config files for the WSTP and LibraryLink SDKs of the
+    /// default Wolfram app.
+    ///
+    /// `wstp.pc` and `wolfram-librarylink.pc` are written to `--out-dir`. Add
+    /// that directory to `PKG_CONFIG_PATH` to use e.g.:
+    ///
+    /// `pkg-config --cflags --libs wstp`
+    ///
+    /// SDK locations are determined using the same logic as the `config`
+    /// subcommand. If an SDK could not be located, the error is reported on
+    /// stderr and no file is written for it.
+    #[clap(display_order(14))]
+    PkgConfig {
+        /// Directory to write the `.pc` files to.
+        #[arg(long, value_name = "DIR", default_value = ".", value_hint = clap::ValueHint::DirPath)]
+        out_dir: PathBuf,
+
+        #[clap(flatten)]
+        discovery: DiscoveryOpts,
+    },
+    /// Print shell commands that set environment variables for the default
+    /// Wolfram app.
+    ///
+    /// `WOLFRAM_APP_DIRECTORY`, `WSTP_COMPILER_ADDITIONS_DIRECTORY`, and
+    /// `WOLFRAM_LIBRARY_LINK_C_INCLUDES_DIRECTORY` are set. Evaluate the output
+    /// to apply it to the current shell, e.g.:
+    ///
+    /// `eval "$(wolfram-app-discovery env)"`
+    #[clap(display_order(8))]
+    Env {
+        /// Shell syntax to print.
+        #[arg(long, value_enum, default_value = "bash")]
+        shell: Shell,
+
+        #[clap(flatten)]
+        discovery: DiscoveryOpts,
+    },
+    /// Print a shell completion script.
+    ///
+    /// For example, to enable completions in the current bash session:
+    ///
+    /// `source <(wolfram-app-discovery completions bash)`
+    #[clap(display_order(9))]
+    Completions {
+        /// The shell to generate a completion script for.
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
+    },
+    /// Answer JSON-RPC queries from a periodically refreshed snapshot of the
+    /// discovered apps.
+    ///
+    /// Each line read from stdin is a JSON-RPC 2.0 request, and each response
+    /// is written to stdout as a single line. The supported methods are
+    /// `default`, `list`, and `refresh`. Apps are returned as objects mapping
+    /// each property name (see `--property`) to its value.
+    ///
+    /// This avoids repeating discovery for every query, e.g. on build machines
+    /// that run many jobs. The command exits when stdin is closed.
+    #[clap(display_order(10))]
+    Serve {
+        #[clap(flatten)]
+        discovery: DiscoveryOpts,
+
+        #[clap(flatten)]
+        system_id: SystemIdOpts,
+
+        /// How often to re-run discovery. `0` disables periodic refresh.
+        #[arg(long, value_name = "DURATION", value_parser = parse_duration, default_value = "5m")]
+        poll: Duration,
+
+        /// Serve Prometheus metrics over HTTP from `/metrics` on this address,
+        /// e.g. `127.0.0.1:9184`.
+        ///
+        /// Metrics include the number and versions of discovered apps, the
+        /// duration of the most recent discovery, and the number of queries
+        /// answered from the cached results.
+        #[arg(long, value_name = "ADDRESS")]
+        metrics_address: Option<SocketAddr>,
+
+        /// Periodically write a one-line summary of the metrics to stderr.
+        #[arg(long, value_name = "DURATION", value_parser = parse_duration)]
+        metrics_log_interval: Option<Duration>,
+    },
+    /// Print the location of the `WolframKernel` executable of the best
+    /// matching Wolfram app.
+    ///
+    /// The path is written without a trailing newline, so that it can be used
+    /// directly in shell scripts, e.g.:
+    ///
+    /// `"$(wolfram-app-discovery kernel)" -noprompt`
+    #[clap(display_order(11))]
+    Kernel {
+        #[clap(flatten)]
+        discovery: DiscoveryOpts,
+
+        #[clap(flatten)]
+        version: VersionOpts,
+    },
+    /// Run `wolframscript` (or `WolframKernel`) from the best matching Wolfram
+    /// app, with the specified arguments.
+    ///
+    /// Arguments after `--` are passed to the program, which inherits the
+    /// standard input, output, and error streams of this command. For example:
+    ///
+    /// `wolfram-app-discovery exec --min-version 14 -- -code '2+2'`
+    ///
+    /// `wolframscript` is passed `-local <KERNEL>`, so that the kernel from the
+    /// selected app is used. The exit status of the program is returned.
+    #[clap(display_order(12))]
+    Exec {
+        #[clap(flatten)]
+        discovery: DiscoveryOpts,
+
+        #[clap(flatten)]
+        version: VersionOpts,
+
+        /// Run the `WolframKernel` executable instead of `wolframscript`.
+        #[arg(long)]
+        kernel: bool,
+
+        /// Arguments passed to the program.
+        #[arg(last = true, value_name = "ARGS")]
+        args: Vec<OsString>,
+    },
     // For generating `docs/CommandLineHelp.md`.
     #[clap(hide = true)]
     PrintAllHelp {
@@ -74,10 +409,69 @@ struct DiscoveryOpts {
     )]
     app_types: Vec<WolframAppType>,
 
+    #[clap(flatten)]
+    execution: ExecutionOpts,
+
     #[clap(flatten)]
     debug: Debug,
 }
 
+/// CLI arguments that restrict discovered apps to those supporting a platform.
+#[derive(Debug, Clone)]
+#[derive(Parser)]
+struct SystemIdOpts {
+    /// Only include apps that provide kernel binaries and a WSTP SDK for this
+    /// `SystemID`, e.g. `MacOSX-ARM64` or `Linux-ARM64`.
+    #[arg(long, value_name = "ID", value_parser = parse_system_id)]
+    system_id: Option<SystemID>,
+}
+
+/// CLI arguments that restrict discovered apps to a range of versions.
+#[derive(Debug, Clone)]
+#[derive(Parser)]
+struct VersionOpts {
+    /// Only consider apps whose Wolfram Language version is at least this
+    /// version, e.g. `13.1` or `14.0.0`.
+    #[arg(long, value_name = "VERSION", value_parser = parse_wolfram_version)]
+    min_version: Option<WolframVersion>,
+}
+
+/// CLI arguments that bound the work done while discovering and inspecting apps.
+#[derive(Debug, Clone)]
+#[derive(Parser)]
+struct ExecutionOpts {
+    /// Maximum time to wait for any subprocess or network check, e.g. `5s` or
+    /// `500ms`.
+    ///
+    /// A number without a unit is interpreted as seconds.
+    #[arg(long, value_name = "DURATION", value_parser = parse_duration)]
+    timeout: Option<Duration>,
+
+    /// Do not spawn any subprocesses (e.g. `wolframscript`) or perform any
+    /// network checks.
+    #[arg(long)]
+    offline: bool,
+
+    /// Do not use `wolframscript` to locate the default app.
+    #[arg(long)]
+    no_wolframscript: bool,
+
+    /// Maximum number of nested directory levels read below each directory
+    /// that is scanned for installed apps.
+    #[arg(long, value_name = "LEVELS")]
+    max_scan_depth: Option<usize>,
+
+    /// Maximum number of directory entries read while scanning each directory
+    /// for installed apps.
+    #[arg(long, value_name = "COUNT")]
+    max_scan_entries: Option<usize>,
+
+    /// Load the apps found by operating system discovery from the persistent
+    /// discovery cache, creating it if necessary. See `cache`.
+    #[arg(long)]
+    use_cache: bool,
+}
+
 /// CLI arguments used by commands that work on a single app instance (i.e. `default`
 /// and `inspect`, but not `list`).
 #[derive(Debug, Clone)]
@@ -88,8 +482,27 @@ struct SingleOutputOpts {
     ///
     /// This is useful when using `wolfram-app-discovery` to initialize the
     /// value of variables in shell scripts or build scripts (e.g. CMake).
-    #[arg(long, value_name = "PROPERTY", conflicts_with_all = ["format", "properties", "all_properties"])]
-    raw_value: Option<Property>,
+    ///
+    /// This option can be repeated, or given a comma-separated list of
+    /// properties, to write several values separated by `--delimiter`.
+    #[arg(
+        long,
+        value_name = "PROPERTY",
+        value_delimiter = ',',
+        conflicts_with_all = ["format", "properties", "all_properties", "print0"]
+    )]
+    raw_value: Vec<Property>,
+
+    /// Separator written between the values of multiple `--raw-value`
+    /// properties. Defaults to a tab character.
+    #[arg(
+        long,
+        value_name = "STRING",
+        default_value = "\t",
+        hide_default_value = true,
+        requires = "raw_value"
+    )]
+    delimiter: String,
 
     #[clap(flatten)]
     output_opts: OutputOpts,
@@ -116,6 +529,68 @@ struct OutputOpts {
 
     #[arg(long, value_enum, default_value = "text")]
     format: OutputFormat,
+
+    /// Terminate each record with a NUL byte instead of a newline, and omit
+    /// the header row.
+    ///
+    /// Each app is written as a single record, using the `tsv` format unless
+    /// `--format csv` is specified. This output can be safely consumed by e.g.
+    /// `xargs -0`, even if values contain spaces or newlines.
+    #[arg(short = '0', long)]
+    print0: bool,
+}
+
+/// A development resource that can be located using the `locate` subcommand.
+#[derive(Debug, Clone)]
+#[derive(clap::ValueEnum)]
+enum Resource {
+    /// The `wstp.h` C header file.
+    WstpHeader,
+    /// The WSTP static library file.
+    WstpStaticLib,
+    /// The WSTP `CompilerAdditions` directory.
+    WstpCompilerAdditions,
+    /// The directory containing the LibraryLink C header files.
+    LibrarylinkIncludes,
+    /// The `WolframKernel` executable.
+    Kernel,
+    /// The `wolframscript` executable.
+    Wolframscript,
+    /// The `$InstallationDirectory` of the default app.
+    InstallationDirectory,
+}
+
+/// Action performed by the `cache` subcommand.
+#[derive(Debug, Clone)]
+#[derive(clap::ValueEnum)]
+enum CacheAction {
+    /// Print the location and age of the cache, and the cached apps.
+    Status,
+    /// Delete the cache.
+    Clear,
+    /// Discover installed apps and replace the contents of the cache.
+    Refresh,
+}
+
+/// Shell syntax used by the `env` subcommand.
+#[derive(Debug, Clone)]
+#[derive(clap::ValueEnum)]
+enum Shell {
+    /// POSIX-compatible shells, e.g. bash and zsh.
+    Bash,
+    Fish,
+    Powershell,
+}
+
+/// The format to use when writing errors.
+#[derive(Debug, Clone)]
+#[derive(clap::ValueEnum)]
+enum ErrorFormat {
+    /// Human-readable error message.
+    Text,
+    /// Single-line JSON object with the error `code`, `message`, and the
+    /// `resource`, `path`, and `environment_variable` involved (or `null`).
+    Json,
 }
 
 /// The format to use when writing output.
@@ -124,6 +599,8 @@ struct OutputOpts {
 enum OutputFormat {
     Text,
     CSV,
+    /// Tab-separated values.
+    Tsv,
 }
 
 #[derive(Debug, Clone)]
@@ -138,17 +615,120 @@ struct Debug {
 // main()
 //======================================
 
-fn main() -> Result<(), wad::Error> {
-    let Args { command } = Args::parse();
+fn main() -> ExitCode {
+    let Args {
+        command,
+        error_format,
+    } = match Args::try_parse() {
+        Ok(args) => args,
+        Err(err) => {
+            let _ = err.print();
+
+            // `--help` and `--version` are reported as errors by clap.
+            return match err.use_stderr() {
+                true => ExitCode::from(EXIT_USAGE),
+                false => ExitCode::SUCCESS,
+            };
+        },
+    };
+
+    let err = match run(command) {
+        Ok(()) => return ExitCode::SUCCESS,
+        Err(err) => err,
+    };
 
+    match error_format {
+        ErrorFormat::Text => eprintln!("error: {err}"),
+        ErrorFormat::Json => {
+            output::write_error_json(&mut std::io::stderr(), &err)
+                .expect("error writing JSON error");
+        },
+    }
+
+    ExitCode::from(exit_code(&err))
+}
+
+fn run(command: Command) -> Result<(), wad::Error> {
     match command {
-        Command::Default { discovery, output } => default(discovery, output),
-        Command::List { discovery, output } => list(discovery, output),
+        Command::Default {
+            discovery,
+            system_id,
+            output,
+        } => default(discovery, system_id, output),
+        Command::List {
+            discovery,
+            system_id,
+            output,
+            duplicates,
+        } => match duplicates {
+            true => list_duplicates(discovery),
+            false => list(discovery, system_id, output),
+        },
         Command::Inspect {
-            app_dir,
+            path,
             opts,
+            execution,
             debug,
-        } => inspect(app_dir, &opts, debug),
+        } => inspect(path, &opts, execution, debug),
+        Command::Compare { a, b, all } => compare(a, b, all),
+        Command::Cache { action, discovery } => cache(action, discovery),
+        Command::Pin {
+            app_dir,
+            discovery,
+            system_id,
+            version,
+        } => pin(app_dir, discovery, system_id, version),
+        Command::Doctor { discovery } => doctor(discovery),
+        Command::Locate {
+            resource,
+            discovery,
+        } => locate(resource, discovery),
+        Command::Config { discovery } => config(discovery),
+        Command::CmakeConfig { out, discovery } => cmake_config(out, discovery),
+        Command::PkgConfig { out_dir, discovery } => pkg_config(out_dir, discovery),
+        Command::Kernel { discovery, version } => kernel(discovery, version),
+        Command::Exec {
+            discovery,
+            version,
+            kernel,
+            args,
+        } => exec(discovery, version, kernel, args),
+        Command::Env { shell, discovery } => env(shell, discovery),
+        Command::Completions { shell } => {
+            let mut command = Args::command();
+            let name = command.get_name().to_owned();
+
+            // Generate the script into a buffer first, because
+            // `clap_complete::generate()` panics if writing fails (e.g. when
+            // stdout is a closed pipe).
+            let mut script = Vec::new();
+            clap_complete::generate(shell, &mut command, name, &mut script);
+
+            std::io::stdout().write_all(&script)?;
+
+            Ok(())
+        },
+        Command::Clean {
+            discovery,
+            dry_run,
+            apply,
+            yes,
+        } => clean(discovery, dry_run, apply, yes),
+        Command::Serve {
+            discovery,
+            system_id,
+            poll,
+            metrics_address,
+            metrics_log_interval,
+        } => serve(
+            discovery,
+            system_id,
+            serve::Settings {
+                poll,
+                metrics_address,
+                metrics_log_interval,
+            },
+        ),
         Command::PrintAllHelp { markdown } => {
             // This is a required argument for the time being.
             assert!(markdown);
@@ -166,57 +746,99 @@ fn main() -> Result<(), wad::Error> {
 
 fn default(
     discovery: DiscoveryOpts,
+    SystemIdOpts { system_id }: SystemIdOpts,
     single_output: SingleOutputOpts,
 ) -> Result<(), wad::Error> {
-    let DiscoveryOpts { app_types, debug } = discovery;
+    let DiscoveryOpts {
+        app_types,
+        execution,
+        debug,
+    } = discovery;
 
-    let filter = make_filter(app_types);
+    let filter = make_filter(app_types, system_id);
 
-    let app = WolframApp::try_default_with_filter(&filter)?;
+    let app =
+        WolframApp::try_default_with_filter_and_options(&filter, &execution.options())?;
 
     print_single_app(&app, &single_output, debug)?;
 
     Ok(())
 }
 
-fn list(discovery: DiscoveryOpts, output: OutputOpts) -> Result<(), wad::Error> {
-    let DiscoveryOpts { app_types, debug } = discovery;
-
-    let filter = make_filter(app_types);
+fn list(
+    discovery: DiscoveryOpts,
+    SystemIdOpts { system_id }: SystemIdOpts,
+    output: OutputOpts,
+) -> Result<(), wad::Error> {
+    let DiscoveryOpts {
+        app_types,
+        execution,
+        debug,
+    } = discovery;
 
-    let OutputOpts {
-        format,
-        properties,
-        all_properties,
-    } = &output;
+    let filter = make_filter(app_types, system_id);
 
-    let apps: Vec<WolframApp> = wad::discover_with_filter(&filter);
+    let apps: Vec<WolframApp> =
+        wad::discover_with_filter_and_options(&filter, &execution.options());
 
-    let properties: &[Property] = match all_properties {
-        true => Property::variants(),
-        false => properties,
-    };
-
-    match format {
-        OutputFormat::Text => {
+    match output.format {
+        OutputFormat::Text if !output.print0 => {
             for (index, app) in apps.iter().enumerate() {
                 println!("\nWolfram App #{}:\n", index);
                 print_app_info(app, &output, debug.debug)?;
             }
         },
-        OutputFormat::CSV => {
-            let mut stdout = std::io::stdout();
+        _ => print_records(&apps, &output),
+    }
 
-            output::write_csv_header(&mut stdout, properties)
-                .expect("error formatting CSV header");
 
-            for app in &apps {
-                output::write_csv_row(&mut stdout, app, properties)
-                    .expect("error formatting CSV row");
-            }
-        },
+    Ok(())
+}
+
+fn list_duplicates(discovery: DiscoveryOpts) -> Result<(), wad::Error> {
+    let DiscoveryOpts {
+        app_types,
+        execution,
+        debug: _,
+    } = discovery;
+
+    let report = wad::analyze_registrations(&execution.options());
+
+    let is_included =
+        |app: &WolframApp| app_types.is_empty() || app_types.contains(&app.app_type());
+
+    let describe = |app: &WolframApp| {
+        format!("{} ({:?})", app.app_directory().display(), app.app_type())
+    };
+
+    let mut found = false;
+
+    for group in report.duplicates() {
+        if !group.iter().any(is_included) {
+            continue;
+        }
+
+        found = true;
+
+        println!("duplicate registrations:");
+        for app in group {
+            println!("    {}", describe(app));
+        }
     }
 
+    for app in report.orphaned() {
+        if !is_included(app) {
+            continue;
+        }
+
+        found = true;
+
+        println!("orphaned registration: {}", describe(app));
+    }
+
+    if !found {
+        println!("no duplicate or orphaned app registrations found");
+    }
 
     Ok(())
 }
@@ -224,17 +846,673 @@ fn list(discovery: DiscoveryOpts, output: OutputOpts) -> Result<(), wad::Error>
 fn inspect(
     location: PathBuf,
     opts: &SingleOutputOpts,
+    execution: ExecutionOpts,
     debug: Debug,
 ) -> Result<(), wad::Error> {
-    let app = WolframApp::from_app_directory(location)?;
+    let app = WolframApp::from_path_with_options(location, &execution.options())?;
 
     print_single_app(&app, opts, debug)
 }
 
+fn compare(a: PathBuf, b: PathBuf, all: bool) -> Result<(), wad::Error> {
+    let a = WolframApp::from_path(a)?;
+    let b = WolframApp::from_path(b)?;
+
+    println!("--- {}", a.app_directory().display());
+    println!("+++ {}", b.app_directory().display());
+
+    let values_a = output::comparison_values(&a);
+    let values_b = output::comparison_values(&b);
+
+    for ((label, value_a), (_, value_b)) in values_a.iter().zip(&values_b) {
+        if value_a != value_b {
+            println!("-{label}: {value_a}");
+            println!("+{label}: {value_b}");
+        } else if all {
+            println!(" {label}: {value_a}");
+        }
+    }
+
+    Ok(())
+}
+
+fn cache(action: CacheAction, discovery: DiscoveryOpts) -> Result<(), wad::Error> {
+    match action {
+        CacheAction::Status => {
+            let Some(entry) = wad::cache::load()? else {
+                println!(
+                    "no discovery cache at {}",
+                    wad::cache::cache_file()?.display()
+                );
+                return Ok(());
+            };
+
+            println!("cache file: {}", entry.path().display());
+            println!("age: {}", format_age(entry.age()));
+            println!("apps: {}", entry.app_directories().len());
+
+            for dir in entry.app_directories() {
+                match dir.is_dir() {
+                    true => println!("    {}", dir.display()),
+                    false => println!("    {} (missing)", dir.display()),
+                }
+            }
+        },
+        CacheAction::Clear => {
+            let path = wad::cache::cache_file()?;
+
+            match wad::cache::clear()? {
+                true => println!("removed {}", path.display()),
+                false => println!("no discovery cache at {}", path.display()),
+            }
+        },
+        CacheAction::Refresh => {
+            let entry = wad::cache::refresh(&discovery.execution.options())?;
+
+            println!(
+                "cached {} apps in {}",
+                entry.app_directories().len(),
+                entry.path().display()
+            );
+        },
+    }
+
+    Ok(())
+}
+
+fn pin(
+    app_dir: Option<PathBuf>,
+    discovery: DiscoveryOpts,
+    SystemIdOpts { system_id }: SystemIdOpts,
+    VersionOpts { min_version }: VersionOpts,
+) -> Result<(), wad::Error> {
+    let DiscoveryOpts {
+        app_types,
+        execution,
+        debug: _,
+    } = discovery;
+
+    let app = match app_dir {
+        Some(path) => WolframApp::from_path(path)?,
+        None => {
+            let filter = Filter {
+                min_version,
+                ..make_filter(app_types, system_id)
+            };
+
+            let apps =
+                wad::discover_with_filter_and_options(&filter, &execution.options());
+
+            match apps.into_iter().next() {
+                Some(app) => app,
+                None => {
+                    eprintln!("error: no installed Wolfram app matches the filters");
+                    std::process::exit(EXIT_NO_APPS_FOUND.into());
+                },
+            }
+        },
+    };
+
+    let config = ProjectConfig::pin(&std::env::current_dir()?, &app)?;
+
+    println!(
+        "pinned {} in {}",
+        app.app_directory().display(),
+        config.path().display()
+    );
+
+    Ok(())
+}
+
+fn doctor(discovery: DiscoveryOpts) -> Result<(), wad::Error> {
+    let DiscoveryOpts {
+        app_types,
+        execution,
+        debug: _,
+    } = discovery;
+
+    let filter = make_filter(app_types, None);
+
+    let mut failed = false;
+
+    let app = match WolframApp::try_default_with_filter_and_options(
+        &filter,
+        &execution.options(),
+    ) {
+        Ok(app) => {
+            println!("ok:    default app: {}", app.app_directory().display());
+
+            for check in app.verify().checks() {
+                let component = check.component();
+
+                match check.status() {
+                    ComponentStatus::Present(path) => {
+                        println!("ok:    {component}: {}", path.display())
+                    },
+                    ComponentStatus::Missing(err) => {
+                        println!("error: {component}: {err}");
+                        failed = true;
+                    },
+                    ComponentStatus::NotApplicable => {
+                        println!("skip:  {component}: not provided by this app type")
+                    },
+                }
+            }
+
+            Some(app)
+        },
+        Err(err) => {
+            println!("error: default app: {err}");
+            failed = true;
+            None
+        },
+    };
+
+    for server in license::license_servers(app.as_ref()) {
+        let source = match server.source() {
+            LicenseServerSource::Env { variable } => variable.to_string(),
+            LicenseServerSource::Mathpass(path) => path.display().to_string(),
+            other => format!("{other:?}"),
+        };
+
+        if execution.offline {
+            println!("skip:  license server {server} (from {source}): offline");
+            continue;
+        }
+
+        let timeout = execution.timeout.unwrap_or(LICENSE_SERVER_TIMEOUT);
+
+        match server.check_reachable(timeout) {
+            Ok(()) => println!("ok:    license server {server} (from {source})"),
+            Err(err) => {
+                println!("error: license server {server} (from {source}): {err}");
+                failed = true;
+            },
+        }
+    }
+
+    // Report capabilities that are not implemented on this platform, and so
+    // may have silently degraded the results above.
+    for warning in wad::discovery_warnings() {
+        println!("warn:  {warning}");
+    }
+
+    if failed {
+        std::process::exit(EXIT_FAILURE.into());
+    }
+
+    Ok(())
+}
+
+fn locate(resource: Resource, discovery: DiscoveryOpts) -> Result<(), wad::Error> {
+    let DiscoveryOpts {
+        app_types,
+        execution,
+        debug: _,
+    } = discovery;
+
+    let filter = make_filter(app_types, None);
+
+    let app =
+        WolframApp::try_default_with_filter_and_options(&filter, &execution.options());
+
+    // Resources that can be configured using an environment variable don't
+    // require that a default app can be found.
+    let path: PathBuf = match resource {
+        Resource::WstpHeader => {
+            build_scripts::wstp_c_header_path(app.as_ref().ok())?.into_path_buf()
+        },
+        Resource::WstpStaticLib => {
+            build_scripts::wstp_static_library_path(app.as_ref().ok())?.into_path_buf()
+        },
+        Resource::WstpCompilerAdditions => {
+            build_scripts::wstp_compiler_additions_directory(app.as_ref().ok())?
+                .into_path_buf()
+        },
+        Resource::LibrarylinkIncludes => {
+            build_scripts::library_link_c_includes_directory(app.as_ref().ok())?
+                .into_path_buf()
+        },
+        Resource::Kernel => {
+            build_scripts::kernel_executable_path(app.as_ref().ok())?.into_path_buf()
+        },
+        Resource::Wolframscript => {
+            build_scripts::wolframscript_executable_path(app.as_ref().ok())?
+                .into_path_buf()
+        },
+        Resource::InstallationDirectory => app?.installation_directory(),
+    };
+
+    // NOTE: Use print! instead of println! to avoid printing a newline, like
+    //       `--raw-value`.
+    print!("{}", path.display());
+
+    Ok(())
+}
+
+fn cmake_config(
+    out: Option<PathBuf>,
+    discovery: DiscoveryOpts,
+) -> Result<(), wad::Error> {
+    let DiscoveryOpts {
+        app_types,
+        execution,
+        debug: _,
+    } = discovery;
+
+    let filter = make_filter(app_types, None);
+
+    let app =
+        WolframApp::try_default_with_filter_and_options(&filter, &execution.options())?;
+
+    let mut failed = false;
+
+    let wstp_link_flags = app
+        .target_system_id()
+        .and_then(artifacts::wstp_static_library_link_flags)
+        .unwrap_or_else(|err| {
+            eprintln!("error: unable to determine WSTP_LINK_LIBRARIES: {err}");
+            failed = true;
+            &[]
+        });
+
+    let mut locate = |key: &str, value: Result<PathBuf, wad::Error>| match value {
+        Ok(path) => path,
+        Err(err) => {
+            eprintln!("error: unable to locate {key}: {err}");
+            failed = true;
+            PathBuf::new()
+        },
+    };
+
+    let config = cmake::CMakeConfig {
+        app_directory: app.app_directory(),
+        kernel: locate("WOLFRAM_KERNEL", app.kernel_executable_path()),
+        wstp_include_dir: locate(
+            "WSTP_INCLUDE_DIR",
+            build_scripts::wstp_compiler_additions_directory(Some(&app))
+                .map(build_scripts::Discovery::into_path_buf),
+        ),
+        wstp_library: locate(
+            "WSTP_LIBRARY",
+            build_scripts::wstp_static_library_path(Some(&app))
+                .map(build_scripts::Discovery::into_path_buf),
+        ),
+        wstp_link_flags,
+        library_link_include_dir: locate(
+            "WOLFRAM_LIBRARYLINK_INCLUDE_DIR",
+            build_scripts::library_link_c_includes_directory(Some(&app))
+                .map(build_scripts::Discovery::into_path_buf),
+        ),
+    };
+
+    if failed {
+        std::process::exit(EXIT_FAILURE.into());
+    }
+
+    match out {
+        Some(out) => {
+            let mut contents = Vec::new();
+            config.write(&mut contents)?;
+            std::fs::write(out, contents)?;
+        },
+        None => config.write(&mut std::io::stdout())?,
+    }
+
+    Ok(())
+}
+
+fn pkg_config(out_dir: PathBuf, discovery: DiscoveryOpts) -> Result<(), wad::Error> {
+    let DiscoveryOpts {
+        app_types,
+        execution,
+        debug: _,
+    } = discovery;
+
+    let filter = make_filter(app_types, None);
+
+    // SDKs configured using environment variables don't require that a
+    // default app can be found.
+    let app =
+        WolframApp::try_default_with_filter_and_options(&filter, &execution.options())
+            .ok();
+    let app = app.as_ref();
+
+    let mut failed = false;
+
+    for (name, file) in [
+        ("wstp", pkg_config::wstp(app)),
+        ("wolfram-librarylink", pkg_config::library_link(app)),
+    ] {
+        match file {
+            Ok(file) => {
+                let path = out_dir.join(file.file_name());
+                std::fs::write(&path, file.to_string())?;
+                println!("wrote {}", path.display());
+            },
+            Err(err) => {
+                eprintln!("error: unable to generate {name}.pc: {err}");
+                failed = true;
+            },
+        }
+    }
+
+    if failed {
+        std::process::exit(EXIT_FAILURE.into());
+    }
+
+    Ok(())
+}
+
+fn kernel(discovery: DiscoveryOpts, version: VersionOpts) -> Result<(), wad::Error> {
+    let app = versioned_default(discovery, version)?;
+
+    // NOTE: Use print! instead of println! to avoid printing a newline, like
+    //       `locate`.
+    print!("{}", app.kernel_executable_path()?.display());
+
+    Ok(())
+}
+
+fn exec(
+    discovery: DiscoveryOpts,
+    version: VersionOpts,
+    kernel: bool,
+    args: Vec<OsString>,
+) -> Result<(), wad::Error> {
+    let app = versioned_default(discovery, version)?;
+
+    let kernel_path = app.kernel_executable_path()?;
+
+    let mut command = match kernel {
+        true => process::Command::new(kernel_path),
+        false => {
+            let mut command = process::Command::new(app.wolframscript_executable_path()?);
+            command.arg("-local").arg(kernel_path);
+            command
+        },
+    };
+
+    command.args(args);
+
+    // On Unix, replace this process, so that signals are delivered directly to
+    // the program.
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+
+        // exec() only returns if an error occurred.
+        return Err(command.exec().into());
+    }
+
+    #[allow(unreachable_code)]
+    {
+        let status = command.status()?;
+
+        // A program terminated by a signal has no exit code.
+        process::exit(status.code().unwrap_or(EXIT_FAILURE.into()))
+    }
+}
+
+/// Find the default app matching `discovery` and `version`.
+fn versioned_default(
+    discovery: DiscoveryOpts,
+    version: VersionOpts,
+) -> Result<WolframApp, wad::Error> {
+    let DiscoveryOpts {
+        app_types,
+        execution,
+        debug: _,
+    } = discovery;
+
+    let VersionOpts { min_version } = version;
+
+    let filter = Filter {
+        min_version,
+        ..make_filter(app_types, None)
+    };
+
+    WolframApp::try_default_with_filter_and_options(&filter, &execution.options())
+}
+
+fn config(discovery: DiscoveryOpts) -> Result<(), wad::Error> {
+    let DiscoveryOpts {
+        app_types,
+        execution,
+        debug: _,
+    } = discovery;
+
+    let filter = make_filter(app_types, None);
+
+    let app =
+        WolframApp::try_default_with_filter_and_options(&filter, &execution.options())
+            .ok();
+    let app = app.as_ref();
+
+    let values = [
+        (
+            env_vars::WSTP_COMPILER_ADDITIONS_DIRECTORY,
+            build_scripts::wstp_compiler_additions_directory(app),
+        ),
+        (
+            env_vars::WOLFRAM_LIBRARY_LINK_C_INCLUDES_DIRECTORY,
+            build_scripts::library_link_c_includes_directory(app),
+        ),
+        (
+            "WSTP_STATIC_LIBRARY",
+            build_scripts::wstp_static_library_path(app),
+        ),
+        (
+            "WSTP_SHARED_LIBRARY",
+            build_scripts::wstp_shared_library_path(app),
+        ),
+    ];
+
+    let mut failed = false;
+
+    for (key, value) in values {
+        match value {
+            Ok(discovery) => println!("{key}={}", discovery.into_path_buf().display()),
+            Err(err) => {
+                eprintln!("error: unable to locate {key}: {err}");
+                failed = true;
+            },
+        }
+    }
+
+    if failed {
+        std::process::exit(EXIT_FAILURE.into());
+    }
+
+    Ok(())
+}
+
+fn env(shell: Shell, discovery: DiscoveryOpts) -> Result<(), wad::Error> {
+    let DiscoveryOpts {
+        app_types,
+        execution,
+        debug: _,
+    } = discovery;
+
+    let filter = make_filter(app_types, None);
+
+    let app =
+        WolframApp::try_default_with_filter_and_options(&filter, &execution.options())?;
+
+    let values = [
+        (env_vars::WOLFRAM_APP_DIRECTORY, Ok(app.app_directory())),
+        (
+            env_vars::WSTP_COMPILER_ADDITIONS_DIRECTORY,
+            app.target_wstp_sdk()
+                .map(|sdk| sdk.wstp_compiler_additions_directory()),
+        ),
+        (
+            env_vars::WOLFRAM_LIBRARY_LINK_C_INCLUDES_DIRECTORY,
+            app.library_link_c_includes_directory(),
+        ),
+    ];
+
+    let mut failed = false;
+
+    for (key, value) in values {
+        let path = match value {
+            Ok(path) => path,
+            Err(err) => {
+                eprintln!("error: unable to locate {key}: {err}");
+                failed = true;
+                continue;
+            },
+        };
+
+        let value = path.display().to_string();
+
+        match shell {
+            Shell::Bash => {
+                println!("export {key}='{}'", value.replace('\'', r"'\''"))
+            },
+            Shell::Fish => println!(
+                "set -gx {key} '{}'",
+                value.replace('\\', r"\\").replace('\'', r"\'")
+            ),
+            Shell::Powershell => {
+                println!("$env:{key} = '{}'", value.replace('\'', "''"))
+            },
+        }
+    }
+
+    if failed {
+        std::process::exit(EXIT_FAILURE.into());
+    }
+
+    Ok(())
+}
+
+fn clean(
+    discovery: DiscoveryOpts,
+    dry_run: bool,
+    apply: bool,
+    yes: bool,
+) -> Result<(), wad::Error> {
+    let DiscoveryOpts {
+        app_types,
+        execution,
+        debug: _,
+    } = discovery;
+
+    let report = wad::analyze_registrations(&execution.options());
+
+    let orphaned: Vec<&WolframApp> = report
+        .orphaned()
+        .iter()
+        .filter(|app| app_types.is_empty() || app_types.contains(&app.app_type()))
+        .collect();
+
+    if orphaned.is_empty() {
+        println!("no orphaned app registrations found");
+        return Ok(());
+    }
+
+    let mut registrations = Vec::new();
+
+    for app in orphaned {
+        println!("orphaned registration: {}", app.app_directory().display());
+
+        match app.registration() {
+            Some(registration) => {
+                println!("    {}", registration.removal_command().join(" "));
+                registrations.push(registration);
+            },
+            None => println!("    (no known command to remove this registration)"),
+        }
+    }
+
+    if registrations.is_empty() {
+        return Ok(());
+    }
+
+    // `--dry-run` is the default if `--apply` is not specified.
+    if dry_run || !apply {
+        println!(
+            "\ndry run: {} registrations would be removed; use --apply to remove them",
+            registrations.len()
+        );
+        return Ok(());
+    }
+
+    if !yes && !confirm(&format!("Remove {} registrations?", registrations.len())) {
+        println!("aborted");
+        return Ok(());
+    }
+
+    let mut failed = false;
+
+    for registration in registrations {
+        match registration.remove() {
+            Ok(()) => println!("removed: {}", registration.removal_command().join(" ")),
+            Err(err) => {
+                eprintln!("error: {err}");
+                failed = true;
+            },
+        }
+    }
+
+    if failed {
+        std::process::exit(EXIT_FAILURE.into());
+    }
+
+    Ok(())
+}
+
+fn serve(
+    discovery: DiscoveryOpts,
+    SystemIdOpts { system_id }: SystemIdOpts,
+    settings: serve::Settings,
+) -> Result<(), wad::Error> {
+    let DiscoveryOpts {
+        app_types,
+        execution,
+        debug: _,
+    } = discovery;
+
+    let filter = make_filter(app_types, system_id);
+
+    serve::serve(filter, execution.options(), settings)?;
+
+    Ok(())
+}
+
 //======================================
 // Utility functions
 //======================================
 
+/// Exit code used when a command fails with `err`.
+fn exit_code(err: &wad::Error) -> u8 {
+    match err.code() {
+        ErrorCode::Undiscoverable => EXIT_NO_APPS_FOUND,
+        ErrorCode::FilterMismatch => EXIT_FILTER_MISMATCH,
+        ErrorCode::UnexpectedLayout => EXIT_INVALID_LAYOUT,
+        ErrorCode::UnsupportedPlatform => EXIT_UNSUPPORTED_PLATFORM,
+        ErrorCode::Io | ErrorCode::Other => EXIT_FAILURE,
+        // Error categories added in future versions of the library.
+        _ => EXIT_FAILURE,
+    }
+}
+
+/// Ask the user a yes/no question on stdin, defaulting to "no".
+fn confirm(question: &str) -> bool {
+    use std::io::Write;
+
+    print!("{question} [y/N] ");
+    let _ = std::io::stdout().flush();
+
+    let mut answer = String::new();
+    if std::io::stdin().read_line(&mut answer).is_err() {
+        return false;
+    }
+
+    matches!(answer.trim(), "y" | "Y" | "yes")
+}
+
 fn print_single_app(
     app: &WolframApp,
     opts: &SingleOutputOpts,
@@ -242,14 +1520,20 @@ fn print_single_app(
 ) -> Result<(), wad::Error> {
     let SingleOutputOpts {
         raw_value,
+        delimiter,
         output_opts,
     } = opts;
 
-    if let Some(prop) = raw_value {
+    if !raw_value.is_empty() {
+        let values: Vec<String> = raw_value
+            .iter()
+            .map(|prop| PropertyValue(app, *prop).to_string())
+            .collect();
+
         // NOTE: Use print! instead of println! to avoid printing a newline,
         //       which would require the user to remove the newline in some
         //       use-cases.
-        print!("{}", PropertyValue(&app, prop.clone()));
+        print!("{}", values.join(delimiter));
 
         return Ok(());
     }
@@ -262,51 +1546,160 @@ fn print_app_info(
     opts: &OutputOpts,
     debug: bool,
 ) -> Result<(), wad::Error> {
-    let OutputOpts {
-        format,
-        properties,
-        all_properties,
-    } = opts;
-
     if debug {
         println!("{:#?}", app);
         return Ok(());
     }
 
-    let properties: &[Property] = match all_properties {
-        true => Property::variants(),
-        false => properties,
-    };
-
-    match format {
-        OutputFormat::Text => {
-            for prop in properties {
-                let value = PropertyValue(app, prop.clone());
+    match opts.format {
+        OutputFormat::Text if !opts.print0 => {
+            for prop in opts.properties() {
+                let value = PropertyValue(app, *prop);
 
                 let name = format!("{prop}:");
 
                 println!("{name:<width$} {value}", width = 35);
             }
         },
+        _ => print_records(std::slice::from_ref(app), opts),
+    }
+
+    Ok(())
+}
+
+/// Print `apps` as CSV or TSV records, one record per app.
+fn print_records(apps: &[WolframApp], opts: &OutputOpts) {
+    let properties = opts.properties();
+
+    let terminator = match opts.print0 {
+        true => '\0',
+        false => '\n',
+    };
+
+    let mut stdout = std::io::stdout();
+
+    match opts.format {
         OutputFormat::CSV => {
-            let mut stdout = std::io::stdout();
+            if !opts.print0 {
+                output::write_csv_header(&mut stdout, properties)
+                    .expect("error formatting CSV header");
+            }
 
-            output::write_csv_header(&mut stdout, properties)
-                .expect("error formatting CSV header");
-            output::write_csv_row(&mut stdout, app, properties)
-                .expect("error formatting CSV row");
+            for app in apps {
+                output::write_csv_row(&mut stdout, app, properties, terminator)
+                    .expect("error formatting CSV row");
+            }
+        },
+        // `--print0` implies TSV unless another tabular format was specified.
+        OutputFormat::Tsv | OutputFormat::Text => {
+            if !opts.print0 {
+                output::write_tsv_header(&mut stdout, properties)
+                    .expect("error formatting TSV header");
+            }
+
+            for app in apps {
+                output::write_tsv_row(&mut stdout, app, properties, terminator)
+                    .expect("error formatting TSV row");
+            }
         },
     }
+}
 
-    Ok(())
+impl OutputOpts {
+    /// The properties to output.
+    fn properties(&self) -> &[Property] {
+        match self.all_properties {
+            true => Property::variants(),
+            false => &self.properties,
+        }
+    }
+}
+
+impl ExecutionOpts {
+    fn options(&self) -> DiscoveryOptions {
+        let ExecutionOpts {
+            timeout,
+            offline,
+            no_wolframscript,
+            max_scan_depth,
+            max_scan_entries,
+            use_cache,
+        } = *self;
+
+        let mut options = DiscoveryOptions::default();
+        options.timeout = timeout;
+        options.offline = offline;
+        options.skip_wolframscript = no_wolframscript;
+        options.max_scan_depth = max_scan_depth;
+        options.max_scan_entries = max_scan_entries;
+        options.use_cache = use_cache;
+        options
+    }
 }
 
-fn make_filter(app_types: Vec<WolframAppType>) -> Filter {
+/// Format `age` using its largest whole unit, e.g. `3 hours`.
+fn format_age(age: Duration) -> String {
+    let secs = age.as_secs();
+
+    let (count, unit) = match secs {
+        0..=59 => (secs, "second"),
+        60..=3599 => (secs / 60, "minute"),
+        3600..=86399 => (secs / 3600, "hour"),
+        _ => (secs / 86400, "day"),
+    };
+
+    match count {
+        1 => format!("1 {unit}"),
+        _ => format!("{count} {unit}s"),
+    }
+}
+
+/// Parse a duration like `5s`, `500ms`, or `2m`.
+fn parse_duration(value: &str) -> Result<Duration, String> {
+    let (number, unit_secs) = if let Some(number) = value.strip_suffix("ms") {
+        (number, 0.001)
+    } else if let Some(number) = value.strip_suffix('s') {
+        (number, 1.0)
+    } else if let Some(number) = value.strip_suffix('m') {
+        (number, 60.0)
+    } else {
+        (value, 1.0)
+    };
+
+    let number: f64 = number
+        .trim()
+        .parse()
+        .map_err(|_| format!("invalid duration: {value:?}"))?;
+
+    Duration::try_from_secs_f64(number * unit_secs)
+        .map_err(|err| format!("invalid duration: {value:?}: {err}"))
+}
+
+fn parse_system_id(value: &str) -> Result<SystemID, String> {
+    value
+        .parse()
+        .map_err(|()| format!("unknown SystemID: {value:?}"))
+}
+
+fn parse_wolfram_version(value: &str) -> Result<WolframVersion, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid Wolfram Language version: {value:?}"))
+}
+
+fn make_filter(app_types: Vec<WolframAppType>, system_id: Option<SystemID>) -> Filter {
     let app_types = if app_types.is_empty() {
         None
     } else {
         Some(app_types)
     };
 
-    Filter { app_types }
+    Filter {
+        app_types,
+        min_version: None,
+        kernel_system_id: system_id,
+        requires_front_end: false,
+        requires_activation: false,
+        wstp_sdk_system_id: system_id,
+    }
 }
@@ -3,45 +3,12 @@ use std::{
     io,
 };
 
-use wolfram_app_discovery::WolframApp;
-
-/// A property of a Wolfram installation that can be discovered.
-#[derive(Debug, Clone, PartialEq)]
-#[derive(clap::ValueEnum)]
-pub enum Property {
-    /// [`WolframAppType`] value describing the installation.
-    ///
-    /// [`WolframAppType`]: https://docs.rs/wolfram-app-discovery/latest/wolfram_app_discovery/enum.WolframAppType.html
-    AppType,
-
-    AppDirectory,
-
-    /// [`WolframVersion`] value of the installation.
-    ///
-    /// [`WolframVersion`]: https://docs.rs/wolfram-app-discovery/latest/wolfram_app_discovery/struct.WolframVersion.html
-    WolframVersion,
-
-    /// [`$InstallationDirectory`] value of the installation.
-    ///
-    /// [`$InstallationDirectory`]: https://reference.wolfram.com/language/ref/$InstallationDirectory
-    InstallationDirectory,
-
-    /// Wolfram *LibraryLink* C includes directory
-    LibraryLinkCIncludesDirectory,
-
-    /// Location of the [`WolframKernel`] executable.
-    ///
-    /// [`WolframKernel`]: https://reference.wolfram.com/language/ref/program/WolframKernel.html
-    KernelExecutablePath,
-
-    /// Location of the [`wolframscript`] executable.
-    ///
-    /// [`wolframscript`]: https://reference.wolfram.com/language/ref/program/wolframscript.html
-    WolframScriptExecutablePath,
-
-    /// Location of the WSTP SDK 'CompilerAdditions' directory.
-    WstpCompilerAdditionsDirectory,
-}
+use wolfram_app_discovery::{
+    self as wad, license, property::Property, verify::ComponentStatus, SystemID,
+    WolframApp,
+};
+
+use crate::json::Value;
 
 /// Represents the value of the specified property on the given app for the
 /// purposes of formatting.
@@ -50,45 +17,6 @@ pub enum Property {
 ///
 pub struct PropertyValue<'app>(pub &'app WolframApp, pub Property);
 
-//==========================================================
-// Impls
-//==========================================================
-
-impl Property {
-    pub const fn variants() -> &'static [Property] {
-        // NOTE: Whenever the match statement below causes a compile time failure
-        //       because a variant has been added, update the returned slice to
-        //       include the new variant.
-        if false {
-            #[allow(unused_variables)]
-            let property: Property = unreachable!();
-
-            #[allow(unreachable_code)]
-            match property {
-                Property::AppType
-                | Property::WolframVersion
-                | Property::AppDirectory
-                | Property::InstallationDirectory
-                | Property::KernelExecutablePath
-                | Property::WolframScriptExecutablePath
-                | Property::WstpCompilerAdditionsDirectory
-                | Property::LibraryLinkCIncludesDirectory => unreachable!(),
-            }
-        }
-
-        &[
-            Property::AppType,
-            Property::WolframVersion,
-            Property::AppDirectory,
-            Property::InstallationDirectory,
-            Property::KernelExecutablePath,
-            Property::WolframScriptExecutablePath,
-            Property::WstpCompilerAdditionsDirectory,
-            Property::LibraryLinkCIncludesDirectory,
-        ]
-    }
-}
-
 //==========================================================
 // CSV
 //==========================================================
@@ -106,10 +34,12 @@ pub fn write_csv_header(
     writeln!(fmt, "{header}")
 }
 
+/// Write the CSV record for `app`, followed by `terminator`.
 pub fn write_csv_row(
     fmt: &mut dyn io::Write,
     app: &WolframApp,
     properties: &[Property],
+    terminator: char,
 ) -> io::Result<()> {
     for (index, prop) in properties.iter().cloned().enumerate() {
         let value = format!("{}", PropertyValue(app, prop));
@@ -124,100 +54,228 @@ pub fn write_csv_row(
         }
     }
 
-    write!(fmt, "\n")
+    write!(fmt, "{terminator}")
+}
+
+//==========================================================
+// TSV
+//==========================================================
+
+pub fn write_tsv_header(
+    fmt: &mut dyn io::Write,
+    properties: &[Property],
+) -> io::Result<()> {
+    let header: String = properties
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<String>>()
+        .join("\t");
+
+    writeln!(fmt, "{header}")
+}
+
+/// Write the TSV record for `app`, followed by `terminator`.
+///
+/// Values are written unescaped, so that paths containing backslashes (e.g. on
+/// Windows) can be used as-is.
+pub fn write_tsv_row(
+    fmt: &mut dyn io::Write,
+    app: &WolframApp,
+    properties: &[Property],
+    terminator: char,
+) -> io::Result<()> {
+    let row: String = properties
+        .iter()
+        .map(|prop| PropertyValue(app, *prop).to_string())
+        .collect::<Vec<String>>()
+        .join("\t");
+
+    write!(fmt, "{row}{terminator}")
+}
+
+//==========================================================
+// JSON
+//==========================================================
+
+/// Write `err` as a single-line JSON object.
+///
+/// Fields that don't apply to `err` are written as `null`, so that every error
+/// object has the same set of fields.
+pub fn write_error_json(fmt: &mut dyn io::Write, err: &wad::Error) -> io::Result<()> {
+    let json = Value::object([("error", error_value(err))]);
+
+    writeln!(fmt, "{json}")
+}
+
+/// JSON representation of `err`.
+pub fn error_value(err: &wad::Error) -> Value {
+    let path = err.path().map(|path| path.display().to_string());
+
+    Value::object([
+        ("code", Value::String(err.code().as_str().to_owned())),
+        ("message", Value::String(err.to_string())),
+        ("resource", Value::string_or_null(err.resource_name())),
+        ("path", Value::string_or_null(path.as_deref())),
+        (
+            "environment_variable",
+            Value::string_or_null(err.environment_variable()),
+        ),
+    ])
+}
+
+/// JSON object containing the value of every [`Property`] of `app`, keyed by
+/// [property name][Property::name].
+///
+/// Properties whose value could not be determined are `null`.
+pub fn app_value(app: &WolframApp) -> Value {
+    Value::object(Property::iter().map(|property| {
+        let value = property_value(app, property).ok();
+
+        (property.name(), Value::string_or_null(value.as_deref()))
+    }))
 }
 
 //======================================
-// Display and formatting
+// Property values
 //======================================
 
-impl Display for Property {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let name = match self {
-            Property::AppType => "App type",
-            Property::WolframVersion => "Wolfram Language version",
-            Property::AppDirectory => "Application directory",
-            Property::InstallationDirectory => "$InstallationDirectory",
-            Property::KernelExecutablePath => "WolframKernel executable",
-            Property::WolframScriptExecutablePath => "wolframscript executable",
-            Property::WstpCompilerAdditionsDirectory => {
-                "WSTP CompilerAdditions directory"
-            },
-            Property::LibraryLinkCIncludesDirectory => "LibraryLink C includes directory",
+/// Get the value of `property` for `app`.
+pub fn property_value(
+    app: &WolframApp,
+    property: Property,
+) -> Result<String, wad::Error> {
+    let value = match property {
+        Property::AppType => format!("{:?}", app.app_type()),
+        Property::AppName => app.app_name().to_owned(),
+        Property::AppVersion => app.app_version().to_string(),
+        Property::WolframVersion => app.wolfram_version()?.to_string(),
+        Property::AppDirectory => app.app_directory().display().to_string(),
+        Property::AppExecutablePath => app.app_executable_path()?.display().to_string(),
+        Property::InstallationDirectory => {
+            app.installation_directory().display().to_string()
+        },
+        Property::SystemId => app.system_id()?.to_string(),
+        Property::KernelExecutablePath => {
+            app.kernel_executable_path()?.display().to_string()
+        },
+        Property::WolframScriptExecutablePath => {
+            app.wolframscript_executable_path()?.display().to_string()
+        },
+        Property::WstpCompilerAdditionsDirectory => app
+            .target_wstp_sdk()?
+            .wstp_compiler_additions_directory()
+            .display()
+            .to_string(),
+        Property::WstpCHeaderPath => app
+            .target_wstp_sdk()?
+            .wstp_c_header_path()
+            .display()
+            .to_string(),
+        Property::WstpStaticLibraryPath => app
+            .target_wstp_sdk()?
+            .wstp_static_library_path()
+            .display()
+            .to_string(),
+        Property::LibraryLinkCIncludesDirectory => app
+            .library_link_c_includes_directory()?
+            .display()
+            .to_string(),
+        Property::LicenseExpiration => match license::license_expiration(app)? {
+            Some(date) => date.to_string(),
+            None => "never".to_owned(),
+        },
+        // Every property defined by the library version this binary is built
+        // with is handled above.
+        property => unreachable!("unhandled property: {property:?}"),
+    };
+
+    Ok(value)
+}
+
+//======================================
+// Comparison
+//======================================
+
+/// Labeled values describing `app`, compared by the `compare` subcommand.
+///
+/// This includes every [`Property`], the platforms that WSTP SDKs and kernel
+/// binaries are provided for, and whether each verified component is present.
+/// Values that could not be determined are described by their [error
+/// code][wad::ErrorCode].
+pub fn comparison_values(app: &WolframApp) -> Vec<(String, String)> {
+    let mut values: Vec<(String, String)> = Property::iter()
+        .map(|property| {
+            let value = match property_value(app, property) {
+                Ok(value) => value,
+                Err(err) => error_summary(&err),
+            };
+
+            (property.to_string(), value)
+        })
+        .collect();
+
+    let system_ids = |system_ids: Result<Vec<SystemID>, wad::Error>| match system_ids {
+        Ok(system_ids) if system_ids.is_empty() => "<none>".to_owned(),
+        Ok(system_ids) => system_ids
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", "),
+        Err(err) => error_summary(&err),
+    };
+
+    let wstp_sdks = app.wstp_sdks().map(|sdks| {
+        // Ignore any SDK directories that are not valid.
+        sdks.into_iter()
+            .filter_map(Result::ok)
+            .map(|sdk| sdk.system_id())
+            .collect()
+    });
+
+    values.push(("WSTP SDKs".to_owned(), system_ids(wstp_sdks)));
+    values.push((
+        "Kernel binaries".to_owned(),
+        system_ids(app.supported_system_ids()),
+    ));
+
+    // Paths are compared above, so only report whether each component is
+    // present.
+    for check in app.verify().checks() {
+        let status = match check.status() {
+            ComponentStatus::Present(_) => "present",
+            ComponentStatus::Missing(_) => "missing",
+            ComponentStatus::NotApplicable => "not applicable",
         };
 
-        write!(f, "{name}")
+        let label = format!("Component: {}", check.component());
+
+        values.push((label, status.to_owned()));
     }
+
+    values
+}
+
+/// Short description of `err` that doesn't include any paths, so that the same
+/// error in two apps compares equal.
+fn error_summary(err: &wad::Error) -> String {
+    format!("<error: {}>", err.code().as_str())
 }
 
+//======================================
+// Display and formatting
+//======================================
+
 impl<'app> Display for PropertyValue<'app> {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let PropertyValue(app, property) = self;
+        let PropertyValue(app, property) = *self;
 
-        match property {
-            Property::AppType => {
-                write!(fmt, "{:?}", app.app_type())
-            },
-            Property::WolframVersion => match app.wolfram_version() {
-                Ok(version) => write!(fmt, "{version}"),
-                Err(error) => {
-                    // Print an error to stderr.
-                    eprintln!("Error getting WolframVersion value: {error}");
-
-                    write!(fmt, "Error")
-                },
-            },
-            Property::AppDirectory => {
-                write!(fmt, "{}", app.app_directory().display())
-            },
-            Property::InstallationDirectory => {
-                write!(fmt, "{}", app.installation_directory().display())
-            },
-            Property::KernelExecutablePath => match app.kernel_executable_path() {
-                Ok(path) => write!(fmt, "{}", path.display()),
-                Err(error) => {
-                    // Print an error to stderr.
-                    eprintln!("Error getting WolframKernel location: {error}");
-
-                    write!(fmt, "Error")
-                },
-            },
-            Property::WolframScriptExecutablePath => {
-                match app.wolframscript_executable_path() {
-                    Ok(path) => write!(fmt, "{}", path.display()),
-                    Err(error) => {
-                        // Print an error to stderr.
-                        eprintln!("Error getting wolframscript location: {error}");
-
-                        write!(fmt, "Error")
-                    },
-                }
-            },
-            Property::WstpCompilerAdditionsDirectory => {
-                match app.target_wstp_sdk() {
-                    Ok(wstp_sdk) => write!(
-                        fmt,
-                        "{}",
-                        wstp_sdk.wstp_compiler_additions_directory().display()
-                    ),
-                    Err(error) => {
-                        // Print an error to stderr.
-                        eprintln!("Error getting target WSTP SDK location: {error}");
-
-                        write!(fmt, "Error")
-                    },
-                }
-            },
-            Property::LibraryLinkCIncludesDirectory => match app
-                .library_link_c_includes_directory()
-            {
-                Ok(value) => write!(fmt, "{}", value.display()),
-                Err(error) => {
-                    // Print an error to stderr.
-                    eprintln!("Error getting LibraryLink C includes directory: {error}");
-
-                    write!(fmt, "Error")
-                },
+        match property_value(app, property) {
+            Ok(value) => write!(fmt, "{value}"),
+            Err(error) => {
+                // Print an error to stderr.
+                eprintln!("Error getting {property} value: {error}");
+
+                write!(fmt, "Error")
             },
         }
     }
@@ -0,0 +1,548 @@
+//! The `serve` subcommand.
+//!
+//! Discovery results are kept in a [`Snapshot`] that is refreshed on a
+//! background thread, so that queries can be answered without repeating the
+//! (potentially slow) discovery process.
+//!
+//! # Protocol
+//!
+//! Each line read from stdin is a [JSON-RPC 2.0](https://www.jsonrpc.org/specification)
+//! request, and the response to each request is written to stdout as a single
+//! line. Supported methods are:
+//!
+//! * `default` — the default app, as an object mapping each property name to
+//!   its value.
+//! * `list` — an array of all discovered apps, in the same format as `default`.
+//! * `refresh` — re-run discovery immediately, and return `null`.
+//!
+//! Discovery errors are returned as JSON-RPC errors, whose `data` is the same
+//! object written by `--error-format json`.
+//!
+//! # Metrics
+//!
+//! If [`Settings::metrics_address`] is set, metrics are served in the
+//! Prometheus text format from `/metrics` on that address. If
+//! [`Settings::metrics_log_interval`] is set, a summary of the same metrics is
+//! periodically written to stderr.
+
+use std::{
+    fmt::Write as _,
+    io::{self, BufRead, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, RwLock, RwLockReadGuard,
+    },
+    time::{Duration, Instant},
+};
+
+use wolfram_app_discovery::{
+    self as wad, property::Property, DiscoveryOptions, Filter, WolframApp,
+};
+
+use crate::{json::Value, output};
+
+// JSON-RPC 2.0 error codes.
+const PARSE_ERROR: i32 = -32700;
+const INVALID_REQUEST: i32 = -32600;
+const METHOD_NOT_FOUND: i32 = -32601;
+/// Error code used for discovery errors.
+const DISCOVERY_ERROR: i32 = -32000;
+
+pub struct Settings {
+    /// How often to re-run discovery. If zero, discovery is only re-run when
+    /// requested by a client.
+    pub poll: Duration,
+    /// Address to serve Prometheus metrics on.
+    pub metrics_address: Option<SocketAddr>,
+    /// How often to write a summary of the metrics to stderr.
+    pub metrics_log_interval: Option<Duration>,
+}
+
+/// State shared between the request loop and background threads.
+struct State {
+    filter: Filter,
+    options: DiscoveryOptions,
+    snapshot: RwLock<Snapshot>,
+    metrics: Metrics,
+}
+
+/// Discovery results, as they are returned to clients.
+struct Snapshot {
+    default: Result<Value, wad::Error>,
+    apps: Vec<Value>,
+    /// How long it took to discover the apps in this snapshot.
+    duration: Duration,
+}
+
+#[derive(Default)]
+struct Metrics {
+    /// Number of times discovery has been run.
+    refreshes: AtomicU64,
+    /// Number of queries answered from the current snapshot.
+    cache_hits: AtomicU64,
+    /// Number of queries that required discovery to be re-run.
+    cache_misses: AtomicU64,
+}
+
+/// Serve requests read from stdin until stdin is closed.
+pub fn serve(
+    filter: Filter,
+    options: DiscoveryOptions,
+    settings: Settings,
+) -> io::Result<()> {
+    let Settings {
+        poll,
+        metrics_address,
+        metrics_log_interval,
+    } = settings;
+
+    let metrics = Metrics::default();
+    let snapshot = RwLock::new(Snapshot::take(&filter, &options, &metrics));
+
+    let state = Arc::new(State {
+        filter,
+        options,
+        snapshot,
+        metrics,
+    });
+
+    // NOTE: The background threads below run until the process exits.
+
+    if let Some(address) = metrics_address {
+        let listener = TcpListener::bind(address)?;
+        let state = Arc::clone(&state);
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                if let Err(err) = serve_metrics(stream, &state) {
+                    eprintln!("warning: error serving metrics: {err}");
+                }
+            }
+        });
+    }
+
+    if let Some(interval) = metrics_log_interval.filter(|interval| !interval.is_zero()) {
+        let state = Arc::clone(&state);
+
+        std::thread::spawn(move || loop {
+            std::thread::sleep(interval);
+
+            eprintln!("metrics: {}", state.metrics_summary());
+        });
+    }
+
+    if !poll.is_zero() {
+        let state = Arc::clone(&state);
+
+        std::thread::spawn(move || loop {
+            std::thread::sleep(poll);
+
+            state.refresh();
+        });
+    }
+
+    let stdin = io::stdin();
+    let mut stdout = io::stdout();
+
+    for line in stdin.lock().lines() {
+        let line = line?;
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let response = match handle_request(&line, &state) {
+            Some(response) => response,
+            // Notifications don't get a response.
+            None => continue,
+        };
+
+        writeln!(stdout, "{response}")?;
+        stdout.flush()?;
+    }
+
+    Ok(())
+}
+
+/// Handle a single JSON-RPC request, returning the response to send, if any.
+fn handle_request(line: &str, state: &State) -> Option<Value> {
+    let request = match crate::json::parse(line) {
+        Ok(request) => request,
+        Err(err) => {
+            return Some(error_response(Value::Null, PARSE_ERROR, &err, None));
+        },
+    };
+
+    let id = request.get("id").cloned();
+
+    let method = match (request.get("jsonrpc"), request.get("method")) {
+        (Some(Value::String(version)), Some(Value::String(method)))
+            if version == "2.0" =>
+        {
+            method
+        },
+        _ => {
+            return Some(error_response(
+                id.unwrap_or(Value::Null),
+                INVALID_REQUEST,
+                "invalid JSON-RPC 2.0 request",
+                None,
+            ));
+        },
+    };
+
+    let result: Result<Value, (i32, String, Option<Value>)> = match method.as_str() {
+        "default" => {
+            state.metrics.cache_hits.fetch_add(1, Ordering::Relaxed);
+
+            let snapshot = state.snapshot();
+
+            snapshot.default.clone().map_err(|err| {
+                (
+                    DISCOVERY_ERROR,
+                    err.to_string(),
+                    Some(output::error_value(&err)),
+                )
+            })
+        },
+        "list" => {
+            state.metrics.cache_hits.fetch_add(1, Ordering::Relaxed);
+
+            let snapshot = state.snapshot();
+
+            Ok(Value::Array(snapshot.apps.clone()))
+        },
+        "refresh" => {
+            state.metrics.cache_misses.fetch_add(1, Ordering::Relaxed);
+
+            state.refresh();
+
+            Ok(Value::Null)
+        },
+        other => Err((METHOD_NOT_FOUND, format!("unknown method: {other:?}"), None)),
+    };
+
+    // Requests without an `id` are notifications.
+    let id = id?;
+
+    let response = match result {
+        Ok(result) => Value::object([
+            ("jsonrpc", Value::String("2.0".to_owned())),
+            ("id", id),
+            ("result", result),
+        ]),
+        Err((code, message, data)) => error_response(id, code, &message, data),
+    };
+
+    Some(response)
+}
+
+fn error_response(id: Value, code: i32, message: &str, data: Option<Value>) -> Value {
+    let mut error = vec![
+        ("code", Value::Number(code.to_string())),
+        ("message", Value::String(message.to_owned())),
+    ];
+
+    if let Some(data) = data {
+        error.push(("data", data));
+    }
+
+    Value::object([
+        ("jsonrpc", Value::String("2.0".to_owned())),
+        ("id", id),
+        ("error", Value::object(error)),
+    ])
+}
+
+//======================================
+// Metrics
+//======================================
+
+/// Respond to a single HTTP request for `/metrics`.
+fn serve_metrics(mut stream: TcpStream, state: &State) -> io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+
+    // Read the request head. Only the request line is used, so the read can
+    // stop once the first line has been received.
+    let mut request = Vec::new();
+    let mut buffer = [0; 1024];
+
+    while !request.contains(&b'\n') && request.len() < 8192 {
+        let count = stream.read(&mut buffer)?;
+        if count == 0 {
+            break;
+        }
+        request.extend_from_slice(&buffer[..count]);
+    }
+
+    let request_line = String::from_utf8_lossy(&request);
+    let mut words = request_line.split_whitespace();
+
+    let (status, body) = match (words.next(), words.next()) {
+        (Some("GET"), Some("/metrics")) => ("200 OK", state.prometheus_metrics()),
+        _ => ("404 Not Found", "not found\n".to_owned()),
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\n\
+        Content-Type: text/plain; version=0.0.4\r\n\
+        Content-Length: {}\r\n\
+        Connection: close\r\n\
+        \r\n\
+        {body}",
+        body.len()
+    )?;
+
+    stream.flush()
+}
+
+impl State {
+    fn snapshot(&self) -> RwLockReadGuard<'_, Snapshot> {
+        self.snapshot.read().unwrap_or_else(|err| err.into_inner())
+    }
+
+    /// Re-run discovery, and replace the current snapshot.
+    fn refresh(&self) {
+        let new = Snapshot::take(&self.filter, &self.options, &self.metrics);
+
+        *self.snapshot.write().unwrap_or_else(|err| err.into_inner()) = new;
+    }
+
+    /// Metrics in the Prometheus text exposition format.
+    fn prometheus_metrics(&self) -> String {
+        let snapshot = self.snapshot();
+        let Metrics {
+            refreshes,
+            cache_hits,
+            cache_misses,
+        } = &self.metrics;
+
+        let mut text = String::new();
+
+        let mut metric =
+            |name: &str, kind: &str, help: &str, samples: &[(String, String)]| {
+                let _ = writeln!(text, "# HELP wolfram_app_discovery_{name} {help}");
+                let _ = writeln!(text, "# TYPE wolfram_app_discovery_{name} {kind}");
+                for (labels, value) in samples {
+                    let _ =
+                        writeln!(text, "wolfram_app_discovery_{name}{labels} {value}");
+                }
+            };
+
+        let app_info: Vec<(String, String)> = snapshot
+            .apps
+            .iter()
+            .map(|app| {
+                let label = |property: Property| {
+                    let value = match app.get(property.name()) {
+                        Some(Value::String(value)) => value.as_str(),
+                        _ => "",
+                    };
+
+                    value
+                        .replace('\\', r"\\")
+                        .replace('"', r#"\""#)
+                        .replace('\n', r"\n")
+                };
+
+                let labels = format!(
+                    "{{app_type=\"{}\",version=\"{}\",app_directory=\"{}\"}}",
+                    label(Property::AppType),
+                    label(Property::WolframVersion),
+                    label(Property::AppDirectory),
+                );
+
+                (labels, "1".to_owned())
+            })
+            .collect();
+
+        metric(
+            "apps",
+            "gauge",
+            "Number of discovered Wolfram apps.",
+            &[(String::new(), snapshot.apps.len().to_string())],
+        );
+        metric("app_info", "gauge", "Discovered Wolfram apps.", &app_info);
+        metric(
+            "default_app_available",
+            "gauge",
+            "Whether a default Wolfram app could be found.",
+            &[(
+                String::new(),
+                u8::from(snapshot.default.is_ok()).to_string(),
+            )],
+        );
+        metric(
+            "discovery_duration_seconds",
+            "gauge",
+            "Duration of the most recent discovery.",
+            &[(String::new(), snapshot.duration.as_secs_f64().to_string())],
+        );
+        metric(
+            "refreshes_total",
+            "counter",
+            "Number of times discovery has been run.",
+            &[(String::new(), refreshes.load(Ordering::Relaxed).to_string())],
+        );
+        metric(
+            "cache_hits_total",
+            "counter",
+            "Number of queries answered from the cached discovery results.",
+            &[(
+                String::new(),
+                cache_hits.load(Ordering::Relaxed).to_string(),
+            )],
+        );
+        metric(
+            "cache_misses_total",
+            "counter",
+            "Number of queries that re-ran discovery.",
+            &[(
+                String::new(),
+                cache_misses.load(Ordering::Relaxed).to_string(),
+            )],
+        );
+
+        text
+    }
+
+    /// One-line summary of the metrics, for logging.
+    fn metrics_summary(&self) -> String {
+        let snapshot = self.snapshot();
+
+        let versions: Vec<&str> = snapshot
+            .apps
+            .iter()
+            .filter_map(|app| match app.get(Property::WolframVersion.name()) {
+                Some(Value::String(version)) => Some(version.as_str()),
+                _ => None,
+            })
+            .collect();
+
+        format!(
+            "apps={} versions={} default_app={} discovery_duration={:?} \
+            refreshes={} cache_hits={} cache_misses={}",
+            snapshot.apps.len(),
+            versions.join(","),
+            if snapshot.default.is_ok() {
+                "ok"
+            } else {
+                "missing"
+            },
+            snapshot.duration,
+            self.metrics.refreshes.load(Ordering::Relaxed),
+            self.metrics.cache_hits.load(Ordering::Relaxed),
+            self.metrics.cache_misses.load(Ordering::Relaxed),
+        )
+    }
+}
+
+impl Snapshot {
+    fn take(filter: &Filter, options: &DiscoveryOptions, metrics: &Metrics) -> Self {
+        let start = Instant::now();
+
+        let default = WolframApp::try_default_with_filter_and_options(filter, options)
+            .map(|app| output::app_value(&app));
+
+        let apps = wad::discover_with_filter_and_options(filter, options)
+            .iter()
+            .map(output::app_value)
+            .collect();
+
+        metrics.refreshes.fetch_add(1, Ordering::Relaxed);
+
+        Snapshot {
+            default,
+            apps,
+            duration: start.elapsed(),
+        }
+    }
+}
+
+//======================================
+// Tests
+//======================================
+
+#[cfg(test)]
+fn test_state(default: Result<Value, wad::Error>) -> State {
+    State {
+        filter: crate::make_filter(Vec::new(), None),
+        options: DiscoveryOptions::default(),
+        snapshot: RwLock::new(Snapshot {
+            default,
+            apps: Vec::new(),
+            duration: Duration::ZERO,
+        }),
+        metrics: Metrics::default(),
+    }
+}
+
+#[test]
+fn test_handle_request() {
+    let app = Value::object([("app-type", Value::String("Engine".to_owned()))]);
+    let state = test_state(Ok(app));
+
+    let response = handle_request(
+        r#"{"jsonrpc": "2.0", "id": 7, "method": "default"}"#,
+        &state,
+    );
+
+    assert_eq!(
+        response.unwrap().to_string(),
+        r#"{"jsonrpc":"2.0","id":7,"result":{"app-type":"Engine"}}"#
+    );
+
+    let response =
+        handle_request(r#"{"jsonrpc": "2.0", "id": "a", "method": "list"}"#, &state);
+
+    assert_eq!(
+        response.unwrap().to_string(),
+        r#"{"jsonrpc":"2.0","id":"a","result":[]}"#
+    );
+
+    assert_eq!(state.metrics.cache_hits.load(Ordering::Relaxed), 2);
+
+    // Notifications don't get a response.
+    assert_eq!(
+        handle_request(r#"{"jsonrpc": "2.0", "method": "list"}"#, &state),
+        None
+    );
+}
+
+#[test]
+fn test_handle_request_errors() {
+    let state = test_state(Ok(Value::Null));
+
+    let error_code = |line: &str| {
+        let response = handle_request(line, &state).unwrap();
+
+        match response.get("error").and_then(|error| error.get("code")) {
+            Some(Value::Number(code)) => code.clone(),
+            other => panic!("unexpected error code: {other:?}"),
+        }
+    };
+
+    // Malformed numbers are parse errors, and are not echoed back.
+    for line in [
+        r#"{"jsonrpc": "2.0", "id": --, "method": "list"}"#,
+        r#"{"jsonrpc": "2.0", "id": 1e, "method": "list"}"#,
+        "not json",
+    ] {
+        assert_eq!(error_code(line), PARSE_ERROR.to_string(), "{line}");
+        assert_eq!(
+            handle_request(line, &state).unwrap().get("id"),
+            Some(&Value::Null)
+        );
+    }
+
+    assert_eq!(
+        error_code(r#"{"jsonrpc": "1.0", "id": 1, "method": "list"}"#),
+        INVALID_REQUEST.to_string()
+    );
+    assert_eq!(
+        error_code(r#"{"jsonrpc": "2.0", "id": 1, "method": "unknown"}"#),
+        METHOD_NOT_FOUND.to_string()
+    );
+}
@@ -0,0 +1,112 @@
+//! Run blocking discovery operations on a background thread, and await their
+//! result from async code.
+//!
+//! The returned futures do not depend on any particular async runtime. They
+//! are completed by the background thread, and so never block the thread that
+//! polls them.
+
+use std::{
+    any::Any,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+};
+
+/// A future that resolves to the return value of a closure run on a
+/// background thread.
+pub(crate) struct BlockingTask<T> {
+    shared: Arc<Mutex<Shared<T>>>,
+}
+
+struct Shared<T> {
+    result: Option<Result<T, Box<dyn Any + Send>>>,
+    waker: Option<Waker>,
+}
+
+/// Run `f` on a new thread, returning a future that resolves to its result.
+///
+/// If `f` panics, the panic is resumed in the task that polls the returned
+/// future.
+pub(crate) fn spawn<T, F>(f: F) -> BlockingTask<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let shared = Arc::new(Mutex::new(Shared {
+        result: None,
+        waker: None,
+    }));
+
+    let thread_shared = Arc::clone(&shared);
+
+    std::thread::spawn(move || {
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(f));
+
+        let mut shared = thread_shared.lock().unwrap_or_else(|err| err.into_inner());
+        shared.result = Some(result);
+
+        if let Some(waker) = shared.waker.take() {
+            waker.wake();
+        }
+    });
+
+    BlockingTask { shared }
+}
+
+impl<T> Future for BlockingTask<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let mut shared = self.shared.lock().unwrap_or_else(|err| err.into_inner());
+
+        match shared.result.take() {
+            Some(Ok(value)) => Poll::Ready(value),
+            Some(Err(panic)) => std::panic::resume_unwind(panic),
+            None => {
+                shared.waker = Some(cx.waker().clone());
+                Poll::Pending
+            },
+        }
+    }
+}
+
+//======================================
+// Tests
+//======================================
+
+#[cfg(test)]
+fn block_on<F: Future>(future: F) -> F::Output {
+    use std::task::Wake;
+
+    struct ThreadWaker(std::thread::Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let waker = Waker::from(Arc::new(ThreadWaker(std::thread::current())));
+    let mut cx = Context::from_waker(&waker);
+
+    let mut future = std::pin::pin!(future);
+
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(value) => return value,
+            Poll::Pending => std::thread::park(),
+        }
+    }
+}
+
+#[test]
+fn test_spawn() {
+    assert_eq!(block_on(spawn(|| 1 + 1)), 2);
+}
+
+#[test]
+#[should_panic(expected = "background panic")]
+fn test_spawn_panic() {
+    block_on(spawn(|| panic!("background panic")))
+}
@@ -9,6 +9,14 @@
 //! variable before falling back to look up the path in the optionally specified
 //! [`WolframApp`].
 //!
+//! Resources that are specific to a particular [`SystemID`][crate::SystemID]
+//! are resolved for the System ID returned by
+//! [`WolframApp::target_system_id()`], which can be overridden using the
+//! [`WOLFRAM_TARGET_SYSTEM_ID`][crate::config::env_vars::WOLFRAM_TARGET_SYSTEM_ID]
+//! environment variable. Each of these functions also has a
+//! `*_for_system_id()` variant that takes the System ID explicitly, e.g. for
+//! use from a build script that is cross-compiling for the Cargo `TARGET`.
+//!
 //! See Also:
 //!
 //! * [`crate::config::set_print_cargo_build_script_directives()`]
@@ -19,15 +27,17 @@ use log::{info, trace};
 
 #[allow(deprecated)]
 use crate::{
+    artifacts,
     config::{
         self,
         env_vars::{
-            WOLFRAM_C_INCLUDES, WOLFRAM_LIBRARY_LINK_C_INCLUDES_DIRECTORY,
-            WSTP_COMPILER_ADDITIONS, WSTP_COMPILER_ADDITIONS_DIRECTORY,
+            MATHLINK_COMPILER_ADDITIONS_DIRECTORY, WOLFRAMSCRIPT_EXECUTABLE,
+            WOLFRAM_C_INCLUDES, WOLFRAM_KERNEL_EXECUTABLE, WOLFRAM_LIBRARIES_DIRECTORY,
+            WOLFRAM_LIBRARY_LINK_C_INCLUDES_DIRECTORY, WSTP_COMPILER_ADDITIONS,
+            WSTP_COMPILER_ADDITIONS_DIRECTORY, WSTP_SHARED_LIBRARY_DIRECTORY,
         },
     },
-    os::OperatingSystem,
-    Error, WolframApp,
+    Error, SystemID, WolframApp,
 };
 
 //======================================
@@ -59,6 +69,124 @@ pub enum Discovery {
     },
 }
 
+/// Every build resource located by [`resolve()`].
+///
+/// Each resource records whether it came from an environment variable or from
+/// the [`WolframApp`], or the error that prevented it from being located.
+#[derive(Clone, Debug)]
+pub struct BuildResources {
+    wstp_c_header_path: Result<Discovery, Error>,
+    wstp_static_library_path: Result<Discovery, Error>,
+    wstp_shared_library_path: Result<Discovery, Error>,
+    library_link_c_includes_directory: Result<Discovery, Error>,
+    kernel_executable_path: Result<Discovery, Error>,
+}
+
+/// Locate every build resource provided by this module in one pass.
+///
+/// Each resource is located using the corresponding function in this module,
+/// so the same environment variables are respected. Resources that can't be
+/// located don't prevent the others from being resolved.
+///
+/// Use [`BuildResources::to_json()`] to record where each resource came from,
+/// e.g. as a build diagnostic.
+pub fn resolve(app: Option<&WolframApp>) -> BuildResources {
+    trace!("start resolve(app={app:?})");
+
+    BuildResources {
+        wstp_c_header_path: wstp_c_header_path(app),
+        wstp_static_library_path: wstp_static_library_path(app),
+        wstp_shared_library_path: wstp_shared_library_path(app),
+        library_link_c_includes_directory: library_link_c_includes_directory(app),
+        kernel_executable_path: kernel_executable_path(app),
+    }
+}
+
+impl BuildResources {
+    /// See [`wstp_c_header_path()`].
+    pub fn wstp_c_header_path(&self) -> Result<&Discovery, &Error> {
+        self.wstp_c_header_path.as_ref()
+    }
+
+    /// See [`wstp_static_library_path()`].
+    pub fn wstp_static_library_path(&self) -> Result<&Discovery, &Error> {
+        self.wstp_static_library_path.as_ref()
+    }
+
+    /// See [`wstp_shared_library_path()`].
+    pub fn wstp_shared_library_path(&self) -> Result<&Discovery, &Error> {
+        self.wstp_shared_library_path.as_ref()
+    }
+
+    /// See [`library_link_c_includes_directory()`].
+    pub fn library_link_c_includes_directory(&self) -> Result<&Discovery, &Error> {
+        self.library_link_c_includes_directory.as_ref()
+    }
+
+    /// See [`kernel_executable_path()`].
+    pub fn kernel_executable_path(&self) -> Result<&Discovery, &Error> {
+        self.kernel_executable_path.as_ref()
+    }
+
+    /// Serialize these resources as a JSON object, keyed by resource name.
+    ///
+    /// Each resource is described by an object with the following fields:
+    ///
+    /// * `"source"`: `"env"`, `"app"`, or `null` if the resource could not be
+    ///   located.
+    /// * `"variable"`: the environment variable the resource came from, or
+    ///   `null`.
+    /// * `"path"`: the location of the resource, or `null`.
+    /// * `"error"`: the [code][crate::ErrorCode] and message of the error that
+    ///   prevented the resource from being located, or `null`.
+    pub fn to_json(&self) -> String {
+        let BuildResources {
+            wstp_c_header_path,
+            wstp_static_library_path,
+            wstp_shared_library_path,
+            library_link_c_includes_directory,
+            kernel_executable_path,
+        } = self;
+
+        let fields = [
+            ("wstp_c_header_path", wstp_c_header_path),
+            ("wstp_static_library_path", wstp_static_library_path),
+            ("wstp_shared_library_path", wstp_shared_library_path),
+            (
+                "library_link_c_includes_directory",
+                library_link_c_includes_directory,
+            ),
+            ("kernel_executable_path", kernel_executable_path),
+        ];
+
+        let fields: Vec<String> = fields
+            .into_iter()
+            .map(|(name, value)| {
+                let value = match value {
+                    Ok(Discovery::App(path)) => format!(
+                        r#"{{"source":"app","variable":null,"path":{},"error":null}}"#,
+                        json_string(&path.display().to_string())
+                    ),
+                    Ok(Discovery::Env { variable, path }) => format!(
+                        r#"{{"source":"env","variable":{},"path":{},"error":null}}"#,
+                        json_string(variable),
+                        json_string(&path.display().to_string())
+                    ),
+                    Err(err) => format!(
+                        r#"{{"source":null,"variable":null,"path":null,"error":{{"code":{},"message":{}}}}}"#,
+                        json_string(err.code().as_str()),
+                        json_string(&err.to_string())
+                    ),
+                };
+
+                format!("{}:{value}", json_string(name))
+            })
+            .collect();
+
+        format!("{{{}}}", fields.join(","))
+    }
+}
+
 impl Discovery {
     /// Converts `self` into a [`PathBuf`].
     pub fn into_path_buf(self) -> PathBuf {
@@ -147,6 +275,8 @@ pub fn library_link_c_includes_directory(
 /// the following dedicated functions:
 ///
 /// * [`wstp_c_header_path()`]
+/// * [`wstp_static_library_path()`]
+/// * [`wstp_shared_library_path()`]
 pub fn wstp_compiler_additions_directory(
     app: Option<&WolframApp>,
 ) -> Result<Discovery, Error> {
@@ -182,6 +312,17 @@ pub fn wstp_compiler_additions_directory(
     Err(err)
 }
 
+/// Discover the CompilerAdditions subdirectory of the WSTP SDK for
+/// `system_id`.
+///
+/// See [`wstp_compiler_additions_directory()`].
+pub fn wstp_compiler_additions_directory_for_system_id(
+    app: Option<&WolframApp>,
+    system_id: SystemID,
+) -> Result<Discovery, Error> {
+    wstp_compiler_additions_directory(retarget(app, system_id).as_ref())
+}
+
 /// Discover the
 /// [`wstp.h`](https://reference.wolfram.com/language/ref/file/wstp.h.html)
 /// header file.
@@ -230,6 +371,18 @@ pub fn wstp_c_header_path(app: Option<&WolframApp>) -> Result<Discovery, Error>
     }
 }
 
+/// Discover the
+/// [`wstp.h`](https://reference.wolfram.com/language/ref/file/wstp.h.html)
+/// header file of the WSTP SDK for `system_id`.
+///
+/// See [`wstp_c_header_path()`].
+pub fn wstp_c_header_path_for_system_id(
+    app: Option<&WolframApp>,
+    system_id: SystemID,
+) -> Result<Discovery, Error> {
+    wstp_c_header_path(retarget(app, system_id).as_ref())
+}
+
 /// Discover the
 /// [WSTP](https://reference.wolfram.com/language/guide/WSTPAPI.html)
 /// static library.
@@ -240,10 +393,22 @@ pub fn wstp_c_header_path(app: Option<&WolframApp>) -> Result<Discovery, Error>
 /// *Note: The [wstp](https://crates.io/crates/wstp) crate provides safe Rust bindings
 /// to WSTP.*
 pub fn wstp_static_library_path(app: Option<&WolframApp>) -> Result<Discovery, Error> {
-    trace!("start wstp_static_library_path(app={app:?})");
+    wstp_static_library_path_for_system_id(app, target_system_id(app)?)
+}
+
+/// Discover the
+/// [WSTP](https://reference.wolfram.com/language/guide/WSTPAPI.html)
+/// static library for `system_id`.
+///
+/// See [`wstp_static_library_path()`].
+pub fn wstp_static_library_path_for_system_id(
+    app: Option<&WolframApp>,
+    system_id: SystemID,
+) -> Result<Discovery, Error> {
+    trace!("start wstp_static_library_path(app={app:?}, system_id={system_id})");
 
-    let static_archive_name =
-        wstp_static_library_file_name(OperatingSystem::target_os())?;
+    let app = retarget(app, system_id);
+    let app = app.as_ref();
 
     match wstp_compiler_additions_directory(app)? {
         // If this location came from `app`, unwrap the app and return
@@ -256,6 +421,16 @@ pub fn wstp_static_library_path(app: Option<&WolframApp>) -> Result<Discovery, E
             return Ok(Discovery::App(path));
         },
         Discovery::Env { variable, path } => {
+            // Use the WSTP interface version declared by `wstp.h`, if present.
+            let wstp_h = path.join("wstp.h");
+            let interface = match wstp_h.is_file() {
+                true => crate::read_wstp_h_version(&wstp_h)?.0,
+                false => artifacts::DEFAULT_WSTP_INTERFACE,
+            };
+
+            let static_archive_name =
+                artifacts::wstp_static_library_name_for_interface(system_id, interface)?;
+
             let static_lib_path = path.join(static_archive_name);
 
             if !static_lib_path.is_file() {
@@ -280,10 +455,521 @@ pub fn wstp_static_library_path(app: Option<&WolframApp>) -> Result<Discovery, E
     }
 }
 
+/// Discover the
+/// [WSTP](https://reference.wolfram.com/language/guide/WSTPAPI.html)
+/// shared (dynamic) library.
+///
+/// The following locations are searched in order:
+///
+/// 1. The [`WSTP_SHARED_LIBRARY_DIRECTORY`] environment variable.
+/// 2. If `app` contains a value, the `SystemFiles/Libraries/$SystemID/`
+///    directory of `app`.
+///
+/// The file name of the library is given by
+/// [`artifacts::wstp_shared_library_name()`]. On macOS, the returned path is
+/// the location of the `wstp.framework` bundle.
+///
+/// This is useful for crates that link to WSTP dynamically, or that need to
+/// copy the WSTP library next to the binary they produce.
+///
+/// *Note: The [wstp](https://crates.io/crates/wstp) crate provides safe Rust bindings
+/// to WSTP.*
+pub fn wstp_shared_library_path(app: Option<&WolframApp>) -> Result<Discovery, Error> {
+    wstp_shared_library_path_for_system_id(app, target_system_id(app)?)
+}
+
+/// Discover the
+/// [WSTP](https://reference.wolfram.com/language/guide/WSTPAPI.html)
+/// shared (dynamic) library for `system_id`.
+///
+/// See [`wstp_shared_library_path()`].
+pub fn wstp_shared_library_path_for_system_id(
+    app: Option<&WolframApp>,
+    system_id: SystemID,
+) -> Result<Discovery, Error> {
+    trace!("start wstp_shared_library_path(app={app:?}, system_id={system_id})");
+
+    let app = retarget(app, system_id);
+    let app = app.as_ref();
+
+    let shared_library_name = artifacts::wstp_shared_library_name(system_id)?;
+
+    if let Some(Discovery::Env { variable, path }) =
+        get_env_resource(WSTP_SHARED_LIBRARY_DIRECTORY, false)
+    {
+        let shared_lib_path = path.join(shared_library_name);
+
+        if !shared_lib_path.exists() {
+            let err = Error::unexpected_env_layout(
+                "WSTP shared library file",
+                variable,
+                path,
+                shared_lib_path,
+            );
+            info!("discovery failed: {err}");
+            return Err(err);
+        }
+
+        let discovery = Discovery::Env {
+            variable,
+            path: shared_lib_path,
+        };
+        info!("discovered in env: {discovery:?}");
+        return Ok(discovery);
+    }
+
+    if let Some(app) = app {
+        let path = app
+            .libraries_directory(system_id)?
+            .join(shared_library_name);
+
+        if !path.exists() {
+            let err = Error::unexpected_app_layout("WSTP shared library file", app, path);
+            info!("discovery failed: {err}");
+            return Err(err);
+        }
+
+        #[rustfmt::skip]
+        info!("discovered in app ({:?}): {}", app.installation_directory().display(), path.display());
+
+        return Ok(Discovery::App(path));
+    }
+
+    let err = Error::undiscoverable(
+        "WSTP shared library".to_owned(),
+        Some(WSTP_SHARED_LIBRARY_DIRECTORY),
+    );
+
+    info!("discovery failed: {err}");
+
+    Err(err)
+}
+
+//======================================
+// MathLink
+//======================================
+
+/// Discover the CompilerAdditions subdirectory of the legacy MathLink SDK.
+///
+/// The following locations are searched in order:
+///
+/// 1. The [`MATHLINK_COMPILER_ADDITIONS_DIRECTORY`] environment variable.
+/// 2. If `app` contains a value, [`WolframApp::mathlink_compiler_additions_directory()`].
+///
+/// MathLink is the predecessor of WSTP. These functions are intended for
+/// bindings that still target MathLink; new code should use
+/// [`wstp_compiler_additions_directory()`] instead.
+pub fn mathlink_compiler_additions_directory(
+    app: Option<&WolframApp>,
+) -> Result<Discovery, Error> {
+    trace!("start mathlink_compiler_additions_directory(app={app:?})");
+
+    if let Some(resource) = get_env_resource(MATHLINK_COMPILER_ADDITIONS_DIRECTORY, false)
+    {
+        info!("discovered in env: {resource:?}");
+        return Ok(resource);
+    }
+
+    if let Some(app) = app {
+        let path = app.mathlink_compiler_additions_directory()?;
+
+        #[rustfmt::skip]
+        info!("discovered in app ({:?}): {}", app.installation_directory().display(), path.display());
+
+        return Ok(Discovery::App(path));
+    }
+
+    let err = Error::undiscoverable(
+        "MathLink CompilerAdditions directory".to_owned(),
+        Some(MATHLINK_COMPILER_ADDITIONS_DIRECTORY),
+    );
+
+    info!("discovery failed: {err}");
+
+    Err(err)
+}
+
+/// Discover the CompilerAdditions subdirectory of the legacy MathLink SDK for
+/// `system_id`.
+///
+/// See [`mathlink_compiler_additions_directory()`].
+pub fn mathlink_compiler_additions_directory_for_system_id(
+    app: Option<&WolframApp>,
+    system_id: SystemID,
+) -> Result<Discovery, Error> {
+    mathlink_compiler_additions_directory(retarget(app, system_id).as_ref())
+}
+
+/// Discover the legacy MathLink `mathlink.h` header file.
+///
+/// The header file is located in the directory returned by
+/// [`mathlink_compiler_additions_directory()`].
+pub fn mathlink_c_header_path(app: Option<&WolframApp>) -> Result<Discovery, Error> {
+    trace!("start mathlink_c_header_path(app={app:?})");
+
+    let dir = mathlink_compiler_additions_directory(app)?;
+
+    file_in_directory(dir, app, "mathlink.h C header file", "mathlink.h")
+}
+
+/// Discover the legacy MathLink `mathlink.h` header file for `system_id`.
+///
+/// See [`mathlink_c_header_path()`].
+pub fn mathlink_c_header_path_for_system_id(
+    app: Option<&WolframApp>,
+    system_id: SystemID,
+) -> Result<Discovery, Error> {
+    mathlink_c_header_path(retarget(app, system_id).as_ref())
+}
+
+/// Discover the legacy MathLink static library.
+///
+/// The library is located in the directory returned by
+/// [`mathlink_compiler_additions_directory()`]. Its file name is given by
+/// [`artifacts::mathlink_static_library_name()`].
+pub fn mathlink_static_library_path(
+    app: Option<&WolframApp>,
+) -> Result<Discovery, Error> {
+    mathlink_static_library_path_for_system_id(app, target_system_id(app)?)
+}
+
+/// Discover the legacy MathLink static library for `system_id`.
+///
+/// See [`mathlink_static_library_path()`].
+pub fn mathlink_static_library_path_for_system_id(
+    app: Option<&WolframApp>,
+    system_id: SystemID,
+) -> Result<Discovery, Error> {
+    trace!("start mathlink_static_library_path(app={app:?}, system_id={system_id})");
+
+    let app = retarget(app, system_id);
+    let app = app.as_ref();
+
+    let name = artifacts::mathlink_static_library_name(system_id)?;
+
+    let dir = mathlink_compiler_additions_directory(app)?;
+
+    file_in_directory(dir, app, "MathLink static library file", name)
+}
+
+//======================================
+// Runtime libraries
+//======================================
+
+/// Discover the directory containing the Wolfram runtime libraries, like
+/// `WolframRTL`.
+///
+/// The following locations are searched in order:
+///
+/// 1. The [`WOLFRAM_LIBRARIES_DIRECTORY`] environment variable.
+/// 2. If `app` contains a value, the `SystemFiles/Libraries/$SystemID/`
+///    directory of `app`.
+///
+/// # Alternatives
+///
+/// When trying to get the path to a particular runtime library, prefer to use
+/// the following dedicated functions:
+///
+/// * [`wolfram_rtl_shared_library_path()`]
+/// * [`wolfram_rtl_static_library_path()`]
+/// * [`wolfram_compile_library_path()`]
+pub fn wolfram_libraries_directory(app: Option<&WolframApp>) -> Result<Discovery, Error> {
+    trace!("start wolfram_libraries_directory(app={app:?})");
+
+    if let Some(resource) = get_env_resource(WOLFRAM_LIBRARIES_DIRECTORY, false) {
+        info!("discovered in env: {resource:?}");
+        return Ok(resource);
+    }
+
+    if let Some(app) = app {
+        let path = app.libraries_directory(app.target_system_id()?)?;
+
+        #[rustfmt::skip]
+        info!("discovered in app ({:?}): {}", app.installation_directory().display(), path.display());
+
+        return Ok(Discovery::App(path));
+    }
+
+    let err = Error::undiscoverable(
+        "Wolfram libraries directory".to_owned(),
+        Some(WOLFRAM_LIBRARIES_DIRECTORY),
+    );
+
+    info!("discovery failed: {err}");
+
+    Err(err)
+}
+
+/// Discover the directory containing the Wolfram runtime libraries for
+/// `system_id`.
+///
+/// See [`wolfram_libraries_directory()`].
+pub fn wolfram_libraries_directory_for_system_id(
+    app: Option<&WolframApp>,
+    system_id: SystemID,
+) -> Result<Discovery, Error> {
+    wolfram_libraries_directory(retarget(app, system_id).as_ref())
+}
+
+/// Discover the `WolframRTL` runtime shared (dynamic) library.
+///
+/// The library is located in the directory returned by
+/// [`wolfram_libraries_directory()`].
+pub fn wolfram_rtl_shared_library_path(
+    app: Option<&WolframApp>,
+) -> Result<Discovery, Error> {
+    wolfram_rtl_shared_library_path_for_system_id(app, target_system_id(app)?)
+}
+
+/// Discover the `WolframRTL` runtime shared (dynamic) library for `system_id`.
+///
+/// See [`wolfram_rtl_shared_library_path()`].
+pub fn wolfram_rtl_shared_library_path_for_system_id(
+    app: Option<&WolframApp>,
+    system_id: SystemID,
+) -> Result<Discovery, Error> {
+    trace!("start wolfram_rtl_shared_library_path(app={app:?}, system_id={system_id})");
+
+    let name = artifacts::wolfram_rtl_shared_library_name(system_id)?;
+
+    wolfram_library_path(
+        retarget(app, system_id).as_ref(),
+        "WolframRTL shared library file",
+        name,
+    )
+}
+
+/// Discover the minimal `WolframRTL` runtime static library.
+///
+/// The library is located in the directory returned by
+/// [`wolfram_libraries_directory()`].
+pub fn wolfram_rtl_static_library_path(
+    app: Option<&WolframApp>,
+) -> Result<Discovery, Error> {
+    wolfram_rtl_static_library_path_for_system_id(app, target_system_id(app)?)
+}
+
+/// Discover the minimal `WolframRTL` runtime static library for `system_id`.
+///
+/// See [`wolfram_rtl_static_library_path()`].
+pub fn wolfram_rtl_static_library_path_for_system_id(
+    app: Option<&WolframApp>,
+    system_id: SystemID,
+) -> Result<Discovery, Error> {
+    trace!("start wolfram_rtl_static_library_path(app={app:?}, system_id={system_id})");
+
+    let name = artifacts::wolfram_rtl_static_library_name(system_id)?;
+
+    wolfram_library_path(
+        retarget(app, system_id).as_ref(),
+        "WolframRTL static library file",
+        name,
+    )
+}
+
+/// Discover the `WolframCompileLibrary` shared (dynamic) library.
+///
+/// The library is located in the directory returned by
+/// [`wolfram_libraries_directory()`].
+pub fn wolfram_compile_library_path(
+    app: Option<&WolframApp>,
+) -> Result<Discovery, Error> {
+    wolfram_compile_library_path_for_system_id(app, target_system_id(app)?)
+}
+
+/// Discover the `WolframCompileLibrary` shared (dynamic) library for `system_id`.
+///
+/// See [`wolfram_compile_library_path()`].
+pub fn wolfram_compile_library_path_for_system_id(
+    app: Option<&WolframApp>,
+    system_id: SystemID,
+) -> Result<Discovery, Error> {
+    trace!("start wolfram_compile_library_path(app={app:?}, system_id={system_id})");
+
+    let name = artifacts::wolfram_compile_library_name(system_id)?;
+
+    wolfram_library_path(
+        retarget(app, system_id).as_ref(),
+        "WolframCompileLibrary library file",
+        name,
+    )
+}
+
+//======================================
+// Executables
+//======================================
+
+/// Discover the
+/// [`WolframKernel`](https://reference.wolfram.com/language/ref/program/WolframKernel.html)
+/// executable.
+///
+/// The following locations are searched in order:
+///
+/// 1. The [`WOLFRAM_KERNEL_EXECUTABLE`] environment variable.
+/// 2. If `app` contains a value, [`WolframApp::kernel_executable_path()`].
+///
+/// This is useful for build scripts that invoke the Wolfram Language kernel
+/// at build time, e.g. to generate code.
+pub fn kernel_executable_path(app: Option<&WolframApp>) -> Result<Discovery, Error> {
+    trace!("start kernel_executable_path(app={app:?})");
+
+    if let Some(resource) = get_env_resource(WOLFRAM_KERNEL_EXECUTABLE, false) {
+        info!("discovered in env: {resource:?}");
+        return Ok(resource);
+    }
+
+    if let Some(app) = app {
+        let path = app.kernel_executable_path()?;
+
+        #[rustfmt::skip]
+        info!("discovered in app ({:?}): {}", app.installation_directory().display(), path.display());
+
+        return Ok(Discovery::App(path));
+    }
+
+    let err = Error::undiscoverable(
+        "WolframKernel executable".to_owned(),
+        Some(WOLFRAM_KERNEL_EXECUTABLE),
+    );
+
+    info!("discovery failed: {err}");
+
+    Err(err)
+}
+
+/// Discover the
+/// [`wolframscript`](https://reference.wolfram.com/language/ref/program/wolframscript.html)
+/// executable.
+///
+/// The following locations are searched in order:
+///
+/// 1. The [`WOLFRAMSCRIPT_EXECUTABLE`] environment variable.
+/// 2. If `app` contains a value, [`WolframApp::wolframscript_executable_path()`].
+///
+/// Setting [`WOLFRAMSCRIPT_EXECUTABLE`] allows tools that invoke
+/// `wolframscript` during a build to be configured without a full Wolfram
+/// installation, e.g. in CI.
+pub fn wolframscript_executable_path(
+    app: Option<&WolframApp>,
+) -> Result<Discovery, Error> {
+    trace!("start wolframscript_executable_path(app={app:?})");
+
+    if let Some(resource) = get_env_resource(WOLFRAMSCRIPT_EXECUTABLE, false) {
+        info!("discovered in env: {resource:?}");
+        return Ok(resource);
+    }
+
+    if let Some(app) = app {
+        let path = app.wolframscript_executable_path()?;
+
+        #[rustfmt::skip]
+        info!("discovered in app ({:?}): {}", app.installation_directory().display(), path.display());
+
+        return Ok(Discovery::App(path));
+    }
+
+    let err = Error::undiscoverable(
+        "wolframscript executable".to_owned(),
+        Some(WOLFRAMSCRIPT_EXECUTABLE),
+    );
+
+    info!("discovery failed: {err}");
+
+    Err(err)
+}
+
 //======================================
 // Helpers
 //======================================
 
+fn target_system_id(app: Option<&WolframApp>) -> Result<SystemID, Error> {
+    match app {
+        Some(app) => app.target_system_id(),
+        None => crate::resolve_target_system_id(None),
+    }
+}
+
+/// Copy of `app` that resolves target-specific resources for `system_id`.
+fn retarget(app: Option<&WolframApp>, system_id: SystemID) -> Option<WolframApp> {
+    app.map(|app| app.with_target_system_id(system_id))
+}
+
+/// Locate the file called `name` in the [`wolfram_libraries_directory()`].
+fn wolfram_library_path(
+    app: Option<&WolframApp>,
+    resource_name: &'static str,
+    name: &str,
+) -> Result<Discovery, Error> {
+    let dir = wolfram_libraries_directory(app)?;
+
+    file_in_directory(dir, app, resource_name, name)
+}
+
+/// Locate the file called `name` in the directory `dir`, which was discovered
+/// in either `app` or an environment variable.
+fn file_in_directory(
+    dir: Discovery,
+    app: Option<&WolframApp>,
+    resource_name: &'static str,
+    name: &str,
+) -> Result<Discovery, Error> {
+    let discovery = match dir {
+        Discovery::App(dir) => {
+            let path = dir.join(name);
+
+            if !path.is_file() {
+                let err = Error::unexpected_app_layout(resource_name, app.unwrap(), path);
+                info!("discovery failed: {err}");
+                return Err(err);
+            }
+
+            Discovery::App(path)
+        },
+        Discovery::Env { variable, path } => {
+            let lib_path = path.join(name);
+
+            if !lib_path.is_file() {
+                let err =
+                    Error::unexpected_env_layout(resource_name, variable, path, lib_path);
+                info!("discovery failed: {err}");
+                return Err(err);
+            }
+
+            Discovery::Env {
+                variable,
+                path: lib_path,
+            }
+        },
+    };
+
+    info!("discovered: {discovery:?}");
+
+    Ok(discovery)
+}
+
+/// Quote and escape `value` as a JSON string.
+fn json_string(value: &str) -> String {
+    let mut string = String::with_capacity(value.len() + 2);
+
+    string.push('"');
+
+    for c in value.chars() {
+        match c {
+            '"' => string.push_str("\\\""),
+            '\\' => string.push_str("\\\\"),
+            '\n' => string.push_str("\\n"),
+            '\r' => string.push_str("\\r"),
+            '\t' => string.push_str("\\t"),
+            c if c.is_control() => string.push_str(&format!("\\u{:04x}", c as u32)),
+            c => string.push(c),
+        }
+    }
+
+    string.push('"');
+
+    string
+}
+
 fn get_env_resource(var: &'static str, deprecated: bool) -> Option<Discovery> {
     if let Some(path) = config::get_env_var(var) {
         if deprecated {
@@ -299,38 +985,18 @@ fn get_env_resource(var: &'static str, deprecated: bool) -> Option<Discovery> {
     None
 }
 
-// Note: In theory, this can also vary based on the WSTP library 'interface' version
-//       (currently v4). But that has not changed in a long time. If the interface
-//       version does change, this logic should be updated to also check the WL
-//       version.
-pub(crate) fn wstp_static_library_file_name(
-    os: OperatingSystem,
-) -> Result<&'static str, Error> {
-    let static_archive_name = match os {
-        OperatingSystem::MacOS => "libWSTPi4.a",
-        OperatingSystem::Windows => "wstp64i4s.lib",
-        OperatingSystem::Linux => "libWSTP64i4.a",
-        OperatingSystem::Other => {
-            return Err(Error::platform_unsupported(
-                "wstp_static_library_file_name()",
-            ));
-        },
-    };
-
-    Ok(static_archive_name)
-}
-
 //======================================
 // Tests
 //======================================
 
 #[test]
 fn test_wstp_c_header_path() {
-    use crate::ErrorKind;
+    use crate::{test_support::EnvVarOverride, ErrorKind};
 
     //========================
 
-    std::env::remove_var("WSTP_COMPILER_ADDITIONS_DIRECTORY");
+    let mut compiler_additions =
+        EnvVarOverride::unset("WSTP_COMPILER_ADDITIONS_DIRECTORY");
 
     assert_eq!(
         wstp_c_header_path(None),
@@ -342,7 +1008,7 @@ fn test_wstp_c_header_path() {
 
     //========================
 
-    std::env::set_var("WSTP_COMPILER_ADDITIONS_DIRECTORY", std::env::temp_dir());
+    compiler_additions.set(std::env::temp_dir());
 
     assert_eq!(
         wstp_c_header_path(None),
@@ -363,7 +1029,7 @@ fn test_wstp_c_header_path() {
         .target_wstp_sdk()
         .unwrap()
         .wstp_compiler_additions_directory();
-    std::env::set_var("WSTP_COMPILER_ADDITIONS_DIRECTORY", &compiler_additions_dir);
+    compiler_additions.set(&compiler_additions_dir);
 
     assert_eq!(
         wstp_c_header_path(None),
@@ -373,3 +1039,167 @@ fn test_wstp_c_header_path() {
         })
     );
 }
+
+#[test]
+fn test_wstp_shared_library_path() {
+    use crate::{test_support::EnvVarOverride, ErrorKind};
+
+    let _shared_library =
+        EnvVarOverride::with_value("WSTP_SHARED_LIBRARY_DIRECTORY", std::env::temp_dir());
+
+    let name = artifacts::wstp_shared_library_name(
+        crate::resolve_target_system_id(None).unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        wstp_shared_library_path(None),
+        Err(Error(ErrorKind::UnexpectedEnvironmentValueLayout {
+            resource_name: "WSTP shared library file",
+            env_var: "WSTP_SHARED_LIBRARY_DIRECTORY",
+            env_value: std::env::temp_dir(),
+            derived_path: std::env::temp_dir().join(name)
+        }))
+    );
+}
+
+#[test]
+fn test_wolfram_rtl_library_paths() {
+    use crate::{test_support::EnvVarOverride, ErrorKind};
+
+    let _libraries =
+        EnvVarOverride::with_value("WOLFRAM_LIBRARIES_DIRECTORY", std::env::temp_dir());
+
+    let system_id = crate::resolve_target_system_id(None).unwrap();
+    let name = artifacts::wolfram_rtl_shared_library_name(system_id).unwrap();
+
+    assert_eq!(
+        wolfram_libraries_directory(None),
+        Ok(Discovery::Env {
+            variable: "WOLFRAM_LIBRARIES_DIRECTORY",
+            path: std::env::temp_dir()
+        })
+    );
+
+    assert_eq!(
+        wolfram_rtl_shared_library_path(None),
+        Err(Error(ErrorKind::UnexpectedEnvironmentValueLayout {
+            resource_name: "WolframRTL shared library file",
+            env_var: "WOLFRAM_LIBRARIES_DIRECTORY",
+            env_value: std::env::temp_dir(),
+            derived_path: std::env::temp_dir().join(name)
+        }))
+    );
+
+    // The file name is determined by the explicit System ID, not the host.
+    assert_eq!(
+        wolfram_rtl_shared_library_path_for_system_id(None, SystemID::Windows_x86_64),
+        Err(Error(ErrorKind::UnexpectedEnvironmentValueLayout {
+            resource_name: "WolframRTL shared library file",
+            env_var: "WOLFRAM_LIBRARIES_DIRECTORY",
+            env_value: std::env::temp_dir(),
+            derived_path: std::env::temp_dir().join("WolframRTL.dll")
+        }))
+    );
+}
+
+#[test]
+fn test_executable_paths() {
+    use crate::{test_support::EnvVarOverride, ErrorKind};
+
+    let mut kernel = EnvVarOverride::unset("WOLFRAM_KERNEL_EXECUTABLE");
+
+    assert_eq!(
+        kernel_executable_path(None),
+        Err(Error(ErrorKind::Undiscoverable {
+            resource: "WolframKernel executable".into(),
+            environment_variable: Some("WOLFRAM_KERNEL_EXECUTABLE")
+        }))
+    );
+
+    kernel.set("/opt/Wolfram/WolframKernel");
+
+    assert_eq!(
+        kernel_executable_path(None),
+        Ok(Discovery::Env {
+            variable: "WOLFRAM_KERNEL_EXECUTABLE",
+            path: PathBuf::from("/opt/Wolfram/WolframKernel")
+        })
+    );
+
+    //========================
+
+    let _wolframscript = EnvVarOverride::with_value(
+        "WOLFRAMSCRIPT_EXECUTABLE",
+        "/usr/local/bin/wolframscript",
+    );
+
+    assert_eq!(
+        wolframscript_executable_path(None),
+        Ok(Discovery::Env {
+            variable: "WOLFRAMSCRIPT_EXECUTABLE",
+            path: PathBuf::from("/usr/local/bin/wolframscript")
+        })
+    );
+}
+
+#[test]
+fn test_build_resources_to_json() {
+    let missing = Error::undiscoverable("WolframKernel executable".to_owned(), None);
+
+    let resources = BuildResources {
+        wstp_c_header_path: Ok(Discovery::App(PathBuf::from("/app/wstp.h"))),
+        wstp_static_library_path: Ok(Discovery::Env {
+            variable: "WSTP_COMPILER_ADDITIONS_DIRECTORY",
+            path: PathBuf::from("/sdk/libWSTP64i4.a"),
+        }),
+        wstp_shared_library_path: Err(missing.clone()),
+        library_link_c_includes_directory: Ok(Discovery::App(PathBuf::from(
+            "C:\\Program Files\\Wolfram",
+        ))),
+        kernel_executable_path: Err(missing.clone()),
+    };
+
+    let json = resources.to_json();
+
+    assert!(json.starts_with(
+        r#"{"wstp_c_header_path":{"source":"app","variable":null,"path":"/app/wstp.h","error":null},"#
+    ));
+    assert!(json.contains(
+        r#""wstp_static_library_path":{"source":"env","variable":"WSTP_COMPILER_ADDITIONS_DIRECTORY","path":"/sdk/libWSTP64i4.a","error":null}"#
+    ));
+    assert!(json.contains(r#""path":"C:\\Program Files\\Wolfram""#));
+    assert!(json.ends_with(&format!(
+        r#""kernel_executable_path":{{"source":null,"variable":null,"path":null,"error":{{"code":"undiscoverable","message":"{missing}"}}}}}}"#
+    )));
+}
+
+#[test]
+fn test_mathlink_paths() {
+    use crate::{test_support::EnvVarOverride, ErrorKind};
+
+    let _compiler_additions = EnvVarOverride::with_value(
+        "MATHLINK_COMPILER_ADDITIONS_DIRECTORY",
+        std::env::temp_dir(),
+    );
+
+    assert_eq!(
+        mathlink_c_header_path(None),
+        Err(Error(ErrorKind::UnexpectedEnvironmentValueLayout {
+            resource_name: "mathlink.h C header file",
+            env_var: "MATHLINK_COMPILER_ADDITIONS_DIRECTORY",
+            env_value: std::env::temp_dir(),
+            derived_path: std::env::temp_dir().join("mathlink.h")
+        }))
+    );
+
+    assert_eq!(
+        mathlink_static_library_path_for_system_id(None, SystemID::Windows_x86_64),
+        Err(Error(ErrorKind::UnexpectedEnvironmentValueLayout {
+            resource_name: "MathLink static library file",
+            env_var: "MATHLINK_COMPILER_ADDITIONS_DIRECTORY",
+            env_value: std::env::temp_dir(),
+            derived_path: std::env::temp_dir().join("ml64i4s.lib")
+        }))
+    );
+}
@@ -0,0 +1,773 @@
+//! Persistent cache of the apps found by operating system discovery.
+//!
+//! Discovering installed apps can be slow on computers with many Wolfram
+//! installations. If [`DiscoveryOptions::use_cache`] is `true`, the apps found by
+//! a previous discovery, including the metadata read from their operating
+//! system registrations, are loaded from a cache file instead of searching the
+//! system again.
+//!
+//! The cache is not updated automatically when Wolfram apps are installed or
+//! removed. Apps whose application directory no longer exists are skipped when
+//! the cache is loaded, but newly installed apps are not found until the cache
+//! is [refreshed][refresh()] or [cleared][clear()].
+//!
+//! The cache file is stored in the directory specified by the
+//! [`WOLFRAM_APP_DISCOVERY_CACHE_DIRECTORY`] environment variable, or the user
+//! cache directory of the operating system.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use crate::{
+    config::{self, env_vars::WOLFRAM_APP_DISCOVERY_CACHE_DIRECTORY},
+    os::{self, OperatingSystem},
+    AppVersion, DiscoveryOptions, Error, InstallationId, Registration, WindowsCaps,
+    WolframApp, WolframAppType,
+};
+
+/// First line of every cache file, identifying its format.
+const HEADER: &str = "# wolfram-app-discovery discovery cache v2";
+
+/// Prefix of the [`HEADER`] of every version of the cache file format.
+const HEADER_PREFIX: &str = "# wolfram-app-discovery discovery cache ";
+
+/// Contents of the discovery cache file.
+#[derive(Debug, Clone)]
+pub struct CacheEntry {
+    path: PathBuf,
+    created: SystemTime,
+    /// The apps found by operating system discovery, including the metadata
+    /// read from their operating system registrations.
+    apps: Vec<WolframApp>,
+}
+
+//======================================
+// API
+//======================================
+
+/// Location of the discovery cache file.
+///
+/// The file may not exist.
+pub fn cache_file() -> Result<PathBuf, Error> {
+    if let Some(dir) = config::get_env_var(WOLFRAM_APP_DISCOVERY_CACHE_DIRECTORY) {
+        return Ok(PathBuf::from(dir).join("apps"));
+    }
+
+    let env_dir = |var: &str, rest: &[&str]| -> Option<PathBuf> {
+        let dir = std::env::var_os(var)?;
+
+        Some(
+            rest.iter()
+                .fold(PathBuf::from(dir), |path, part| path.join(part)),
+        )
+    };
+
+    let dir = match OperatingSystem::target_os() {
+        OperatingSystem::MacOS => env_dir("HOME", &["Library", "Caches"]),
+        OperatingSystem::Windows => env_dir("LOCALAPPDATA", &[]),
+        OperatingSystem::Linux | OperatingSystem::Other => {
+            env_dir("XDG_CACHE_HOME", &[]).or_else(|| env_dir("HOME", &[".cache"]))
+        },
+    };
+
+    match dir {
+        Some(dir) => Ok(dir.join("wolfram-app-discovery").join("apps")),
+        None => Err(Error::undiscoverable(
+            "discovery cache directory".to_owned(),
+            Some(WOLFRAM_APP_DISCOVERY_CACHE_DIRECTORY),
+        )),
+    }
+}
+
+/// Load the discovery cache, if it exists.
+///
+/// A cache file written in the format of a different version of this library
+/// is treated as not existing.
+pub fn load() -> Result<Option<CacheEntry>, Error> {
+    let path = cache_file()?;
+
+    let contents = match fs::read_to_string(&path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+
+    match contents.lines().next() {
+        Some(header) if header != HEADER && header.starts_with(HEADER_PREFIX) => {
+            return Ok(None)
+        },
+        _ => (),
+    }
+
+    CacheEntry::parse(path, &contents).map(Some)
+}
+
+/// Discover installed apps using operating system discovery, and replace the
+/// contents of the cache with the result.
+pub fn refresh(options: &DiscoveryOptions) -> Result<CacheEntry, Error> {
+    let mut apps: Vec<WolframApp> = Vec::new();
+
+    for app in os::discover_all(options, None) {
+        if !apps
+            .iter()
+            .any(|other| other.app_directory == app.app_directory)
+        {
+            apps.push(app);
+        }
+    }
+
+    let entry = CacheEntry {
+        path: cache_file()?,
+        created: SystemTime::now(),
+        apps,
+    };
+
+    entry.write()?;
+
+    Ok(entry)
+}
+
+/// Delete the discovery cache file.
+///
+/// Returns `true` if the cache file existed.
+pub fn clear() -> Result<bool, Error> {
+    match fs::remove_file(cache_file()?) {
+        Ok(()) => Ok(true),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(false),
+        Err(err) => Err(err.into()),
+    }
+}
+
+impl CacheEntry {
+    /// Location of the cache file.
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    /// When the cached discovery was performed.
+    pub fn created(&self) -> SystemTime {
+        self.created
+    }
+
+    /// Time elapsed since the cached discovery was performed.
+    pub fn age(&self) -> Duration {
+        self.created.elapsed().unwrap_or_default()
+    }
+
+    /// Application directories of the cached apps.
+    pub fn app_directories(&self) -> Vec<&Path> {
+        self.apps
+            .iter()
+            .map(|app| app.app_directory.as_path())
+            .collect()
+    }
+
+    /// Load the cached apps.
+    ///
+    /// Apps whose application directory no longer exists are skipped.
+    pub fn apps(&self) -> Vec<WolframApp> {
+        self.apps
+            .iter()
+            .filter(|app| app.app_directory.is_dir())
+            .cloned()
+            .collect()
+    }
+}
+
+//======================================
+// Helpers
+//======================================
+
+/// Load the cached apps, performing and caching a fresh discovery if the cache
+/// does not exist.
+///
+/// If the cache can't be read or written, a warning is printed and a fresh
+/// discovery is returned.
+pub(crate) fn load_or_refresh(options: &DiscoveryOptions) -> Vec<WolframApp> {
+    let entry = match load() {
+        Ok(Some(entry)) => Ok(entry),
+        Ok(None) => refresh(options),
+        Err(err) => Err(err),
+    };
+
+    match entry {
+        Ok(entry) => entry.apps(),
+        Err(err) => {
+            crate::warning(&format!("unable to use discovery cache: {err}"));
+
+            os::discover_all(options, None)
+        },
+    }
+}
+
+impl CacheEntry {
+    fn parse(path: PathBuf, contents: &str) -> Result<Self, Error> {
+        let invalid = |reason: &str| {
+            Error::other(format!(
+                "invalid discovery cache file: {}: {reason}",
+                path.display()
+            ))
+        };
+
+        let mut lines = contents.lines();
+
+        if lines.next() != Some(HEADER) {
+            return Err(invalid("unrecognized format"));
+        }
+
+        let mut created = None;
+        let mut records: Vec<AppRecord> = Vec::new();
+        let mut in_embedded_player = false;
+
+        for line in lines.filter(|line| !line.is_empty()) {
+            match line {
+                "[app]" => {
+                    records.push(AppRecord::default());
+                    in_embedded_player = false;
+                },
+                "[embedded_player]" => {
+                    let record = records
+                        .last_mut()
+                        .ok_or_else(|| invalid("embedded player outside of [app]"))?;
+                    record.embedded_player = Some(Box::default());
+                    in_embedded_player = true;
+                },
+                _ => {
+                    let (key, value) = line
+                        .split_once('=')
+                        .ok_or_else(|| invalid(&format!("unexpected line: {line:?}")))?;
+
+                    let record = match records.last_mut() {
+                        None if key == "created" => {
+                            let secs: u64 =
+                                value.parse().map_err(|_| invalid("invalid time"))?;
+                            created =
+                                Some(SystemTime::UNIX_EPOCH + Duration::from_secs(secs));
+                            continue;
+                        },
+                        None => {
+                            return Err(invalid(&format!("unexpected line: {line:?}")))
+                        },
+                        Some(record) => record,
+                    };
+
+                    let record = match record.embedded_player {
+                        Some(ref mut player) if in_embedded_player => player,
+                        _ => record,
+                    };
+
+                    record
+                        .set(key, value)
+                        .ok_or_else(|| invalid(&format!("invalid line: {line:?}")))?;
+                },
+            }
+        }
+
+        let apps = records
+            .into_iter()
+            .map(|record| {
+                record
+                    .into_app()
+                    .ok_or_else(|| invalid("incomplete app record"))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(CacheEntry {
+            created: created.ok_or_else(|| invalid("missing creation time"))?,
+            path,
+            apps,
+        })
+    }
+
+    fn write(&self) -> Result<(), Error> {
+        let CacheEntry {
+            path,
+            created,
+            apps,
+        } = self;
+
+        let created = created
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        let mut contents = format!("{HEADER}\ncreated={created}\n");
+
+        for app in apps {
+            write_app(&mut contents, "app", app)?;
+
+            if let Some(ref player) = app.embedded_player {
+                write_app(&mut contents, "embedded_player", player)?;
+            }
+        }
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        // Write to a temporary file and then rename it over the cache file, so
+        // that concurrent readers never see a partially written cache.
+        let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+        let temp_path =
+            path.with_file_name(format!(".{file_name}.{}.tmp", std::process::id()));
+
+        let result =
+            fs::write(&temp_path, contents).and_then(|()| fs::rename(&temp_path, path));
+
+        if let Err(err) = result {
+            let _ = fs::remove_file(&temp_path);
+            return Err(err.into());
+        }
+
+        Ok(())
+    }
+}
+
+/// Fields of an app read from the cache file, which may not yet be complete.
+#[derive(Default)]
+struct AppRecord {
+    app_name: Option<String>,
+    app_type: Option<WolframAppType>,
+    app_version: Option<AppVersion>,
+    app_directory: Option<PathBuf>,
+    app_executable: Option<PathBuf>,
+    embedded_player: Option<Box<AppRecord>>,
+    windows_caps: Option<WindowsCaps>,
+    installation_id: Option<InstallationId>,
+    registration: Option<Registration>,
+}
+
+impl AppRecord {
+    /// Set the field named `key` from its encoded `value`.
+    ///
+    /// Returns `None` if `key` is unknown or `value` is invalid.
+    fn set(&mut self, key: &str, value: &str) -> Option<()> {
+        match key {
+            "name" => self.app_name = Some(decode_str(value)?),
+            "type" => {
+                self.app_type = WolframAppType::variants()
+                    .into_iter()
+                    .find(|app_type| format!("{app_type:?}") == value);
+                self.app_type.as_ref()?;
+            },
+            "version" => self.app_version = Some(decode_version(value)?),
+            "directory" => self.app_directory = Some(decode_path(value)?),
+            "executable" => self.app_executable = Some(decode_path(value)?),
+            "windows_caps" => {
+                self.windows_caps = Some(WindowsCaps::from_bits(value.parse().ok()?))
+            },
+            "installation_id" => {
+                let (kind, id) = value.split_once(':')?;
+                let id = decode_str(id)?;
+
+                self.installation_id = Some(match kind {
+                    "MacOSBundleIdentifier" => InstallationId::MacOSBundleIdentifier(id),
+                    "WindowsClsid" => InstallationId::WindowsClsid(id),
+                    "WindowsPackageFullName" => {
+                        InstallationId::WindowsPackageFullName(id)
+                    },
+                    _ => return None,
+                });
+            },
+            "registration" => {
+                self.registration = Some(match value.split_once(':')? {
+                    ("LaunchServices", path) => {
+                        Registration::LaunchServices(decode_path(path)?)
+                    },
+                    ("WindowsRegistryKey", rest) => {
+                        let (wow64_32, key) = rest.split_once(':')?;
+
+                        Registration::WindowsRegistryKey {
+                            key: decode_str(key)?,
+                            wow64_32: wow64_32.parse().ok()?,
+                        }
+                    },
+                    _ => return None,
+                });
+            },
+            _ => return None,
+        }
+
+        Some(())
+    }
+
+    /// Construct the app described by this record.
+    ///
+    /// Returns `None` if a required field is missing.
+    fn into_app(self) -> Option<WolframApp> {
+        let AppRecord {
+            app_name,
+            app_type,
+            app_version,
+            app_directory,
+            app_executable,
+            embedded_player,
+            windows_caps,
+            installation_id,
+            registration,
+        } = self;
+
+        let embedded_player = match embedded_player {
+            Some(player) => Some(Box::new(player.into_app()?)),
+            None => None,
+        };
+
+        Some(WolframApp {
+            app_name: app_name?,
+            app_type: app_type?,
+            app_version: app_version?,
+            app_directory: app_directory?,
+            app_executable,
+            embedded_player,
+            windows_caps,
+            installation_id,
+            target_system_id: None,
+            front_end_preference: None,
+            timeout: None,
+            offline: false,
+            wolframscript_invocation: None,
+            registration,
+        })
+    }
+}
+
+/// Append the fields of `app` to `contents`, under a `[section]` header.
+///
+/// Discovery settings are not written, because they are applied again each
+/// time the cached apps are loaded.
+fn write_app(
+    contents: &mut String,
+    section: &str,
+    app: &WolframApp,
+) -> Result<(), Error> {
+    let WolframApp {
+        app_name,
+        app_type,
+        app_version,
+        app_directory,
+        app_executable,
+        embedded_player: _,
+        windows_caps,
+        installation_id,
+        target_system_id: _,
+        front_end_preference: _,
+        timeout: _,
+        offline: _,
+        wolframscript_invocation: _,
+        registration,
+    } = app;
+
+    let AppVersion {
+        major,
+        minor,
+        revision,
+        minor_revision,
+        build_code,
+    } = app_version;
+
+    let optional = |value: Option<u64>| match value {
+        Some(value) => value.to_string(),
+        None => "-".to_owned(),
+    };
+
+    contents.push_str(&format!("\n[{section}]\n"));
+    contents.push_str(&format!("name={}\n", encode_str(app_name)));
+    contents.push_str(&format!("type={app_type:?}\n"));
+    contents.push_str(&format!(
+        "version={major}.{minor}.{revision}.{}.{}\n",
+        optional(minor_revision.map(u64::from)),
+        optional(*build_code)
+    ));
+    contents.push_str(&format!("directory={}\n", encode_path(app_directory)?));
+
+    if let Some(executable) = app_executable {
+        contents.push_str(&format!("executable={}\n", encode_path(executable)?));
+    }
+
+    if let Some(caps) = windows_caps {
+        contents.push_str(&format!("windows_caps={}\n", caps.bits()));
+    }
+
+    if let Some(id) = installation_id {
+        let (kind, id) = match id {
+            InstallationId::MacOSBundleIdentifier(id) => ("MacOSBundleIdentifier", id),
+            InstallationId::WindowsClsid(id) => ("WindowsClsid", id),
+            InstallationId::WindowsPackageFullName(id) => ("WindowsPackageFullName", id),
+        };
+
+        contents.push_str(&format!("installation_id={kind}:{}\n", encode_str(id)));
+    }
+
+    match registration {
+        Some(Registration::LaunchServices(path)) => {
+            contents.push_str(&format!(
+                "registration=LaunchServices:{}\n",
+                encode_path(path)?
+            ));
+        },
+        Some(Registration::WindowsRegistryKey { key, wow64_32 }) => {
+            contents.push_str(&format!(
+                "registration=WindowsRegistryKey:{wow64_32}:{}\n",
+                encode_str(key)
+            ));
+        },
+        None => (),
+    }
+
+    Ok(())
+}
+
+/// Parse a version written by [`write_app()`], in which a missing minor
+/// revision or build code is written as `-`.
+fn decode_version(value: &str) -> Option<AppVersion> {
+    let optional = |component: &str| match component {
+        "-" => Some(None),
+        _ => component.parse().ok().map(Some),
+    };
+
+    match value.split('.').collect::<Vec<_>>().as_slice() {
+        [major, minor, revision, minor_revision, build_code] => Some(AppVersion {
+            major: major.parse().ok()?,
+            minor: minor.parse().ok()?,
+            revision: revision.parse().ok()?,
+            minor_revision: optional(minor_revision)?
+                .map(u32::try_from)
+                .transpose()
+                .ok()?,
+            build_code: optional(build_code)?,
+        }),
+        _ => None,
+    }
+}
+
+//======================================
+// Value encoding
+//======================================
+
+// Values are written one per line, so `%`, `\n`, and `\r` are escaped as `%XX`.
+// Paths that are not valid Unicode are written losslessly by escaping their
+// raw bytes (on Unix) or UTF-16 code units (on Windows).
+
+fn escape_char(c: char, out: &mut String) {
+    match c {
+        '%' | '\n' | '\r' => out.push_str(&format!("%{:02X}", u32::from(c))),
+        _ => out.push(c),
+    }
+}
+
+fn encode_str(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        escape_char(c, &mut out);
+    }
+
+    out
+}
+
+/// Decode `value`, in which `%XX` escapes are raw bytes, into UTF-8 bytes.
+fn unescape(value: &str) -> Option<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut chars = value.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '%' => {
+                let hex = chars.as_str().get(..2)?;
+                bytes.push(u8::from_str_radix(hex, 16).ok()?);
+                chars = chars.as_str()[2..].chars();
+            },
+            _ => bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes()),
+        }
+    }
+
+    Some(bytes)
+}
+
+fn decode_str(value: &str) -> Option<String> {
+    String::from_utf8(unescape(value)?).ok()
+}
+
+#[cfg(unix)]
+fn encode_path(path: &Path) -> Result<String, Error> {
+    use std::os::unix::ffi::OsStrExt;
+
+    let mut out = String::new();
+    let mut bytes = path.as_os_str().as_bytes();
+
+    while !bytes.is_empty() {
+        let (valid, invalid) = match std::str::from_utf8(bytes) {
+            Ok(valid) => (valid, &[][..]),
+            Err(err) => {
+                let (valid, rest) = bytes.split_at(err.valid_up_to());
+                let invalid_len = err.error_len().unwrap_or(rest.len());
+
+                (std::str::from_utf8(valid).unwrap(), &rest[..invalid_len])
+            },
+        };
+
+        for c in valid.chars() {
+            escape_char(c, &mut out);
+        }
+
+        for byte in invalid {
+            out.push_str(&format!("%{byte:02X}"));
+        }
+
+        bytes = &bytes[valid.len() + invalid.len()..];
+    }
+
+    Ok(out)
+}
+
+#[cfg(unix)]
+fn decode_path(value: &str) -> Option<PathBuf> {
+    use std::{ffi::OsString, os::unix::ffi::OsStringExt};
+
+    Some(PathBuf::from(OsString::from_vec(unescape(value)?)))
+}
+
+// On Windows, an unpaired surrogate code unit is written as `%uXXXX`.
+
+#[cfg(windows)]
+fn encode_path(path: &Path) -> Result<String, Error> {
+    use std::os::windows::ffi::OsStrExt;
+
+    let mut out = String::new();
+
+    for c in char::decode_utf16(path.as_os_str().encode_wide()) {
+        match c {
+            Ok(c) => escape_char(c, &mut out),
+            Err(err) => out.push_str(&format!("%u{:04X}", err.unpaired_surrogate())),
+        }
+    }
+
+    Ok(out)
+}
+
+#[cfg(windows)]
+fn decode_path(value: &str) -> Option<PathBuf> {
+    use std::{ffi::OsString, os::windows::ffi::OsStringExt};
+
+    let mut units: Vec<u16> = Vec::with_capacity(value.len());
+    let mut rest = value;
+
+    while let Some(c) = rest.chars().next() {
+        rest = &rest[c.len_utf8()..];
+
+        if c != '%' {
+            units.extend_from_slice(c.encode_utf16(&mut [0; 2]));
+        } else if let Some(hex) = rest.strip_prefix('u') {
+            units.push(u16::from_str_radix(hex.get(..4)?, 16).ok()?);
+            rest = &hex[4..];
+        } else {
+            units.push(u16::from_str_radix(rest.get(..2)?, 16).ok()?);
+            rest = &rest[2..];
+        }
+    }
+
+    Some(PathBuf::from(OsString::from_wide(&units)))
+}
+
+#[cfg(not(any(unix, windows)))]
+fn encode_path(path: &Path) -> Result<String, Error> {
+    match path.to_str() {
+        Some(path) => Ok(encode_str(path)),
+        None => Err(Error::other(format!(
+            "unable to write non-Unicode path to discovery cache: {}",
+            path.display()
+        ))),
+    }
+}
+
+#[cfg(not(any(unix, windows)))]
+fn decode_path(value: &str) -> Option<PathBuf> {
+    decode_str(value).map(PathBuf::from)
+}
+
+//======================================
+// Tests
+//======================================
+
+#[test]
+fn test_cache_entry_round_trip() {
+    let dir = crate::test_support::temp_dir("cache");
+
+    let mut player = crate::tests::test_app(
+        WolframAppType::Player,
+        (13, 3, 0),
+        "/Applications/Wolfram Engine.app/Contents/Resources/Wolfram Player.app",
+    );
+    player.app_executable =
+        Some(player.app_directory.join("Contents/MacOS/WolframPlayer"));
+
+    let mut engine = crate::tests::test_app(
+        WolframAppType::Engine,
+        (13, 3, 0),
+        "/Applications/Wolfram Engine.app",
+    );
+    engine.app_name = "100% Engine\nline 2".to_owned();
+    engine.app_version.minor_revision = Some(1);
+    engine.app_version.build_code = Some(202302011100);
+    engine.embedded_player = Some(Box::new(player));
+    engine.installation_id = Some(InstallationId::MacOSBundleIdentifier(
+        "com.wolfram.WolframEngine".to_owned(),
+    ));
+    engine.registration =
+        Some(Registration::LaunchServices(engine.app_directory.clone()));
+
+    let mut mathematica = crate::tests::test_app(
+        WolframAppType::Mathematica,
+        (14, 0, 0),
+        r"C:\Program Files\Wolfram Research\Mathematica\14.0",
+    );
+    mathematica.windows_caps = Some(WindowsCaps::from_bits(7));
+    mathematica.installation_id = Some(InstallationId::WindowsClsid("{1234}".to_owned()));
+    mathematica.registration = Some(Registration::WindowsRegistryKey {
+        key: r"HKEY_LOCAL_MACHINE\Software\Wolfram Research\Installations\1234"
+            .to_owned(),
+        wow64_32: true,
+    });
+
+    let entry = CacheEntry {
+        path: dir.join("apps"),
+        created: SystemTime::UNIX_EPOCH + Duration::from_secs(1_700_000_000),
+        apps: vec![engine, mathematica],
+    };
+
+    entry.write().unwrap();
+
+    // Only the cache file remains after writing.
+    assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+
+    let contents = fs::read_to_string(dir.join("apps")).unwrap();
+    let parsed = CacheEntry::parse(dir.join("apps"), &contents).unwrap();
+
+    assert_eq!(parsed.created(), entry.created());
+    assert_eq!(format!("{:?}", parsed.apps), format!("{:?}", entry.apps));
+
+    assert!(CacheEntry::parse(dir.join("apps"), "created=1\n").is_err());
+    assert!(CacheEntry::parse(
+        dir.join("apps"),
+        &format!("{HEADER}\ncreated=1\n\n[app]\nname=Mathematica\n")
+    )
+    .is_err());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn test_encode_non_unicode_path() {
+    use std::{ffi::OsStr, os::unix::ffi::OsStrExt};
+
+    let path = Path::new(OsStr::from_bytes(b"/a%b/\xFF\xFEc\n\xC3\xA9"));
+
+    let encoded = encode_path(path).unwrap();
+
+    assert_eq!(encoded, "/a%25b/%FF%FEc%0A\u{e9}");
+    assert_eq!(decode_path(&encoded).as_deref(), Some(path));
+}
@@ -1,6 +1,9 @@
 //! Configuration of `wolfram-app-discovery` behavior.
 
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::{
+    ffi::OsString,
+    sync::atomic::{AtomicBool, Ordering},
+};
 
 //======================================
 // Environment variable names
@@ -25,12 +28,26 @@ pub mod env_vars {
 
     /// Name of the environment variable that specifies the default Wolfram application
     /// directory.
+    ///
+    /// The value can list several application directories, separated in the same
+    /// way as the `PATH` environment variable (`:` on Unix and `;` on Windows).
+    /// They are tried in order, and are all included in the results of
+    /// [`discover()`][crate::discover].
     pub const WOLFRAM_APP_DIRECTORY: &str = "WOLFRAM_APP_DIRECTORY";
 
     /// WSTP `CompilerAdditions` directory
     #[deprecated(note = "use WSTP_COMPILER_ADDITIONS_DIRECTORY instead")]
     pub const WSTP_COMPILER_ADDITIONS: &str = "WSTP_COMPILER_ADDITIONS";
 
+    /// Minimum Wolfram Language version of the apps returned by any discovery
+    /// function, e.g. `13.1`.
+    ///
+    /// Apps with an older (or undeterminable) version are never selected as the
+    /// default app, and are omitted from the results of
+    /// [`discover()`][crate::discover]. This can be used to prevent an old
+    /// installation from being picked up by a build script.
+    pub const WOLFRAM_APP_MIN_VERSION: &str = "WOLFRAM_APP_MIN_VERSION";
+
     /// WSTP `CompilerAdditions` directory
     ///
     /// In a typical Wolfram Language installation, this is the
@@ -39,6 +56,38 @@ pub mod env_vars {
     pub const WSTP_COMPILER_ADDITIONS_DIRECTORY: &str =
         "WSTP_COMPILER_ADDITIONS_DIRECTORY";
 
+    /// Directory containing the WSTP shared library.
+    ///
+    /// In a typical Wolfram Language installation, this is the
+    /// `$InstallationDirectory/SystemFiles/Libraries/$SystemID/` directory.
+    pub const WSTP_SHARED_LIBRARY_DIRECTORY: &str = "WSTP_SHARED_LIBRARY_DIRECTORY";
+
+    /// Directory containing the Wolfram runtime libraries, like `WolframRTL`.
+    ///
+    /// In a typical Wolfram Language installation, this is the
+    /// `$InstallationDirectory/SystemFiles/Libraries/$SystemID/` directory.
+    pub const WOLFRAM_LIBRARIES_DIRECTORY: &str = "WOLFRAM_LIBRARIES_DIRECTORY";
+
+    /// Location of the [`WolframKernel`](https://reference.wolfram.com/language/ref/program/WolframKernel.html)
+    /// executable.
+    ///
+    /// See [`crate::build_scripts::kernel_executable_path()`].
+    pub const WOLFRAM_KERNEL_EXECUTABLE: &str = "WOLFRAM_KERNEL_EXECUTABLE";
+
+    /// Location of the [`wolframscript`](https://reference.wolfram.com/language/ref/program/wolframscript.html)
+    /// executable.
+    ///
+    /// See [`crate::build_scripts::wolframscript_executable_path()`].
+    pub const WOLFRAMSCRIPT_EXECUTABLE: &str = "WOLFRAMSCRIPT_EXECUTABLE";
+
+    /// Legacy MathLink `CompilerAdditions` directory
+    ///
+    /// In a typical Wolfram Language installation, this is the
+    /// `$InstallationDirectory/SystemFiles/Links/MathLink/DeveloperKit/$SystemID/CompilerAdditions/`
+    /// directory.
+    pub const MATHLINK_COMPILER_ADDITIONS_DIRECTORY: &str =
+        "MATHLINK_COMPILER_ADDITIONS_DIRECTORY";
+
     // /// *Deprecated:* Use [`WOLFRAM_LIBRARY_LINK_C_INCLUDES_DIRECTORY`] instead.
     // #[deprecated(note = "use WOLFRAM_LIBRARY_LINK_C_INCLUDES_DIRECTORY instead.")]
 
@@ -52,6 +101,50 @@ pub mod env_vars {
     /// `$InstallationDirectory/SystemFiles/IncludeFiles/C/` directory.
     pub const WOLFRAM_LIBRARY_LINK_C_INCLUDES_DIRECTORY: &str =
         "WOLFRAM_LIBRARY_LINK_C_INCLUDES_DIRECTORY";
+
+    /// [`$SystemID`](https://reference.wolfram.com/language/ref/$SystemID)
+    /// to use when resolving target-specific resources, like the WSTP SDK.
+    ///
+    /// By default, the target System ID is derived from the Rust target this
+    /// library was compiled for. Setting this variable can be necessary when
+    /// building inside an emulated container, where the compile-time target
+    /// does not match the Wolfram installation being built against.
+    ///
+    /// The value must be a System ID string like `Linux-ARM64`.
+    pub const WOLFRAM_TARGET_SYSTEM_ID: &str = "WOLFRAM_TARGET_SYSTEM_ID";
+
+    /// MathLM network license server to use, in the form `host` or
+    /// `host:port`.
+    ///
+    /// See [`crate::license::license_servers()`].
+    pub const WOLFRAM_LICENSE_SERVER: &str = "WOLFRAM_LICENSE_SERVER";
+
+    /// Location of the Wolfram Language
+    /// [`$BaseDirectory`](https://reference.wolfram.com/language/ref/$BaseDirectory.html).
+    ///
+    /// This is also read by the Wolfram kernel. See
+    /// [`crate::directories::base_directory()`].
+    pub const WOLFRAM_BASE: &str = "WOLFRAM_BASE";
+
+    /// Location of the Wolfram Language
+    /// [`$UserBaseDirectory`](https://reference.wolfram.com/language/ref/$UserBaseDirectory.html).
+    ///
+    /// This is also read by the Wolfram kernel. See
+    /// [`crate::directories::user_base_directory()`].
+    pub const WOLFRAM_USERBASE: &str = "WOLFRAM_USERBASE";
+
+    /// If set to `1` or `true`, `wolframscript` is not used to locate the
+    /// default app.
+    ///
+    /// See [`DiscoveryOptions::skip_wolframscript`][crate::DiscoveryOptions::skip_wolframscript].
+    pub const WOLFRAM_APP_DISCOVERY_DISABLE_WOLFRAMSCRIPT: &str =
+        "WOLFRAM_APP_DISCOVERY_DISABLE_WOLFRAMSCRIPT";
+
+    /// Directory containing the persistent discovery cache.
+    ///
+    /// See [`crate::cache::cache_file()`].
+    pub const WOLFRAM_APP_DISCOVERY_CACHE_DIRECTORY: &str =
+        "WOLFRAM_APP_DISCOVERY_CACHE_DIRECTORY";
 }
 
 static PRINT_CARGO_INSTRUCTIONS: AtomicBool = AtomicBool::new(false);
@@ -88,31 +181,56 @@ fn should_print_cargo_build_script_directives() -> bool {
 // Helpers
 //======================================
 
+/// Print a `cargo:` build script directive to stdout, if
+/// [`set_print_cargo_build_script_directives()`] has been enabled.
+///
+/// This is the only function in this library that writes to stdout. Any other
+/// output written to stdout could be misinterpreted by Cargo when this library
+/// is used from a build script.
+#[allow(clippy::print_stdout)]
+pub(crate) fn print_cargo_directive(directive: &str) {
+    debug_assert!(directive.starts_with("cargo:"));
+
+    if should_print_cargo_build_script_directives() {
+        println!("{directive}");
+    }
+}
+
 pub(crate) fn print_deprecated_env_var_warning(var: &str, value: &str) {
     let message = format!(
         "wolfram-app-discovery: warning: use of deprecated environment variable '{var}' (value={value:?})",
     );
 
     // Print to stderr.
-    eprintln!("{message}");
+    crate::warning_sink(&message);
 
     // If this is a cargo build script, print a directive that Cargo will
     // highlight to the user.
-    if should_print_cargo_build_script_directives() {
-        println!("cargo:warning={message}");
-    }
+    print_cargo_directive(&format!("cargo:warning={message}"));
 }
 
 pub(crate) fn get_env_var(var: &'static str) -> Option<String> {
-    if should_print_cargo_build_script_directives() {
-        println!("cargo:rerun-if-env-changed={}", var);
-    }
+    print_cargo_directive(&format!("cargo:rerun-if-env-changed={var}"));
 
-    match std::env::var(var) {
+    match env_var_os(var)?.into_string() {
         Ok(string) => Some(string),
-        Err(std::env::VarError::NotPresent) => None,
-        Err(std::env::VarError::NotUnicode(err)) => {
+        Err(err) => {
             panic!("value of env var '{}' is not valid unicode: {:?}", var, err)
         },
     }
 }
+
+/// Get the value of the environment variable `var`.
+///
+/// In unit tests, a value set on the current thread by
+/// `test_support::EnvVarOverride` takes precedence over the process
+/// environment, so that tests running in parallel do not observe each
+/// other's environment variables.
+pub(crate) fn env_var_os(var: &str) -> Option<OsString> {
+    #[cfg(test)]
+    if let Some(value) = crate::test_support::env_var_override(var) {
+        return value;
+    }
+
+    std::env::var_os(var)
+}
@@ -0,0 +1,183 @@
+//! Discovery of the Wolfram Language base directories.
+//!
+//! The [`$BaseDirectory`][ref/$BaseDirectory] (system-wide) and
+//! [`$UserBaseDirectory`][ref/$UserBaseDirectory] (per-user) directories
+//! contain configuration shared by all Wolfram installations on a computer,
+//! like `Kernel/init.m` files, installed paclets, and `Licensing` files.
+//!
+//! Older Wolfram products use a `Mathematica` base directory instead of a
+//! `Wolfram` base directory. If only the `Mathematica` directory exists, it
+//! is returned.
+//!
+//! Wolfram Language packages installed by users are stored in the
+//! `Applications` subdirectory of these directories. See
+//! [`applications_directory()`] and [`user_applications_directory()`].
+//!
+//! [ref/$BaseDirectory]: https://reference.wolfram.com/language/ref/$BaseDirectory.html
+//! [ref/$UserBaseDirectory]: https://reference.wolfram.com/language/ref/$UserBaseDirectory.html
+
+use std::path::PathBuf;
+
+use crate::{
+    config::{
+        self,
+        env_vars::{WOLFRAM_BASE, WOLFRAM_USERBASE},
+    },
+    os::OperatingSystem,
+    Error,
+};
+
+/// Get the system-wide
+/// [`$BaseDirectory`](https://reference.wolfram.com/language/ref/$BaseDirectory.html).
+///
+/// If the [`WOLFRAM_BASE`] environment variable is set, its value is
+/// returned. Otherwise, the default location for the current operating system
+/// is returned:
+///
+/// | OS      | Location                   |
+/// |---------|----------------------------|
+/// | macOS   | `/Library/Wolfram`         |
+/// | Windows | `%PROGRAMDATA%\Wolfram`    |
+/// | Linux   | `/usr/share/Wolfram`       |
+///
+/// The returned directory is not guaranteed to exist.
+pub fn base_directory() -> Result<PathBuf, Error> {
+    if let Some(dir) = config::get_env_var(WOLFRAM_BASE) {
+        return Ok(PathBuf::from(dir));
+    }
+
+    let root = match OperatingSystem::target_os() {
+        OperatingSystem::MacOS => Some(PathBuf::from("/Library")),
+        OperatingSystem::Windows => std::env::var_os("PROGRAMDATA").map(PathBuf::from),
+        OperatingSystem::Linux => Some(PathBuf::from("/usr/share")),
+        OperatingSystem::Other => None,
+    };
+
+    match root {
+        Some(root) => Ok(wolfram_or_legacy_directory(root, "Wolfram", "Mathematica")),
+        None => Err(Error::undiscoverable(
+            "$BaseDirectory".to_owned(),
+            Some(WOLFRAM_BASE),
+        )),
+    }
+}
+
+/// Get the
+/// [`$UserBaseDirectory`](https://reference.wolfram.com/language/ref/$UserBaseDirectory.html)
+/// of the current user.
+///
+/// If the [`WOLFRAM_USERBASE`] environment variable is set, its value is
+/// returned. Otherwise, the default location for the current operating system
+/// is returned:
+///
+/// | OS      | Location                   |
+/// |---------|----------------------------|
+/// | macOS   | `~/Library/Wolfram`        |
+/// | Windows | `%APPDATA%\Wolfram`        |
+/// | Linux   | `~/.Wolfram`               |
+///
+/// The returned directory is not guaranteed to exist.
+pub fn user_base_directory() -> Result<PathBuf, Error> {
+    if let Some(dir) = config::get_env_var(WOLFRAM_USERBASE) {
+        return Ok(PathBuf::from(dir));
+    }
+
+    let home = |var: &str| std::env::var_os(var).map(PathBuf::from);
+
+    let dir = match OperatingSystem::target_os() {
+        OperatingSystem::MacOS => home("HOME").map(|home| {
+            wolfram_or_legacy_directory(home.join("Library"), "Wolfram", "Mathematica")
+        }),
+        OperatingSystem::Windows => home("APPDATA").map(|appdata| {
+            wolfram_or_legacy_directory(appdata, "Wolfram", "Mathematica")
+        }),
+        OperatingSystem::Linux => home("HOME")
+            .map(|home| wolfram_or_legacy_directory(home, ".Wolfram", ".Mathematica")),
+        OperatingSystem::Other => None,
+    };
+
+    dir.ok_or_else(|| {
+        Error::undiscoverable("$UserBaseDirectory".to_owned(), Some(WOLFRAM_USERBASE))
+    })
+}
+
+/// Get the system-wide `Applications` add-ons directory, which contains
+/// Wolfram Language packages installed for all users.
+///
+/// This is the `Applications` subdirectory of [`base_directory()`]. The
+/// returned directory is not guaranteed to exist.
+pub fn applications_directory() -> Result<PathBuf, Error> {
+    Ok(base_directory()?.join("Applications"))
+}
+
+/// Get the `Applications` add-ons directory of the current user, which
+/// contains Wolfram Language packages installed by the user.
+///
+/// This is the `Applications` subdirectory of [`user_base_directory()`]. The
+/// returned directory is not guaranteed to exist.
+pub fn user_applications_directory() -> Result<PathBuf, Error> {
+    Ok(user_base_directory()?.join("Applications"))
+}
+
+//======================================
+// Helpers
+//======================================
+
+/// Returns `root/legacy` if only that directory exists, and `root/name`
+/// otherwise.
+fn wolfram_or_legacy_directory(root: PathBuf, name: &str, legacy: &str) -> PathBuf {
+    let dir = root.join(name);
+    let legacy = root.join(legacy);
+
+    if !dir.is_dir() && legacy.is_dir() {
+        return legacy;
+    }
+
+    dir
+}
+
+//======================================
+// Tests
+//======================================
+
+#[test]
+fn test_wolfram_or_legacy_directory() {
+    let root = crate::test_support::temp_dir("base-directory");
+
+    assert_eq!(
+        wolfram_or_legacy_directory(root.clone(), "Wolfram", "Mathematica"),
+        root.join("Wolfram")
+    );
+
+    std::fs::create_dir(root.join("Mathematica")).unwrap();
+
+    assert_eq!(
+        wolfram_or_legacy_directory(root.clone(), "Wolfram", "Mathematica"),
+        root.join("Mathematica")
+    );
+
+    std::fs::create_dir(root.join("Wolfram")).unwrap();
+
+    assert_eq!(
+        wolfram_or_legacy_directory(root.clone(), "Wolfram", "Mathematica"),
+        root.join("Wolfram")
+    );
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn test_user_base_directory_env() {
+    use crate::test_support::EnvVarOverride;
+
+    let _userbase = EnvVarOverride::with_value(WOLFRAM_USERBASE, "/custom/userbase");
+
+    assert_eq!(
+        user_base_directory().unwrap(),
+        PathBuf::from("/custom/userbase")
+    );
+    assert_eq!(
+        user_applications_directory().unwrap(),
+        PathBuf::from("/custom/userbase/Applications")
+    );
+}
@@ -0,0 +1,162 @@
+//! Individual discovery steps used by [`WolframApp::try_default()`].
+//!
+//! Most programs should use [`WolframApp::try_default()`] or
+//! [`discover()`][crate::discover] instead of the functions in this module.
+//! These functions are provided for programs that want to perform only a
+//! single discovery step.
+//!
+//! [`WolframApp::try_default()`]: crate::WolframApp::try_default
+
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+};
+
+use crate::os::OperatingSystem;
+
+/// Command-line program provided by a Wolfram installation.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum WolframTool {
+    /// [`wolframscript`](https://reference.wolfram.com/language/ref/program/wolframscript.html)
+    WolframScript,
+    /// [`WolframKernel`](https://reference.wolfram.com/language/ref/program/WolframKernel.html)
+    WolframKernel,
+}
+
+impl WolframTool {
+    /// Name of this tool's executable, without any file extension.
+    fn base_name(&self) -> &'static str {
+        match self {
+            WolframTool::WolframScript => "wolframscript",
+            WolframTool::WolframKernel => "WolframKernel",
+        }
+    }
+}
+
+/// Find the location of `tool` on the `PATH` of the current process.
+///
+/// Each directory in `PATH` is searched in order, and the first file that
+/// is executable by the current user is returned. This follows the same rules
+/// used by a shell to resolve a command name:
+///
+/// * On Windows, each extension listed in the `PATHEXT` environment variable
+///   is tried (defaulting to `.COM;.EXE;.BAT;.CMD`).
+/// * On Unix, files that are not executable are skipped.
+/// * Empty and relative `PATH` entries are skipped, and a leading `~` is
+///   expanded to the user's home directory, because `PATH` values written to
+///   per-user shell configuration files do not always have `~` expanded.
+///
+/// If the executable found is a symbolic link, the returned path is the
+/// resolved target of the link, so that it refers to the location of the tool
+/// within its Wolfram installation.
+///
+/// Returns `None` if `tool` could not be found on `PATH`.
+pub fn resolve_on_path(tool: WolframTool) -> Option<PathBuf> {
+    let path_var = crate::config::env_var_os("PATH")?;
+
+    let file_names = executable_file_names(tool.base_name());
+
+    for dir in std::env::split_paths(&path_var) {
+        let Some(dir) = normalize_path_entry(dir) else {
+            continue;
+        };
+
+        for file_name in &file_names {
+            let candidate = dir.join(file_name);
+
+            if is_executable_file(&candidate) {
+                return Some(candidate.canonicalize().unwrap_or(candidate));
+            }
+        }
+    }
+
+    None
+}
+
+//======================================
+// Helpers
+//======================================
+
+/// File names that a command named `base_name` may resolve to.
+fn executable_file_names(base_name: &str) -> Vec<OsString> {
+    if OperatingSystem::target_os() != OperatingSystem::Windows {
+        return vec![OsString::from(base_name)];
+    }
+
+    let path_ext = std::env::var("PATHEXT")
+        .ok()
+        .filter(|value| !value.trim().is_empty())
+        .unwrap_or_else(|| ".COM;.EXE;.BAT;.CMD".to_owned());
+
+    path_ext
+        .split(';')
+        .map(str::trim)
+        .filter(|ext| !ext.is_empty())
+        .map(|ext| OsString::from(format!("{base_name}{}", ext.to_ascii_lowercase())))
+        .collect()
+}
+
+/// Expand a leading `~` in a `PATH` entry, and skip entries that are empty or
+/// relative.
+fn normalize_path_entry(dir: PathBuf) -> Option<PathBuf> {
+    let dir = match dir.strip_prefix("~") {
+        Ok(rest) => {
+            let home = std::env::var_os(match OperatingSystem::target_os() {
+                OperatingSystem::Windows => "USERPROFILE",
+                _ => "HOME",
+            })?;
+
+            PathBuf::from(home).join(rest)
+        },
+        Err(_) => dir,
+    };
+
+    if dir.as_os_str().is_empty() || dir.is_relative() {
+        return None;
+    }
+
+    Some(dir)
+}
+
+#[cfg(unix)]
+fn is_executable_file(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    // Note: `metadata()` follows symbolic links.
+    match std::fs::metadata(path) {
+        Ok(metadata) => metadata.is_file() && metadata.permissions().mode() & 0o111 != 0,
+        Err(_) => false,
+    }
+}
+
+#[cfg(not(unix))]
+fn is_executable_file(path: &Path) -> bool {
+    path.is_file()
+}
+
+//======================================
+// Tests
+//======================================
+
+#[cfg(unix)]
+#[test]
+fn test_resolve_on_path() {
+    use crate::test_support::{FakeBehavior, FakeWolframScript};
+
+    {
+        let fake = FakeWolframScript::install(
+            "resolve-on-path",
+            FakeBehavior::InstallationDirectory(PathBuf::from("/unused")),
+        );
+
+        let path = resolve_on_path(WolframTool::WolframScript)
+            .expect("fake wolframscript was not found on PATH");
+
+        assert!(path.is_absolute());
+        assert_eq!(path, fake.path().canonicalize().unwrap());
+    }
+
+    assert_eq!(normalize_path_entry(PathBuf::from("")), None);
+    assert_eq!(normalize_path_entry(PathBuf::from("relative/bin")), None);
+}
@@ -0,0 +1,302 @@
+//! Information needed to launch and connect to a local Wolfram kernel.
+//!
+//! [`KernelDiscovery`] collects everything a WSTP client needs to launch a
+//! local [`WolframKernel`][WolframKernel] and connect to it: the kernel
+//! executable, the command-line arguments that make the kernel listen for a
+//! WSTP connection, and the environment the kernel should be launched with.
+//!
+//! Crates that open kernel connections (like [wstp] and [wolfram-client]) can
+//! depend on this module instead of duplicating kernel path logic. The
+//! [`KernelDiscovery::VERSION`] constant is incremented whenever the meaning
+//! of the returned launch information changes, so that dependent crates can
+//! detect incompatible changes.
+//!
+//! [WolframKernel]: https://reference.wolfram.com/language/ref/program/WolframKernel.html
+//! [wstp]: https://crates.io/crates/wstp
+//! [wolfram-client]: https://crates.io/crates/wolfram-client
+//!
+//! # Examples
+//!
+//! Launch the default Wolfram kernel so that it creates a WSTP link:
+//!
+//! ```no_run
+//! use wolfram_app_discovery::kernel::KernelDiscovery;
+//!
+//! let kernel = KernelDiscovery::try_default()
+//!     .expect("unable to locate a Wolfram kernel");
+//!
+//! println!(
+//!     "launching Wolfram Language {} kernel: {}",
+//!     kernel.wolfram_version(),
+//!     kernel.kernel_executable().display()
+//! );
+//!
+//! // `command()` applies the recommended arguments and environment. A WSTP
+//! // client would additionally pass the name of the link the kernel should
+//! // connect to, e.g. `-linkname <name>`.
+//! let mut command = kernel.command();
+//! command.args(["-linkmode", "connect", "-linkname", "1234@localhost"]);
+//!
+//! let child = command.spawn().expect("unable to launch kernel");
+//! ```
+
+use std::{ffi::OsString, path::PathBuf, process};
+
+#[cfg(feature = "kernel-properties")]
+use std::{str::FromStr, time::Duration};
+
+#[cfg(feature = "kernel-properties")]
+use crate::SystemID;
+use crate::{Error, WolframApp, WolframVersion};
+
+/// Properties of a Wolfram Language installation, as reported by its kernel.
+///
+/// Use [`WolframApp::kernel_properties()`] to launch the kernel of an app and
+/// query these values.
+///
+/// *This type is only available if the `kernel-properties` feature is enabled.*
+#[cfg(feature = "kernel-properties")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KernelProperties {
+    version: String,
+    wolfram_version: WolframVersion,
+    system_id: SystemID,
+    license_id: String,
+}
+
+/// Everything needed to launch a local Wolfram kernel that accepts a WSTP
+/// connection.
+///
+/// See the [module documentation](self) for an example.
+#[derive(Debug, Clone)]
+pub struct KernelDiscovery {
+    kernel_executable: PathBuf,
+    installation_directory: PathBuf,
+    wolfram_version: WolframVersion,
+    launch_args: Vec<String>,
+    environment: Vec<(OsString, OsString)>,
+}
+
+impl KernelDiscovery {
+    /// Version of the launch information returned by this type.
+    ///
+    /// This is incremented whenever the recommended launch arguments or
+    /// environment change in a way that affects how the kernel behaves.
+    pub const VERSION: u32 = 1;
+
+    /// Get the launch information for the kernel of the default Wolfram
+    /// Language installation.
+    ///
+    /// See [`WolframApp::try_default()`] for how the default installation is
+    /// located.
+    pub fn try_default() -> Result<Self, Error> {
+        let app = WolframApp::try_default()?;
+
+        KernelDiscovery::from_app(&app)
+    }
+
+    /// Get the launch information for the kernel provided by `app`.
+    pub fn from_app(app: &WolframApp) -> Result<Self, Error> {
+        Ok(KernelDiscovery {
+            kernel_executable: app.kernel_executable_path()?,
+            installation_directory: app.installation_directory(),
+            wolfram_version: app.wolfram_version()?,
+            launch_args: vec!["-wstp".to_owned()],
+            environment: Vec::new(),
+        })
+    }
+
+    /// Location of the
+    /// [`WolframKernel`](https://reference.wolfram.com/language/ref/program/WolframKernel.html)
+    /// executable.
+    pub fn kernel_executable(&self) -> &PathBuf {
+        &self.kernel_executable
+    }
+
+    /// The [`$InstallationDirectory`](https://reference.wolfram.com/language/ref/$InstallationDirectory.html)
+    /// of the installation the kernel belongs to.
+    pub fn installation_directory(&self) -> &PathBuf {
+        &self.installation_directory
+    }
+
+    /// Version of the Wolfram Language provided by the kernel.
+    pub fn wolfram_version(&self) -> &WolframVersion {
+        &self.wolfram_version
+    }
+
+    /// Recommended command-line arguments for launching the kernel so that it
+    /// communicates over WSTP.
+    ///
+    /// Currently this is `["-wstp"]`.
+    pub fn launch_args(&self) -> &[String] {
+        &self.launch_args
+    }
+
+    /// Environment variables that should be set in the kernel process, in
+    /// addition to the environment inherited from the current process.
+    ///
+    /// This is currently empty on all platforms. Clients should still apply
+    /// these values, so that future versions can add entries without requiring
+    /// client changes.
+    pub fn environment(&self) -> &[(OsString, OsString)] {
+        &self.environment
+    }
+
+    /// Construct a [`Command`][process::Command] that launches the kernel with
+    /// the recommended [arguments][Self::launch_args] and
+    /// [environment][Self::environment].
+    pub fn command(&self) -> process::Command {
+        let mut command = process::Command::new(&self.kernel_executable);
+
+        command.args(&self.launch_args);
+        command.envs(self.environment.iter().map(|(key, value)| (key, value)));
+
+        command
+    }
+}
+
+#[cfg(feature = "kernel-properties")]
+impl KernelProperties {
+    /// Environment variable used to pass the location of the output file to
+    /// the kernel.
+    ///
+    /// Passing the path in the environment avoids having to escape it inside
+    /// the Wolfram Language code string.
+    const OUTPUT_VAR: &'static str = "WOLFRAM_APP_DISCOVERY_KERNEL_OUTPUT";
+
+    /// Wolfram Language code that writes each queried value on its own line of
+    /// the output file, and then exits.
+    const CODE: &'static str = r#"With[{stream = OpenWrite[Environment["WOLFRAM_APP_DISCOVERY_KERNEL_OUTPUT"], CharacterEncoding -> "UTF-8"]}, WriteString[stream, StringRiffle[ToString /@ {$Version, $SystemID, $LicenseID, $VersionNumber, $ReleaseNumber}, "\n"]]; Close[stream]]; Exit[0]"#;
+
+    /// Launch the kernel of `app` and query its properties.
+    pub(crate) fn query(
+        app: &WolframApp,
+        timeout: Option<Duration>,
+    ) -> Result<Self, Error> {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+        let output_file = std::env::temp_dir().join(format!(
+            "wolfram-app-discovery-kernel-{}-{}.txt",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+
+        let mut command = app.kernel_command()?;
+        command
+            .args(["-noinit", "-noprompt", "-run", KernelProperties::CODE])
+            .env(KernelProperties::OUTPUT_VAR, &output_file);
+
+        let result = crate::command_output(command, timeout);
+
+        let contents = std::fs::read_to_string(&output_file);
+        let _ = std::fs::remove_file(&output_file);
+
+        let output = match result {
+            Ok(output) => output,
+            Err(err) if err.kind() == std::io::ErrorKind::TimedOut => {
+                return Err(Error::other(format!("kernel timed out: {err}")));
+            },
+            Err(err) => {
+                return Err(Error::other(format!(
+                    "unable to execute kernel command: {err}"
+                )));
+            },
+        };
+
+        if !output.status.success() {
+            return Err(Error::other(format!(
+                "kernel exited with non-success status code: {}",
+                output.status
+            )));
+        }
+
+        let contents = contents.map_err(|err| {
+            Error::other(format!("unable to read kernel properties output: {err}"))
+        })?;
+
+        KernelProperties::parse(&contents)
+    }
+
+    fn parse(contents: &str) -> Result<Self, Error> {
+        let unexpected =
+            || Error::other(format!("unexpected kernel properties output: {contents:?}"));
+
+        let lines: Vec<&str> = contents.lines().collect();
+
+        let [version, system_id, license_id, version_number, release_number] =
+            <[&str; 5]>::try_from(lines).map_err(|_| unexpected())?;
+
+        let release_number = u32::from_str(release_number).map_err(|_| unexpected())?;
+
+        let wolfram_version =
+            WolframVersion::parse_version_number(version_number, release_number)
+                .ok_or_else(unexpected)?;
+
+        let system_id = SystemID::from_str(system_id).map_err(|()| {
+            Error::other(format!("kernel reported unknown $SystemID: {system_id}"))
+        })?;
+
+        Ok(KernelProperties {
+            version: version.to_owned(),
+            wolfram_version,
+            system_id,
+            license_id: license_id.to_owned(),
+        })
+    }
+
+    /// The [`$Version`](https://reference.wolfram.com/language/ref/$Version.html)
+    /// string of the kernel.
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Wolfram Language version number, from
+    /// [`$VersionNumber`](https://reference.wolfram.com/language/ref/$VersionNumber.html)
+    /// and [`$ReleaseNumber`](https://reference.wolfram.com/language/ref/$ReleaseNumber.html).
+    pub fn wolfram_version(&self) -> &WolframVersion {
+        &self.wolfram_version
+    }
+
+    /// The [`$SystemID`](https://reference.wolfram.com/language/ref/$SystemID.html)
+    /// of the kernel.
+    pub fn system_id(&self) -> SystemID {
+        self.system_id
+    }
+
+    /// The [`$LicenseID`](https://reference.wolfram.com/language/ref/$LicenseID.html)
+    /// of the kernel.
+    pub fn license_id(&self) -> &str {
+        &self.license_id
+    }
+}
+
+//======================================
+// Tests
+//======================================
+
+#[cfg(feature = "kernel-properties")]
+#[test]
+fn test_parse_kernel_properties() {
+    let properties = KernelProperties::parse(
+        "14.0.0 for Linux x86 (64-bit) (December 13, 2023)\nLinux-x86-64\nL1234-5678\n14.\n0",
+    )
+    .unwrap();
+
+    assert_eq!(
+        properties.version(),
+        "14.0.0 for Linux x86 (64-bit) (December 13, 2023)"
+    );
+    assert_eq!(properties.wolfram_version(), &WolframVersion::new(14, 0, 0));
+    assert_eq!(properties.system_id(), SystemID::Linux_x86_64);
+    assert_eq!(properties.license_id(), "L1234-5678");
+
+    let properties =
+        KernelProperties::parse("13.1.0\nMacOSX-ARM64\nL0\n13.1\n2").unwrap();
+
+    assert_eq!(properties.wolfram_version(), &WolframVersion::new(13, 1, 2));
+
+    assert!(KernelProperties::parse("").is_err());
+    assert!(KernelProperties::parse("14.0.0\nUnknown\nL0\n14.\n0").is_err());
+}
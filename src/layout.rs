@@ -0,0 +1,428 @@
+//! Locations of files and directories within a Wolfram installation.
+//!
+//! The location of each [`LayoutEntry`] relative to the
+//! [`$InstallationDirectory`](https://reference.wolfram.com/language/ref/$InstallationDirectory.html)
+//! of an app depends on the operating system, and can differ between Wolfram
+//! versions. That knowledge is recorded in a single table in this module, which
+//! is used by [`WolframApp::relative_path()`] and [`WolframApp::resolve()`], and
+//! by accessors like [`WolframApp::kernel_executable_path()`].
+
+use std::path::PathBuf;
+
+use crate::{os::OperatingSystem, Error, SystemID, WolframApp};
+
+/// A file or directory whose location within a Wolfram installation is known.
+///
+/// See [`WolframApp::resolve()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum LayoutEntry {
+    /// The [`WolframKernel`](https://reference.wolfram.com/language/ref/program/WolframKernel.html)
+    /// executable.
+    KernelExecutable,
+    /// The [`wolframscript`](https://reference.wolfram.com/language/ref/program/wolframscript.html)
+    /// executable.
+    WolframScriptExecutable,
+    /// Directory containing the front end launcher executables.
+    FrontEndExecutablesDirectory,
+    /// Directory containing a subdirectory of kernel binaries for each
+    /// supported [`SystemID`][crate::SystemID].
+    KernelBinariesDirectory,
+    /// Directory containing a WSTP SDK for each supported
+    /// [`SystemID`][crate::SystemID].
+    WstpDeveloperKitDirectory,
+    /// Directory containing the Wolfram *LibraryLink* C header files.
+    LibraryLinkIncludesDirectory,
+    /// Directory containing the Wolfram runtime libraries for the
+    /// [target `SystemID`][WolframApp::target_system_id].
+    LibrariesDirectory,
+    /// `CompilerAdditions` directory of the legacy MathLink SDK for the
+    /// [target `SystemID`][WolframApp::target_system_id].
+    MathLinkCompilerAdditionsDirectory,
+    /// Directory containing the [.NET/Link](https://reference.wolfram.com/language/NETLink/tutorial/Overview.html)
+    /// assemblies.
+    NetLinkDirectory,
+    /// Directory containing the local copy of the Wolfram Language
+    /// documentation.
+    DocumentationDirectory,
+    /// Directory containing paclets bundled with the installation.
+    PacletsDirectory,
+    /// Directory containing the Wolfram link libraries, like WSTP and
+    /// J/Link, many of which are paclets.
+    LinksDirectory,
+    /// `AddOns/Applications` directory, containing Wolfram Language packages
+    /// bundled with the installation.
+    AddOnsApplicationsDirectory,
+}
+
+//======================================
+// Layout table
+//======================================
+
+/// A component of a relative path in the layout table.
+enum Component {
+    /// A fixed file or directory name.
+    Name(&'static str),
+    /// The [target `SystemID`][WolframApp::target_system_id] of the app.
+    SystemId,
+}
+
+/// Range of Wolfram Language versions that a [`Rule`] applies to.
+#[derive(Copy, Clone)]
+struct Versions {
+    /// First `(major, minor)` version this rule applies to.
+    since: (u32, u32),
+    /// First `(major, minor)` version this rule no longer applies to.
+    until: Option<(u32, u32)>,
+}
+
+/// Location of `entry` on `os`, for apps whose version is in `versions`.
+struct Rule {
+    entry: LayoutEntry,
+    os: OperatingSystem,
+    versions: Versions,
+    path: &'static [Component],
+}
+
+/// Every Wolfram version.
+const ALL: Versions = Versions {
+    since: (0, 0),
+    until: None,
+};
+
+/// Wolfram versions before `major.minor`.
+const fn before(major: u32, minor: u32) -> Versions {
+    Versions {
+        since: (0, 0),
+        until: Some((major, minor)),
+    }
+}
+
+/// Wolfram versions starting at `major.minor`.
+const fn since(major: u32, minor: u32) -> Versions {
+    Versions {
+        since: (major, minor),
+        until: None,
+    }
+}
+
+/// Wolfram versions starting at `since`, and before `until`.
+const fn between(since: (u32, u32), until: (u32, u32)) -> Versions {
+    Versions {
+        since,
+        until: Some(until),
+    }
+}
+
+/// Rules are searched in order, and the first matching rule is used.
+#[rustfmt::skip]
+const LAYOUT: &[Rule] = {
+    use self::{
+        Component::{Name, SystemId},
+        LayoutEntry::*,
+        OperatingSystem::{Linux, MacOS, Windows},
+    };
+
+    &[
+        // NOTE: The Linux paths below are empirically valid for:
+        //     - Mathematica    (tested: 13.1)
+        //     - Wolfram Engine (tested: 13.0, 13.3 prerelease)
+        // TODO: Are they correct for Wolfram Desktop?
+        // The kernel executable was named MathKernel before 10.0, and on macOS
+        // was in the MacOSX directory before 12.0.
+        Rule { entry: KernelExecutable, os: MacOS, versions: since(12, 0), path: &[Name("MacOS"), Name("WolframKernel")] },
+        Rule { entry: KernelExecutable, os: MacOS, versions: between((10, 0), (12, 0)), path: &[Name("MacOSX"), Name("WolframKernel")] },
+        Rule { entry: KernelExecutable, os: MacOS, versions: before(10, 0), path: &[Name("MacOSX"), Name("MathKernel")] },
+        Rule { entry: KernelExecutable, os: Windows, versions: since(10, 0), path: &[Name("WolframKernel.exe")] },
+        Rule { entry: KernelExecutable, os: Windows, versions: before(10, 0), path: &[Name("MathKernel.exe")] },
+        Rule { entry: KernelExecutable, os: Linux, versions: since(10, 0), path: &[Name("Executables"), Name("WolframKernel")] },
+        Rule { entry: KernelExecutable, os: Linux, versions: before(10, 0), path: &[Name("Executables"), Name("MathKernel")] },
+
+        Rule { entry: WolframScriptExecutable, os: MacOS, versions: since(12, 0), path: &[Name("MacOS"), Name("wolframscript")] },
+        Rule { entry: WolframScriptExecutable, os: MacOS, versions: before(12, 0), path: &[Name("MacOSX"), Name("wolframscript")] },
+        Rule { entry: WolframScriptExecutable, os: Windows, versions: ALL, path: &[Name("wolframscript.exe")] },
+        Rule { entry: WolframScriptExecutable, os: Linux, versions: ALL, path: &[Name("SystemFiles"), Name("Kernel"), Name("Binaries"), SystemId, Name("wolframscript")] },
+
+        // NOTE: On Linux, `Executables/wolfram` (lowercase) is a kernel
+        //       launcher, not a front end launcher.
+        Rule { entry: FrontEndExecutablesDirectory, os: MacOS, versions: ALL, path: &[Name("MacOS")] },
+        Rule { entry: FrontEndExecutablesDirectory, os: Windows, versions: ALL, path: &[] },
+        Rule { entry: FrontEndExecutablesDirectory, os: Linux, versions: ALL, path: &[Name("Executables")] },
+
+        Rule { entry: KernelBinariesDirectory, os: MacOS, versions: ALL, path: &[Name("SystemFiles"), Name("Kernel"), Name("Binaries")] },
+        Rule { entry: KernelBinariesDirectory, os: Windows, versions: ALL, path: &[Name("SystemFiles"), Name("Kernel"), Name("Binaries")] },
+        Rule { entry: KernelBinariesDirectory, os: Linux, versions: ALL, path: &[Name("SystemFiles"), Name("Kernel"), Name("Binaries")] },
+
+        Rule { entry: WstpDeveloperKitDirectory, os: MacOS, versions: ALL, path: &[Name("SystemFiles"), Name("Links"), Name("WSTP"), Name("DeveloperKit")] },
+        Rule { entry: WstpDeveloperKitDirectory, os: Windows, versions: ALL, path: &[Name("SystemFiles"), Name("Links"), Name("WSTP"), Name("DeveloperKit")] },
+        Rule { entry: WstpDeveloperKitDirectory, os: Linux, versions: ALL, path: &[Name("SystemFiles"), Name("Links"), Name("WSTP"), Name("DeveloperKit")] },
+
+        Rule { entry: LibraryLinkIncludesDirectory, os: MacOS, versions: ALL, path: &[Name("SystemFiles"), Name("IncludeFiles"), Name("C")] },
+        Rule { entry: LibraryLinkIncludesDirectory, os: Windows, versions: ALL, path: &[Name("SystemFiles"), Name("IncludeFiles"), Name("C")] },
+        Rule { entry: LibraryLinkIncludesDirectory, os: Linux, versions: ALL, path: &[Name("SystemFiles"), Name("IncludeFiles"), Name("C")] },
+
+        Rule { entry: LibrariesDirectory, os: MacOS, versions: ALL, path: &[Name("SystemFiles"), Name("Libraries"), SystemId] },
+        Rule { entry: LibrariesDirectory, os: Windows, versions: ALL, path: &[Name("SystemFiles"), Name("Libraries"), SystemId] },
+        Rule { entry: LibrariesDirectory, os: Linux, versions: ALL, path: &[Name("SystemFiles"), Name("Libraries"), SystemId] },
+
+        Rule { entry: MathLinkCompilerAdditionsDirectory, os: MacOS, versions: ALL, path: &[Name("SystemFiles"), Name("Links"), Name("MathLink"), Name("DeveloperKit"), SystemId, Name("CompilerAdditions")] },
+        Rule { entry: MathLinkCompilerAdditionsDirectory, os: Windows, versions: ALL, path: &[Name("SystemFiles"), Name("Links"), Name("MathLink"), Name("DeveloperKit"), SystemId, Name("CompilerAdditions")] },
+        Rule { entry: MathLinkCompilerAdditionsDirectory, os: Linux, versions: ALL, path: &[Name("SystemFiles"), Name("Links"), Name("MathLink"), Name("DeveloperKit"), SystemId, Name("CompilerAdditions")] },
+
+        Rule { entry: NetLinkDirectory, os: MacOS, versions: ALL, path: &[Name("SystemFiles"), Name("Links"), Name("NETLink")] },
+        Rule { entry: NetLinkDirectory, os: Windows, versions: ALL, path: &[Name("SystemFiles"), Name("Links"), Name("NETLink")] },
+        Rule { entry: NetLinkDirectory, os: Linux, versions: ALL, path: &[Name("SystemFiles"), Name("Links"), Name("NETLink")] },
+
+        Rule { entry: DocumentationDirectory, os: MacOS, versions: ALL, path: &[Name("Documentation")] },
+        Rule { entry: DocumentationDirectory, os: Windows, versions: ALL, path: &[Name("Documentation")] },
+        Rule { entry: DocumentationDirectory, os: Linux, versions: ALL, path: &[Name("Documentation")] },
+
+        Rule { entry: PacletsDirectory, os: MacOS, versions: ALL, path: &[Name("SystemFiles"), Name("Paclets")] },
+        Rule { entry: PacletsDirectory, os: Windows, versions: ALL, path: &[Name("SystemFiles"), Name("Paclets")] },
+        Rule { entry: PacletsDirectory, os: Linux, versions: ALL, path: &[Name("SystemFiles"), Name("Paclets")] },
+
+        Rule { entry: LinksDirectory, os: MacOS, versions: ALL, path: &[Name("SystemFiles"), Name("Links")] },
+        Rule { entry: LinksDirectory, os: Windows, versions: ALL, path: &[Name("SystemFiles"), Name("Links")] },
+        Rule { entry: LinksDirectory, os: Linux, versions: ALL, path: &[Name("SystemFiles"), Name("Links")] },
+
+        Rule { entry: AddOnsApplicationsDirectory, os: MacOS, versions: ALL, path: &[Name("AddOns"), Name("Applications")] },
+        Rule { entry: AddOnsApplicationsDirectory, os: Windows, versions: ALL, path: &[Name("AddOns"), Name("Applications")] },
+        Rule { entry: AddOnsApplicationsDirectory, os: Linux, versions: ALL, path: &[Name("AddOns"), Name("Applications")] },
+    ]
+};
+
+//======================================
+// API
+//======================================
+
+impl WolframApp {
+    /// Location of `entry`, relative to the
+    /// [`installation_directory()`][WolframApp::installation_directory] of
+    /// this app.
+    ///
+    /// This does not check whether `entry` exists. Use
+    /// [`WolframApp::resolve()`] to get the absolute location of an entry that
+    /// is present.
+    pub fn relative_path(&self, entry: LayoutEntry) -> Result<PathBuf, Error> {
+        if let Some(ref player) = self.embedded_player {
+            return player.relative_path(entry);
+        }
+
+        let os = OperatingSystem::target_os();
+
+        // Apps whose Wolfram version is not known, like prototype builds with
+        // an application version of 0.0.0, are assumed to use the current
+        // layout.
+        let version = match self.app_wolfram_version() {
+            Ok(version) => (version.major(), version.minor()),
+            Err(_) => (u32::MAX, u32::MAX),
+        };
+
+        let rule = find_rule(entry, &os, version).ok_or_else(|| match os {
+            OperatingSystem::Other => {
+                Error::platform_unsupported(&format!("locate {}", entry.description()))
+            },
+            _ => Error::other(format!(
+                "no known location for {} in Wolfram apps with version {} on {}",
+                entry.description(),
+                self.app_version,
+                os.name()
+            )),
+        })?;
+
+        rule.relative_path(|| self.target_system_id())
+    }
+
+    /// Absolute location of `entry` within this app.
+    ///
+    /// Returns an error if `entry` does not exist at the expected location.
+    pub fn resolve(&self, entry: LayoutEntry) -> Result<PathBuf, Error> {
+        let path = self
+            .installation_directory()
+            .join(self.relative_path(entry)?);
+
+        let exists = match entry.is_directory() {
+            true => path.is_dir(),
+            false => path.is_file(),
+        };
+
+        if !exists {
+            return Err(Error::unexpected_app_layout(
+                entry.description(),
+                self,
+                path,
+            ));
+        }
+
+        Ok(path)
+    }
+}
+
+//======================================
+// Helpers
+//======================================
+
+impl LayoutEntry {
+    /// Human-readable description of this entry, used in error messages.
+    fn description(&self) -> &'static str {
+        match self {
+            LayoutEntry::KernelExecutable => "WolframKernel executable",
+            LayoutEntry::WolframScriptExecutable => "wolframscript executable",
+            LayoutEntry::FrontEndExecutablesDirectory => {
+                "front end executables directory"
+            },
+            LayoutEntry::KernelBinariesDirectory => "kernel binaries directory",
+            LayoutEntry::WstpDeveloperKitDirectory => "WSTP DeveloperKit directory",
+            LayoutEntry::LibraryLinkIncludesDirectory => {
+                "LibraryLink C header includes directory"
+            },
+            LayoutEntry::LibrariesDirectory => "libraries directory",
+            LayoutEntry::MathLinkCompilerAdditionsDirectory => {
+                "MathLink CompilerAdditions directory"
+            },
+            LayoutEntry::NetLinkDirectory => ".NET/Link directory",
+            LayoutEntry::DocumentationDirectory => "documentation directory",
+            LayoutEntry::PacletsDirectory => "paclets directory",
+            LayoutEntry::LinksDirectory => "links directory",
+            LayoutEntry::AddOnsApplicationsDirectory => "AddOns/Applications directory",
+        }
+    }
+
+    fn is_directory(&self) -> bool {
+        match self {
+            LayoutEntry::KernelExecutable | LayoutEntry::WolframScriptExecutable => false,
+            LayoutEntry::FrontEndExecutablesDirectory
+            | LayoutEntry::KernelBinariesDirectory
+            | LayoutEntry::WstpDeveloperKitDirectory
+            | LayoutEntry::LibraryLinkIncludesDirectory
+            | LayoutEntry::LibrariesDirectory
+            | LayoutEntry::MathLinkCompilerAdditionsDirectory
+            | LayoutEntry::NetLinkDirectory
+            | LayoutEntry::DocumentationDirectory
+            | LayoutEntry::PacletsDirectory
+            | LayoutEntry::LinksDirectory
+            | LayoutEntry::AddOnsApplicationsDirectory => true,
+        }
+    }
+}
+
+/// Find the first rule in [`LAYOUT`] giving the location of `entry` on `os`
+/// in apps with the specified `(major, minor)` version.
+fn find_rule(
+    entry: LayoutEntry,
+    os: &OperatingSystem,
+    version: (u32, u32),
+) -> Option<&'static Rule> {
+    LAYOUT.iter().find(|rule| {
+        rule.entry == entry && rule.os == *os && rule.versions.contains(version)
+    })
+}
+
+impl Rule {
+    fn relative_path(
+        &self,
+        system_id: impl Fn() -> Result<SystemID, Error>,
+    ) -> Result<PathBuf, Error> {
+        let mut path = PathBuf::new();
+
+        for component in self.path {
+            match component {
+                Component::Name(name) => path.push(name),
+                Component::SystemId => path.push(system_id()?.as_str()),
+            }
+        }
+
+        Ok(path)
+    }
+}
+
+impl Versions {
+    fn contains(&self, version: (u32, u32)) -> bool {
+        let Versions { since, until } = *self;
+
+        let before_until = match until {
+            Some(until) => version < until,
+            None => true,
+        };
+
+        since <= version && before_until
+    }
+}
+
+//======================================
+// Tests
+//======================================
+
+#[test]
+fn test_historical_layouts() {
+    use self::LayoutEntry::*;
+    use crate::os::OperatingSystem::{Linux, MacOS, Windows};
+
+    #[rustfmt::skip]
+    let cases: &[(LayoutEntry, OperatingSystem, (u32, u32), &str)] = &[
+        (KernelExecutable, MacOS, (9, 0), "MacOSX/MathKernel"),
+        (KernelExecutable, MacOS, (10, 0), "MacOSX/WolframKernel"),
+        (KernelExecutable, MacOS, (11, 3), "MacOSX/WolframKernel"),
+        (KernelExecutable, MacOS, (12, 0), "MacOS/WolframKernel"),
+        (KernelExecutable, MacOS, (14, 1), "MacOS/WolframKernel"),
+        (KernelExecutable, Windows, (9, 0), "MathKernel.exe"),
+        (KernelExecutable, Windows, (13, 1), "WolframKernel.exe"),
+        (KernelExecutable, Linux, (9, 0), "Executables/MathKernel"),
+        (KernelExecutable, Linux, (13, 1), "Executables/WolframKernel"),
+        (WolframScriptExecutable, MacOS, (11, 3), "MacOSX/wolframscript"),
+        (WolframScriptExecutable, MacOS, (13, 1), "MacOS/wolframscript"),
+        (WolframScriptExecutable, Linux, (13, 1), "SystemFiles/Kernel/Binaries/Linux-x86-64/wolframscript"),
+        (FrontEndExecutablesDirectory, MacOS, (11, 3), "MacOS"),
+        (FrontEndExecutablesDirectory, Windows, (13, 1), ""),
+        (LibrariesDirectory, Windows, (13, 1), "SystemFiles/Libraries/Windows-x86-64"),
+        (MathLinkCompilerAdditionsDirectory, Linux, (11, 3), "SystemFiles/Links/MathLink/DeveloperKit/Linux-x86-64/CompilerAdditions"),
+    ];
+
+    for (entry, os, version, expected) in cases {
+        let rule = find_rule(*entry, os, *version).unwrap();
+
+        // Resolve target-specific components for a target on the same
+        // platform as the app.
+        let system_id = match os {
+            MacOS => SystemID::MacOSX_x86_64,
+            Windows => SystemID::Windows_x86_64,
+            _ => SystemID::Linux_x86_64,
+        };
+
+        assert_eq!(
+            rule.relative_path(|| Ok(system_id)).unwrap(),
+            PathBuf::from(expected),
+            "{entry:?} on {os:?} in {version:?}"
+        );
+    }
+
+    // Every entry has a location on every supported platform, in every version.
+    let entries = [
+        KernelExecutable,
+        WolframScriptExecutable,
+        FrontEndExecutablesDirectory,
+        KernelBinariesDirectory,
+        WstpDeveloperKitDirectory,
+        LibraryLinkIncludesDirectory,
+        LibrariesDirectory,
+        MathLinkCompilerAdditionsDirectory,
+        NetLinkDirectory,
+        DocumentationDirectory,
+        PacletsDirectory,
+        LinksDirectory,
+        AddOnsApplicationsDirectory,
+    ];
+
+    for entry in entries {
+        for os in [MacOS, Windows, Linux] {
+            for major in 0..=20 {
+                for minor in 0..=3 {
+                    assert!(
+                        find_rule(entry, &os, (major, minor)).is_some(),
+                        "{entry:?} on {os:?} in {major}.{minor}"
+                    );
+                }
+            }
+        }
+    }
+}
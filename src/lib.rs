@@ -46,39 +46,112 @@
 //!     .next()
 //!     .unwrap();
 //! ```
+//!
+//! # Crate features
+//!
+//! Each platform-specific discovery provider used by [`discover()`] can be
+//! disabled at compile time. All providers are enabled by default.
+//!
+//! * `discovery-macos-launchservices` — find apps registered with macOS Launch
+//!   Services.
+//! * `discovery-windows-appx` — find apps installed as Windows Appx packages.
+//!   Apps registered in the Windows registry are always found.
+//! * `discovery-linux-scan` — find apps installed into the default Linux
+//!   installation locations, like `/usr/local/Wolfram/`.
+//!
+//! Disabling a provider does not affect [`WolframApp::from_app_directory()`] or
+//! the `WOLFRAM_APP_DIRECTORY` environment variable.
+//!
+//! Crates that depend on `wolfram-app-discovery` with `default-features = false`
+//! should enable the providers they need explicitly:
+//!
+//! ```toml
+//! [dependencies]
+//! wolfram-app-discovery = { version = "0.5", default-features = false, features = [
+//!     "discovery-macos-launchservices",
+//!     "discovery-windows-appx",
+//!     "discovery-linux-scan",
+//! ] }
+//! ```
+//!
+//! The following optional features are disabled by default:
+//!
+//! * `evaluate` — enables `WolframApp::evaluate()`, which evaluates Wolfram
+//!   Language code using the `wolframscript` executable of an app.
+//! * `kernel-properties` — enables `WolframApp::kernel_properties()`, which
+//!   launches the kernel of an app to query values like `$Version` and
+//!   `$LicenseID`.
+//! * `async` — enables async variants of potentially slow operations, like
+//!   `WolframApp::try_default_async()`. The blocking work is run on a
+//!   background thread, so the returned futures can be awaited from any async
+//!   runtime without blocking its threads.
 
 #![warn(missing_docs)]
+// Only `config::print_cargo_directive()` may write to stdout.
+#![deny(clippy::print_stdout)]
 
-
+pub mod artifacts;
 pub mod build_scripts;
+pub mod cache;
 pub mod config;
-
+pub mod directories;
+pub mod discovery;
+pub mod kernel;
+pub mod layout;
+pub mod license;
+pub mod paclet;
+pub mod pkg_config;
+pub mod project_config;
+pub mod property;
+pub mod user_config;
+pub mod verify;
+
+#[cfg(feature = "async")]
+mod blocking;
 mod os;
+mod toml;
+mod wl;
 
 #[cfg(test)]
 mod tests;
 
+#[cfg(test)]
+mod test_support;
+
 #[doc(hidden)]
 mod test_readme {
     // Ensure that doc tests in the README.md file get run.
     #![doc = include_str!("../README.md")]
 }
 
-
 use std::{
     cmp::Ordering,
+    collections::HashSet,
     fmt::{self, Display},
-    path::PathBuf,
+    hash::{Hash, Hasher},
+    io::Read,
+    path::{Path, PathBuf},
     process,
     str::FromStr,
+    sync::{Arc, Mutex, OnceLock},
+    time::{Duration, Instant},
 };
 
 use log::info;
 
 #[allow(deprecated)]
-use config::env_vars::{RUST_WOLFRAM_LOCATION, WOLFRAM_APP_DIRECTORY};
+use config::env_vars::{
+    RUST_WOLFRAM_LOCATION, WOLFRAM_APP_DIRECTORY,
+    WOLFRAM_APP_DISCOVERY_DISABLE_WOLFRAMSCRIPT, WOLFRAM_APP_MIN_VERSION,
+    WOLFRAM_TARGET_SYSTEM_ID,
+};
+
+pub use crate::os::OperatingSystem;
 
-use crate::os::OperatingSystem;
+use crate::{
+    discovery::WolframTool, layout::LayoutEntry, project_config::ProjectConfig,
+    user_config::UserConfig,
+};
 
 //======================================
 // Types
@@ -87,13 +160,19 @@ use crate::os::OperatingSystem;
 /// A local installation of the Wolfram System.
 ///
 /// See the [wolfram-app-discovery](crate) crate documentation for usage examples.
+///
+/// # Equality
+///
+/// Two `WolframApp` values are equal if their
+/// [application directories][WolframApp::app_directory] refer to the same
+/// location on the file system, after symbolic links and relative path
+/// components have been resolved.
 #[rustfmt::skip]
 #[derive(Debug, Clone)]
 pub struct WolframApp {
     //-----------------------
     // Application properties
     //-----------------------
-    #[allow(dead_code)]
     app_name: String,
     app_type: WolframAppType,
     app_version: AppVersion,
@@ -105,6 +184,32 @@ pub struct WolframApp {
     // If this is a Wolfram Engine application, then it contains an embedded Wolfram
     // Player application that actually contains the WL system content.
     embedded_player: Option<Box<WolframApp>>,
+
+    /// Capability bits read from the Windows registry `Caps` value.
+    windows_caps: Option<WindowsCaps>,
+
+    /// Platform identity of this installation, if known.
+    installation_id: Option<InstallationId>,
+
+    //-----------------------
+    // Discovery settings
+    //-----------------------
+    /// Explicit [`SystemID`] override from [`DiscoveryOptions::target_system_id`].
+    target_system_id: Option<SystemID>,
+    /// Explicit front end preference from [`DiscoveryOptions::front_end_preference`].
+    front_end_preference: Option<Vec<FrontEndRole>>,
+    /// Subprocess timeout from [`DiscoveryOptions::timeout`].
+    timeout: Option<Duration>,
+    /// Whether spawning subprocesses is disabled by [`DiscoveryOptions::offline`].
+    offline: bool,
+
+    //-----------------------
+    // Provenance
+    //-----------------------
+    /// The `wolframscript` evaluation used to locate this app, if any.
+    wolframscript_invocation: Option<WolframScriptInvocation>,
+    /// The operating system registration this app was discovered from, if any.
+    registration: Option<Registration>,
 }
 
 /// Standalone application type distributed by Wolfram Research.
@@ -145,6 +250,8 @@ pub enum SystemID {
     MacOSX_ARM64,
     /// `"Windows-x86-64"`
     Windows_x86_64,
+    /// `"Windows-ARM64"`
+    Windows_ARM64,
     /// `"Linux-x86-64"`
     Linux_x86_64,
     /// `"Linux-ARM64"`
@@ -162,24 +269,32 @@ pub enum SystemID {
     ///
     /// Legacy Windows 32-bit x86
     Windows,
+    /// `"Windows-ARM"`
+    ///
+    /// Windows 32-bit ARM
+    Windows_ARM,
     /// `"Linux"`
     ///
     /// Legacy Linux 32-bit x86
     Linux,
+    // NOTE: When adding a new variant here, be sure to update SystemID::all().
 }
 
 /// Wolfram application version number.
 ///
 /// The major, minor, and revision components of most Wolfram applications will
 /// be the same as version of the Wolfram Language they provide.
-#[derive(Debug, Clone)]
+///
+/// Versions are ordered by their components, from most to least significant.
+/// A missing minor revision or build code is ordered before any known value.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct AppVersion {
     major: u32,
     minor: u32,
     revision: u32,
     minor_revision: Option<u32>,
 
-    build_code: Option<u32>,
+    build_code: Option<u64>,
 }
 
 /// Wolfram Language version number.
@@ -199,20 +314,415 @@ pub struct WstpSdk {
     sdk_dir: PathBuf,
     compiler_additions: PathBuf,
 
+    /// WSTP library interface version, e.g. `4`.
+    interface: u32,
+    /// WSTP library revision, if it could be determined.
+    revision: Option<u32>,
+
     wstp_h: PathBuf,
     wstp_static_library: PathBuf,
 }
 
+/// The Wolfram *LibraryLink* developer resources of an app, for a particular
+/// [`SystemID`].
+///
+/// See [`WolframApp::library_link_sdk()`] and
+/// [`WolframApp::target_library_link_sdk()`].
+#[derive(Debug, Clone)]
+pub struct LibraryLinkSdk {
+    system_id: SystemID,
+    /// E.g. `$InstallationDirectory/SystemFiles/IncludeFiles/C/`
+    c_includes_directory: PathBuf,
+    /// E.g. `$InstallationDirectory/SystemFiles/Libraries/MacOSX-x86-64/`
+    libraries_directory: PathBuf,
+
+    /// Value of `WolframLibraryVersion` in `WolframLibrary.h`, if present.
+    library_version: Option<u32>,
+}
+
+/// Record of a `wolframscript` evaluation performed during discovery.
+///
+/// See [`WolframApp::wolframscript_invocation()`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct WolframScriptInvocation {
+    command_line: Vec<String>,
+    duration: Duration,
+    output: String,
+}
+
+/// A front end launcher executable provided by a Wolfram app.
+///
+/// Use [`WolframApp::front_end_executables()`] to get all front end launchers
+/// provided by an app.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FrontEndExecutable {
+    role: FrontEndRole,
+    path: PathBuf,
+}
+
+/// Branding of a front end launcher executable.
+///
+/// Newer Wolfram apps may contain both a `Mathematica` and a `Wolfram` front
+/// end launcher, which start the same notebook front end with different
+/// product branding.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum FrontEndRole {
+    /// The `Wolfram` launcher.
+    Wolfram,
+    /// The `Mathematica` launcher.
+    Mathematica,
+}
+
+/// A launcher script in the `Executables/` directory of a Linux Wolfram app.
+///
+/// The set of launcher scripts provided differs between app types and
+/// versions. For example, Wolfram Engine does not provide the `mathematica`
+/// launcher. Use [`WolframApp::linux_launchers()`] to get the launchers
+/// present in an app.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum LinuxLauncher {
+    /// The `mathematica` notebook front end launcher.
+    Mathematica,
+    /// The `wolfram` kernel launcher.
+    Wolfram,
+    /// The `math` kernel launcher.
+    Math,
+    /// The `WolframKernel` kernel launcher.
+    WolframKernel,
+}
+
+/// Strategy used to rank the apps returned by [`discover()`] and related
+/// functions.
+///
+/// # Examples
+///
+/// Rank apps that provide a notebook front end before apps that do not:
+///
+/// ```no_run
+/// use wolfram_app_discovery::{discover_with_options, DiscoveryOptions, SortStrategy};
+///
+/// let mut options = DiscoveryOptions::default();
+/// options.sort_strategy = SortStrategy::PreferFrontEnd;
+///
+/// let apps = discover_with_options(&options);
+/// ```
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub enum SortStrategy {
+    /// Rank apps by Wolfram Language version, and then by application feature
+    /// set. The newest and most general app is ranked first.
+    ///
+    /// This is the default strategy.
+    #[default]
+    PreferNewest,
+    /// Rank apps that provide a notebook front end first, and then rank as
+    /// [`SortStrategy::PreferNewest`].
+    PreferFrontEnd,
+    /// Rank [Wolfram Engine][WolframAppType::Engine] apps first, and then rank
+    /// as [`SortStrategy::PreferNewest`].
+    PreferEngine,
+    /// Rank released apps before [prototype and nightly
+    /// builds][WolframApp::is_prerelease], and then rank as
+    /// [`SortStrategy::PreferNewest`].
+    PreferReleased,
+    /// Rank apps using a custom comparison function.
+    ///
+    /// Apps are sorted in ascending order according to this function, so an
+    /// app `a` is ranked before `b` if `f(a, b)` returns [`Ordering::Less`].
+    Custom(fn(&WolframApp, &WolframApp) -> Ordering),
+}
+
+/// Options that control how Wolfram applications are discovered, and how
+/// target-specific resources within a discovered app are resolved.
+///
+/// # Examples
+///
+/// Locate the WSTP SDK for a specific [`SystemID`], regardless of the Rust
+/// target `wolfram-app-discovery` was compiled for:
+///
+/// ```no_run
+/// use wolfram_app_discovery::{DiscoveryOptions, SystemID, WolframApp};
+///
+/// let mut options = DiscoveryOptions::default();
+/// options.target_system_id = Some(SystemID::Linux_ARM64);
+///
+/// let app = WolframApp::try_default_with_options(&options).unwrap();
+///
+/// assert_eq!(app.target_wstp_sdk().unwrap().system_id(), SystemID::Linux_ARM64);
+/// ```
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct DiscoveryOptions {
+    /// [`SystemID`] to use when resolving target-specific resources, like the
+    /// SDK returned by [`WolframApp::target_wstp_sdk()`].
+    ///
+    /// If `None`, the
+    /// [`WOLFRAM_TARGET_SYSTEM_ID`][crate::config::env_vars::WOLFRAM_TARGET_SYSTEM_ID]
+    /// environment variable is checked, falling back to
+    /// [`SystemID::current_rust_target()`].
+    pub target_system_id: Option<SystemID>,
+
+    /// If `true`, independent discovery backends (e.g. separate installation
+    /// root directories or registry hives) are searched concurrently.
+    ///
+    /// This can reduce the time taken by a cold discovery on computers with
+    /// many installed apps. Defaults to `false`.
+    pub parallel: bool,
+
+    /// Maximum time to wait for any subprocess spawned during discovery (e.g.
+    /// `wolframscript`) to exit.
+    ///
+    /// A subprocess that does not exit within this duration is killed, and
+    /// the discovery step that spawned it fails. If `None`, subprocesses are
+    /// waited on indefinitely.
+    pub timeout: Option<Duration>,
+
+    /// If `true`, discovery will not spawn any subprocesses.
+    ///
+    /// This skips the `wolframscript` step of [`WolframApp::try_default()`],
+    /// and prevents [`WolframApp::wolfram_version()`] from launching the kernel
+    /// of the returned apps. Defaults to `false`.
+    pub offline: bool,
+
+    /// If `true`, the `wolframscript` step of [`WolframApp::try_default()`] is
+    /// skipped.
+    ///
+    /// Launching `wolframscript` can trigger license checks or network access,
+    /// and take several seconds. The `wolframscript` step is also skipped if
+    /// the [`WOLFRAM_APP_DISCOVERY_DISABLE_WOLFRAMSCRIPT`][crate::config::env_vars::WOLFRAM_APP_DISCOVERY_DISABLE_WOLFRAMSCRIPT]
+    /// environment variable is set to `1` or `true`. Defaults to `false`.
+    pub skip_wolframscript: bool,
+
+    /// Order in which front end launchers are preferred by
+    /// [`WolframApp::front_end_executable_path()`].
+    ///
+    /// If `None`, [`FrontEndRole::DEFAULT_PREFERENCE`] is used.
+    pub front_end_preference: Option<Vec<FrontEndRole>>,
+
+    /// Strategy used to rank the apps returned by [`discover_with_options()`]
+    /// and [`discover_with_filter_and_options()`].
+    pub sort_strategy: SortStrategy,
+
+    /// Maximum number of nested directory levels read below each root
+    /// directory that is scanned for installed apps.
+    ///
+    /// If `None`, [`DiscoveryOptions::DEFAULT_MAX_SCAN_DEPTH`] is used.
+    pub max_scan_depth: Option<usize>,
+
+    /// Maximum number of directory entries read while scanning each root
+    /// directory for installed apps.
+    ///
+    /// If `None`, [`DiscoveryOptions::DEFAULT_MAX_SCAN_ENTRIES`] is used.
+    pub max_scan_entries: Option<usize>,
+
+    /// If `true`, the apps found by operating system discovery are loaded from
+    /// the persistent [discovery cache][crate::cache], performing and caching
+    /// a fresh discovery if the cache does not exist yet.
+    ///
+    /// Defaults to `false`.
+    pub use_cache: bool,
+}
+
+/// Operating system registration that a [`WolframApp`] was discovered from.
+///
+/// See [`WolframApp::registration()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Registration {
+    /// A Windows registry key under `Software\Wolfram Research\Installations`.
+    WindowsRegistryKey {
+        /// Full path of the registry key, starting with the name of the root
+        /// key, e.g. `HKEY_LOCAL_MACHINE\Software\Wolfram Research\Installations\<build>`.
+        key: String,
+        /// Whether the key is in the 32-bit registry view on 64-bit Windows.
+        wow64_32: bool,
+    },
+    /// A macOS Launch Services entry for the application bundle at the
+    /// specified location.
+    LaunchServices(PathBuf),
+}
+
+/// Platform identity of a Wolfram app installation.
+///
+/// These identifiers can be used to correlate a [`WolframApp`] with the
+/// records of operating system package databases.
+///
+/// See [`WolframApp::installation_id()`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum InstallationId {
+    /// macOS application bundle identifier, e.g. `com.wolfram.Mathematica`.
+    MacOSBundleIdentifier(String),
+    /// `CLSID` value of the Windows registry key of the installation.
+    WindowsClsid(String),
+    /// Full name of the Windows MSIX (Appx) package of the installation.
+    WindowsPackageFullName(String),
+}
+
+/// Capability bits recorded in the Windows registry for a Wolfram app.
+///
+/// Wolfram installers write a `Caps` DWORD value into the registry key of each
+/// installation. The value is a bit field encoding the features the
+/// installation is entitled to. The meaning of the individual bits is not
+/// publicly documented, so this type exposes the raw bits, via
+/// [`WindowsCaps::bits()`] and [`WindowsCaps::set_bits()`], to allow deployment
+/// tooling to audit and compare them across installations.
+///
+/// See [`WolframApp::windows_caps()`].
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct WindowsCaps(u32);
+
+/// Suspected problems with the Wolfram app registrations on this computer.
+///
+/// See [`analyze_registrations()`].
+#[derive(Debug, Clone)]
+pub struct RegistrationReport {
+    duplicates: Vec<Vec<WolframApp>>,
+    orphaned: Vec<WolframApp>,
+}
+
+/// The default Wolfram app, and the other apps that were considered.
+///
+/// See [`WolframApp::try_default_detailed()`].
+#[derive(Debug, Clone)]
+pub struct DefaultAppSelection {
+    app: WolframApp,
+    runners_up: Vec<RunnerUp>,
+}
+
+/// A Wolfram app that was found, but not selected as the default app.
+///
+/// See [`DefaultAppSelection::runners_up()`].
+#[derive(Debug, Clone)]
+pub struct RunnerUp {
+    app: WolframApp,
+    reason: RunnerUpReason,
+}
+
+/// Reason a [`RunnerUp`] app was not selected as the default app.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RunnerUpReason {
+    /// The default app was specified using an environment variable or project
+    /// configuration file, or was located using `wolframscript`, which take
+    /// precedence over installed apps.
+    Overridden,
+    /// The app has an older Wolfram Language version than the default app.
+    OlderVersion,
+    /// The app does not provide a notebook front end, but the default app
+    /// does.
+    LacksFrontEnd,
+    /// The app was ranked lower than the default app by
+    /// [`DiscoveryOptions::sort_strategy`] for another reason, e.g. because
+    /// of its application feature set.
+    RankedLower,
+    /// The app does not satisfy the filter used to select the default app.
+    ///
+    /// The message describes which part of the filter was not satisfied.
+    Filtered(String),
+}
+
 #[doc(hidden)]
+#[derive(Clone)]
 pub struct Filter {
     pub app_types: Option<Vec<WolframAppType>>,
+    /// If set, only apps whose [`WolframVersion`] is at least this version
+    /// match.
+    pub min_version: Option<WolframVersion>,
+    /// If set, only apps that provide kernel binaries for this [`SystemID`]
+    /// match.
+    ///
+    /// See [`WolframApp::supported_system_ids()`].
+    pub kernel_system_id: Option<SystemID>,
+    /// If `true`, only apps that provide a notebook front end match.
+    ///
+    /// Apps are required to be of an application type that includes a front
+    /// end (e.g. not [Wolfram Engine][WolframAppType::Engine]), and to have a
+    /// front end executable present.
+    pub requires_front_end: bool,
+    /// If `true`, only apps that appear to be activated match.
+    ///
+    /// This is a quick check for a configured password or license server, and
+    /// does not launch a Wolfram kernel. This can be used to skip e.g. a
+    /// freshly installed Wolfram Engine that has not been activated yet.
+    pub requires_activation: bool,
+    /// If set, only apps that contain a WSTP SDK for this [`SystemID`] match.
+    ///
+    /// This can be used by build scripts that cross-compile to select an app
+    /// for which [`WolframApp::target_wstp_sdk()`] will succeed.
+    pub wstp_sdk_system_id: Option<SystemID>,
+}
+
+/// Non-fatal problem encountered during discovery.
+///
+/// Warnings are printed to stderr when they occur, and are also recorded so
+/// that they can be retrieved later using [`discovery_warnings()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DiscoveryWarning {
+    /// A discovery capability is not implemented on the current platform, and
+    /// so the corresponding information is missing or degraded.
+    UnimplementedOnPlatform {
+        /// Description of the operation that is not implemented.
+        operation: String,
+        /// Name of the current operating system.
+        os: &'static str,
+    },
+    /// A directory scan stopped early because it reached a limit set by
+    /// [`DiscoveryOptions`]. Apps inside the unscanned part of the directory
+    /// were not discovered.
+    ScanTruncated {
+        /// Root directory of the scan.
+        root: PathBuf,
+        /// The limit that was reached.
+        limit: ScanLimit,
+    },
+}
+
+/// A limit on the work done by a directory scan.
+///
+/// See [`DiscoveryWarning::ScanTruncated`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ScanLimit {
+    /// See [`DiscoveryOptions::max_scan_depth`].
+    Depth(usize),
+    /// See [`DiscoveryOptions::max_scan_entries`].
+    Entries(usize),
 }
 
 /// Wolfram app discovery error.
+///
+/// Use [`Error::code()`] to branch on the cause of an error, instead of
+/// parsing the error message.
 #[derive(Debug, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 pub struct Error(ErrorKind);
 
+/// Category of an [`Error`].
+///
+/// See [`Error::code()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorCode {
+    /// A Wolfram app or app component could not be located.
+    Undiscoverable,
+    /// A file or directory did not appear at the expected location within a
+    /// Wolfram app, component, or directory specified by an environment
+    /// variable.
+    UnexpectedLayout,
+    /// The app specified by an environment variable does not match the
+    /// required [`Filter`].
+    FilterMismatch,
+    /// The operation is not implemented on the current platform.
+    UnsupportedPlatform,
+    /// An IO error occurred.
+    Io,
+    /// Any other error.
+    Other,
+}
+
 #[derive(Debug, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 pub(crate) enum ErrorKind {
@@ -253,14 +763,27 @@ pub(crate) enum ErrorKind {
         environment_variable: &'static str,
         filter_err: FilterError,
     },
+    /// The app specified by a project configuration file does not match the
+    /// filter the app is expected to satisfy.
+    ConfiguredAppDoesNotMatchFilter {
+        config_file: PathBuf,
+        filter_err: FilterError,
+    },
     UnsupportedPlatform {
         operation: String,
         target_os: OperatingSystem,
     },
-    IO(String),
+    IO(IoError),
     Other(String),
 }
 
+/// An [`std::io::Error`] retained as the [source][std::error::Error::source]
+/// of an [`Error`].
+///
+/// `std::io::Error` does not implement `Clone`, so it is shared instead.
+#[derive(Debug, Clone)]
+pub(crate) struct IoError(Arc<std::io::Error>);
+
 #[derive(Debug, Clone)]
 #[cfg_attr(test, derive(PartialEq))]
 pub(crate) enum FilterError {
@@ -268,6 +791,24 @@ pub(crate) enum FilterError {
         app_type: WolframAppType,
         allowed: Vec<WolframAppType>,
     },
+    FilterDoesNotMatchMinVersion {
+        version: Option<WolframVersion>,
+        min_version: WolframVersion,
+    },
+    FilterDoesNotMatchKernelSystemID {
+        required: SystemID,
+        available: Vec<SystemID>,
+    },
+    FilterRequiresFrontEnd {
+        app_type: WolframAppType,
+    },
+    FilterRequiresActivation {
+        installation_directory: PathBuf,
+    },
+    FilterDoesNotMatchWstpSdkSystemID {
+        required: SystemID,
+        available: Vec<SystemID>,
+    },
 }
 
 impl Error {
@@ -370,14 +911,197 @@ impl Error {
         info!("discovery error: {err}");
         err
     }
+
+    pub(crate) fn configured_app_does_not_match_filter(
+        config_file: PathBuf,
+        filter_err: FilterError,
+    ) -> Self {
+        let err = Error(ErrorKind::ConfiguredAppDoesNotMatchFilter {
+            config_file,
+            filter_err,
+        });
+        info!("discovery error: {err}");
+        err
+    }
+}
+
+impl Error {
+    /// Get the category of this error.
+    pub fn code(&self) -> ErrorCode {
+        let Error(kind) = self;
+
+        match kind {
+            ErrorKind::Undiscoverable { .. } => ErrorCode::Undiscoverable,
+            ErrorKind::UnexpectedAppLayout { .. }
+            | ErrorKind::UnexpectedLayout { .. }
+            | ErrorKind::UnexpectedEnvironmentValueLayout { .. } => {
+                ErrorCode::UnexpectedLayout
+            },
+            ErrorKind::SpecifiedAppDoesNotMatchFilter { .. }
+            | ErrorKind::ConfiguredAppDoesNotMatchFilter { .. } => {
+                ErrorCode::FilterMismatch
+            },
+            ErrorKind::UnsupportedPlatform { .. } => ErrorCode::UnsupportedPlatform,
+            ErrorKind::IO(_) => ErrorCode::Io,
+            ErrorKind::Other(_) => ErrorCode::Other,
+        }
+    }
+
+    /// Name of the file, directory, or other resource this error concerns,
+    /// if any.
+    pub fn resource_name(&self) -> Option<&str> {
+        let Error(kind) = self;
+
+        match kind {
+            ErrorKind::Undiscoverable { resource, .. } => Some(resource),
+            ErrorKind::UnexpectedAppLayout { resource_name, .. }
+            | ErrorKind::UnexpectedLayout { resource_name, .. }
+            | ErrorKind::UnexpectedEnvironmentValueLayout { resource_name, .. } => {
+                Some(resource_name)
+            },
+            ErrorKind::SpecifiedAppDoesNotMatchFilter { .. }
+            | ErrorKind::ConfiguredAppDoesNotMatchFilter { .. }
+            | ErrorKind::UnsupportedPlatform { .. }
+            | ErrorKind::IO(_)
+            | ErrorKind::Other(_) => None,
+        }
+    }
+
+    /// Location that was expected to exist, but does not, if any.
+    pub fn path(&self) -> Option<&Path> {
+        let Error(kind) = self;
+
+        match kind {
+            ErrorKind::UnexpectedAppLayout { path, .. }
+            | ErrorKind::UnexpectedLayout { path, .. } => Some(path),
+            ErrorKind::UnexpectedEnvironmentValueLayout { derived_path, .. } => {
+                Some(derived_path)
+            },
+            ErrorKind::Undiscoverable { .. }
+            | ErrorKind::SpecifiedAppDoesNotMatchFilter { .. }
+            | ErrorKind::ConfiguredAppDoesNotMatchFilter { .. }
+            | ErrorKind::UnsupportedPlatform { .. }
+            | ErrorKind::IO(_)
+            | ErrorKind::Other(_) => None,
+        }
+    }
+
+    /// Environment variable that is involved in this error, if any.
+    ///
+    /// This is either the variable that configured the value that caused this
+    /// error, or a variable that could be set to avoid it.
+    pub fn environment_variable(&self) -> Option<&'static str> {
+        let Error(kind) = self;
+
+        match kind {
+            ErrorKind::Undiscoverable {
+                environment_variable,
+                ..
+            } => *environment_variable,
+            ErrorKind::UnexpectedEnvironmentValueLayout { env_var, .. } => Some(env_var),
+            ErrorKind::SpecifiedAppDoesNotMatchFilter {
+                environment_variable,
+                ..
+            } => Some(environment_variable),
+            ErrorKind::UnexpectedAppLayout { .. }
+            | ErrorKind::UnexpectedLayout { .. }
+            | ErrorKind::ConfiguredAppDoesNotMatchFilter { .. }
+            | ErrorKind::UnsupportedPlatform { .. }
+            | ErrorKind::IO(_)
+            | ErrorKind::Other(_) => None,
+        }
+    }
+}
+
+impl ErrorCode {
+    /// Stable identifier for this error category, suitable for use in
+    /// machine-readable output.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCode::Undiscoverable => "undiscoverable",
+            ErrorCode::UnexpectedLayout => "unexpected_layout",
+            ErrorCode::FilterMismatch => "filter_mismatch",
+            ErrorCode::UnsupportedPlatform => "unsupported_platform",
+            ErrorCode::Io => "io",
+            ErrorCode::Other => "other",
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    /// The underlying [`std::io::Error`], if this is an
+    /// [IO error][ErrorCode::Io].
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        let Error(kind) = self;
+
+        match kind {
+            ErrorKind::IO(IoError(err)) => Some(&**err),
+            ErrorKind::Undiscoverable { .. }
+            | ErrorKind::UnexpectedAppLayout { .. }
+            | ErrorKind::UnexpectedLayout { .. }
+            | ErrorKind::UnexpectedEnvironmentValueLayout { .. }
+            | ErrorKind::SpecifiedAppDoesNotMatchFilter { .. }
+            | ErrorKind::ConfiguredAppDoesNotMatchFilter { .. }
+            | ErrorKind::UnsupportedPlatform { .. }
+            | ErrorKind::Other(_) => None,
+        }
+    }
 }
 
-impl std::error::Error for Error {}
+#[cfg(test)]
+impl PartialEq for IoError {
+    fn eq(&self, other: &Self) -> bool {
+        let (IoError(a), IoError(b)) = (self, other);
+
+        a.kind() == b.kind() && a.to_string() == b.to_string()
+    }
+}
 
 //======================================
 // Functions
 //======================================
 
+/// Minimum version specified by the [`WOLFRAM_APP_MIN_VERSION`] environment
+/// variable, if set.
+fn env_min_version() -> Result<Option<WolframVersion>, Error> {
+    let Some(value) = config::get_env_var(WOLFRAM_APP_MIN_VERSION) else {
+        return Ok(None);
+    };
+
+    match WolframVersion::parse(&value) {
+        Some(version) => Ok(Some(version)),
+        None => Err(Error::other(format!(
+            "value of environment variable '{WOLFRAM_APP_MIN_VERSION}' is not a \
+            valid Wolfram Language version: {value:?}"
+        ))),
+    }
+}
+
+/// Directories listed in the [`WOLFRAM_APP_DIRECTORY`] environment variable.
+///
+/// The value can contain several directories, separated in the same way as
+/// the `PATH` environment variable (`:` on Unix and `;` on Windows).
+fn specified_app_directories() -> Vec<PathBuf> {
+    match config::get_env_var(WOLFRAM_APP_DIRECTORY) {
+        Some(value) => std::env::split_paths(&value)
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Get the [`DiscoveryWarning`]s that have occurred in this process.
+///
+/// Each distinct warning is returned once, in the order it first occurred.
+pub fn discovery_warnings() -> Vec<DiscoveryWarning> {
+    DISCOVERY_WARNINGS
+        .lock()
+        .unwrap_or_else(|err| err.into_inner())
+        .clone()
+}
+
+static DISCOVERY_WARNINGS: Mutex<Vec<DiscoveryWarning>> = Mutex::new(Vec::new());
+
 /// Discover all installed Wolfram applications.
 ///
 /// The [`WolframApp`] elements in the returned vector will be sorted by Wolfram
@@ -389,18 +1113,114 @@ impl std::error::Error for Error {}
 /// This function will use operating-system specific logic to discover installations of
 /// Wolfram applications. If a Wolfram application is installed to a non-standard
 /// location, it may not be discoverable by this function.
+///
+/// The apps listed in the [`WOLFRAM_APP_DIRECTORY`] environment variable are also
+/// included.
 pub fn discover() -> Vec<WolframApp> {
-    let mut apps = os::discover_all();
+    discover_with_options(&DiscoveryOptions::default())
+}
+
+/// Discover all installed Wolfram applications, using the specified
+/// [`DiscoveryOptions`].
+///
+/// The returned apps are ranked according to
+/// [`DiscoveryOptions::sort_strategy`]. With the default strategy, they are
+/// sorted in the same order as [`discover()`].
+pub fn discover_with_options(options: &DiscoveryOptions) -> Vec<WolframApp> {
+    discover_impl(options, None)
+}
+
+/// Discover installed Wolfram applications, skipping apps that aren't one of
+/// `app_types` where the discovery backend supports it.
+fn discover_impl(
+    options: &DiscoveryOptions,
+    app_types: Option<&[WolframAppType]>,
+) -> Vec<WolframApp> {
+    let mut apps = if options.use_cache {
+        let mut apps = cache::load_or_refresh(options);
+        apps.retain(|app| os::is_allowed(app_types, &app.app_type()));
+        apps
+    } else {
+        os::discover_all(options, app_types)
+    };
 
-    // Sort `apps` so that the "best" app is the last element in the vector.
-    apps.sort_by(WolframApp::best_order);
+    if let Some(user_config) = UserConfig::load_or_warn() {
+        apps.extend(user_config.discover_in_search_roots(options, app_types));
+    }
+
+    for dir in specified_app_directories() {
+        match WolframApp::from_app_directory(dir) {
+            Ok(app) if !os::is_allowed(app_types, &app.app_type()) => (),
+            Ok(app) => apps.push(app),
+            Err(err) => warning(&format!(
+                "ignoring directory specified by environment variable \
+                '{WOLFRAM_APP_DIRECTORY}': {err}"
+            )),
+        }
+    }
+
+    match env_min_version() {
+        Ok(Some(min_version)) => {
+            let filter = Filter {
+                min_version: Some(min_version),
+                ..Filter::allow_all()
+            };
+
+            apps.retain(|app| filter.check_app(app).is_ok());
+        },
+        Ok(None) => (),
+        Err(err) => warning(&format!("ignoring minimum version: {err}")),
+    }
+
+    for app in &mut apps {
+        app.apply_options(options);
+    }
+
+    // Sort `apps` so that the "best" app is the first element in the vector.
+    apps.sort_by(|a, b| options.sort_strategy.compare(a, b));
 
-    // Reverse `apps`, so that the best come first.
-    apps.reverse();
+    // The same app can be found by more than one discovery backend. Because
+    // `apps` is sorted, this keeps the first (i.e. best) of any duplicates.
+    dedup_apps(&mut apps);
 
     apps
 }
 
+/// Analyze the app registrations found by operating system discovery for
+/// suspected duplicates and orphaned entries.
+///
+/// Unlike [`discover()`], which silently removes duplicates, this reports:
+///
+/// * **Duplicates:** groups of registrations whose application directories
+///   refer to the same location (e.g. the same app registered under several
+///   registry keys or bundle URLs).
+/// * **Orphaned registrations:** registrations whose application directory no
+///   longer exists, e.g. left behind by an incomplete uninstall.
+///
+/// This can be used to help clean up computers that have accumulated stale
+/// registrations after many Wolfram app upgrades.
+pub fn analyze_registrations(options: &DiscoveryOptions) -> RegistrationReport {
+    // Discovery skips registrations whose application directory is missing, so
+    // read every registration, including orphaned ones.
+    let mut apps = os::discover_registrations(options);
+
+    for app in &mut apps {
+        app.apply_options(options);
+    }
+
+    RegistrationReport::from_registrations(apps)
+}
+
+/// Remove all but the first occurrence of each app from `apps`, preserving the
+/// order of the remaining apps.
+///
+/// Apps are compared using the [`PartialEq`] impl for [`WolframApp`].
+pub(crate) fn dedup_apps(apps: &mut Vec<WolframApp>) {
+    let mut seen: HashSet<PathBuf> = HashSet::new();
+
+    apps.retain(|app| seen.insert(app.canonical_app_directory()));
+}
+
 /// Discover all installed Wolfram applications that match the specified filtering
 /// parameters.
 ///
@@ -410,7 +1230,22 @@ pub fn discover() -> Vec<WolframApp> {
 /// Wolfram applications. If a Wolfram application is installed to a non-standard
 /// location, it may not be discoverable by this function.
 pub fn discover_with_filter(filter: &Filter) -> Vec<WolframApp> {
-    let mut apps = discover();
+    discover_with_filter_and_options(filter, &DiscoveryOptions::default())
+}
+
+/// Discover all installed Wolfram applications that match the specified filtering
+/// parameters, using the specified [`DiscoveryOptions`].
+///
+/// The returned apps are ranked according to
+/// [`DiscoveryOptions::sort_strategy`].
+pub fn discover_with_filter_and_options(
+    filter: &Filter,
+    options: &DiscoveryOptions,
+) -> Vec<WolframApp> {
+    // Let discovery backends skip apps of the wrong type before doing the
+    // work to fully load them. The backends may still return apps that don't
+    // match, so the filter is applied again afterwards.
+    let mut apps = discover_impl(options, filter.app_types.as_deref());
 
     apps.retain(|app| filter.check_app(&app).is_ok());
 
@@ -437,11 +1272,38 @@ pub fn system_id_from_target(rust_target: &str) -> Result<&'static str, Error> {
     SystemID::try_from_rust_target(rust_target).map(|id| id.as_str())
 }
 
-//======================================
-// Struct Impls
-//======================================
-
-impl WolframAppType {
+/// Resolve the [`SystemID`] that target-specific resources should be looked up
+/// for.
+///
+/// The following sources are checked in order:
+///
+/// 1. `explicit`, typically from [`DiscoveryOptions::target_system_id`].
+/// 2. The [`WOLFRAM_TARGET_SYSTEM_ID`] environment variable.
+/// 3. [`SystemID::try_current_rust_target()`].
+pub(crate) fn resolve_target_system_id(
+    explicit: Option<SystemID>,
+) -> Result<SystemID, Error> {
+    if let Some(system_id) = explicit {
+        return Ok(system_id);
+    }
+
+    if let Some(value) = config::get_env_var(WOLFRAM_TARGET_SYSTEM_ID) {
+        return SystemID::from_str(&value).map_err(|()| {
+            Error::other(format!(
+                "value of environment variable '{WOLFRAM_TARGET_SYSTEM_ID}' is not a \
+                recognized SystemID: {value:?}"
+            ))
+        });
+    }
+
+    SystemID::try_current_rust_target()
+}
+
+//======================================
+// Struct Impls
+//======================================
+
+impl WolframAppType {
     /// Enumerate all `WolframAppType` variants.
     pub fn variants() -> Vec<WolframAppType> {
         use WolframAppType::*;
@@ -494,6 +1356,22 @@ impl WolframAppType {
         }
     }
 
+    /// Whether this application type provides a notebook front end.
+    fn has_front_end(&self) -> bool {
+        use WolframAppType::*;
+
+        match self {
+            Desktop
+            | Mathematica
+            | FinancePlatform
+            | ProgrammingLab
+            | PlayerPro
+            | Player
+            | WolframAlphaNotebookEdition => true,
+            Engine => false,
+        }
+    }
+
     // TODO(cleanup): Make this method unnecessary. This is a synthesized thing,
     // not necessarily meaningful. Remove WolframApp.app_name?
     #[allow(dead_code)]
@@ -513,6 +1391,32 @@ impl WolframAppType {
     }
 }
 
+impl FromStr for WolframVersion {
+    type Err = Error;
+
+    /// Parse a version number like `13`, `13.3`, or `13.3.1`.
+    ///
+    /// Missing components are `0`, so `"14.0"` is parsed as `14.0.0`.
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        WolframVersion::parse(string).ok_or_else(|| {
+            Error::other(format!("invalid Wolfram Language version: {string:?}"))
+        })
+    }
+}
+
+impl FromStr for AppVersion {
+    type Err = Error;
+
+    /// Parse a version number with 3, 4, or 5 components, like `13.2.0`,
+    /// `13.2.0.5829781`, or `13.2.0.0.5829781`.
+    ///
+    /// A 4-component version number is interpreted as
+    /// `major.minor.revision.build_code`.
+    fn from_str(string: &str) -> Result<Self, Self::Err> {
+        AppVersion::parse(string)
+    }
+}
+
 impl FromStr for SystemID {
     type Err = ();
 
@@ -521,12 +1425,14 @@ impl FromStr for SystemID {
             "MacOSX-x86-64" => SystemID::MacOSX_x86_64,
             "MacOSX-ARM64" => SystemID::MacOSX_ARM64,
             "Windows-x86-64" => SystemID::Windows_x86_64,
+            "Windows-ARM64" => SystemID::Windows_ARM64,
             "Linux-x86-64" => SystemID::Linux_x86_64,
             "Linux-ARM64" => SystemID::Linux_ARM64,
             "Linux-ARM" => SystemID::Linux_ARM,
             "iOS-ARM64" => SystemID::iOS_ARM64,
             "Android" => SystemID::Android,
             "Windows" => SystemID::Windows,
+            "Windows-ARM" => SystemID::Windows_ARM,
             "Linux" => SystemID::Linux,
             _ => return Err(()),
         };
@@ -536,6 +1442,38 @@ impl FromStr for SystemID {
 }
 
 impl SystemID {
+    /// Get a list of all [`SystemID`] variants.
+    ///
+    /// ```
+    /// use wolfram_app_discovery::{OperatingSystem, SystemID};
+    ///
+    /// let windows: Vec<SystemID> = SystemID::all()
+    ///     .iter()
+    ///     .copied()
+    ///     .filter(|id| id.operating_system() == OperatingSystem::Windows)
+    ///     .collect();
+    ///
+    /// assert!(windows.contains(&SystemID::Windows_x86_64));
+    /// ```
+    pub const fn all() -> &'static [SystemID] {
+        use SystemID::*;
+
+        &[
+            MacOSX_x86_64,
+            MacOSX_ARM64,
+            Windows_x86_64,
+            Windows_ARM64,
+            Linux_x86_64,
+            Linux_ARM64,
+            Linux_ARM,
+            iOS_ARM64,
+            Android,
+            Windows,
+            Windows_ARM,
+            Linux,
+        ]
+    }
+
     /// [`$SystemID`][$SystemID] string value of this [`SystemID`].
     ///
     /// [$SystemID]: https://reference.wolfram.com/language/ref/$SystemID
@@ -544,12 +1482,14 @@ impl SystemID {
             SystemID::MacOSX_x86_64 => "MacOSX-x86-64",
             SystemID::MacOSX_ARM64 => "MacOSX-ARM64",
             SystemID::Windows_x86_64 => "Windows-x86-64",
+            SystemID::Windows_ARM64 => "Windows-ARM64",
             SystemID::Linux_x86_64 => "Linux-x86-64",
             SystemID::Linux_ARM64 => "Linux-ARM64",
             SystemID::Linux_ARM => "Linux-ARM",
             SystemID::iOS_ARM64 => "iOS-ARM64",
             SystemID::Android => "Android",
             SystemID::Windows => "Windows",
+            SystemID::Windows_ARM => "Windows-ARM",
             SystemID::Linux => "Linux",
         }
     }
@@ -635,8 +1575,11 @@ impl SystemID {
             "aarch64-apple-ios" |
             "aarch64-apple-ios-sim" => SystemID::iOS_ARM64,
             "aarch64-linux-android" => SystemID::Android,
+            "aarch64-pc-windows-msvc" |
+            "aarch64-pc-windows-gnullvm" => SystemID::Windows_ARM64,
             // 32-bit ARM (e.g. Raspberry Pi)
             "armv7-unknown-linux-gnueabihf" => SystemID::Linux_ARM,
+            "thumbv7a-pc-windows-msvc" => SystemID::Windows_ARM,
 
             _ => {
                 return Err(Error::other(format!(
@@ -649,10 +1592,42 @@ impl SystemID {
         Ok(id)
     }
 
-    pub(crate) fn operating_system(&self) -> OperatingSystem {
+    /// The processor architecture of this [`SystemID`], using the
+    /// [`target_arch`][target_arch] name Rust uses for it.
+    ///
+    /// ```
+    /// use wolfram_app_discovery::SystemID;
+    ///
+    /// assert_eq!(SystemID::MacOSX_ARM64.arch(), "aarch64");
+    /// assert_eq!(SystemID::Windows.arch(), "x86");
+    /// ```
+    ///
+    /// [target_arch]: https://doc.rust-lang.org/reference/conditional-compilation.html#target_arch
+    pub const fn arch(&self) -> &'static str {
+        match self {
+            SystemID::MacOSX_x86_64
+            | SystemID::Windows_x86_64
+            | SystemID::Linux_x86_64 => "x86_64",
+            SystemID::MacOSX_ARM64
+            | SystemID::Windows_ARM64
+            | SystemID::Linux_ARM64
+            | SystemID::iOS_ARM64
+            | SystemID::Android => "aarch64",
+            SystemID::Linux_ARM | SystemID::Windows_ARM => "arm",
+            SystemID::Windows | SystemID::Linux => "x86",
+        }
+    }
+
+    /// The operating system of this [`SystemID`].
+    ///
+    /// iOS and Android are [`OperatingSystem::Other`].
+    pub const fn operating_system(&self) -> OperatingSystem {
         match self {
             SystemID::MacOSX_x86_64 | SystemID::MacOSX_ARM64 => OperatingSystem::MacOS,
-            SystemID::Windows_x86_64 | SystemID::Windows => OperatingSystem::Windows,
+            SystemID::Windows_x86_64
+            | SystemID::Windows_ARM64
+            | SystemID::Windows
+            | SystemID::Windows_ARM => OperatingSystem::Windows,
             SystemID::Linux_x86_64
             | SystemID::Linux_ARM64
             | SystemID::Linux_ARM
@@ -684,6 +1659,70 @@ impl WolframVersion {
         }
     }
 
+    /// Construct a [`WolframVersion`] from the values of
+    /// [`$VersionNumber`][ref/$VersionNumber] and
+    /// [`$ReleaseNumber`][ref/$ReleaseNumber].
+    ///
+    /// ```
+    /// use wolfram_app_discovery::WolframVersion;
+    ///
+    /// assert_eq!(
+    ///     WolframVersion::from_version_number(13.3, 1),
+    ///     Some(WolframVersion::new(13, 3, 1))
+    /// );
+    /// assert_eq!(
+    ///     WolframVersion::from_version_number(14.0, 0),
+    ///     Some(WolframVersion::new(14, 0, 0))
+    /// );
+    /// ```
+    ///
+    /// Returns `None` if `version_number` is negative or not finite.
+    ///
+    /// [ref/$VersionNumber]: https://reference.wolfram.com/language/ref/$VersionNumber.html
+    /// [ref/$ReleaseNumber]: https://reference.wolfram.com/language/ref/$ReleaseNumber.html
+    pub fn from_version_number(version_number: f64, release_number: u32) -> Option<Self> {
+        // Note: The `Display` impl for `f64` formats the shortest representation
+        //       that round trips, so e.g. `13.3` formats as "13.3", and not as
+        //       "13.300000000000001".
+        WolframVersion::parse_version_number(&version_number.to_string(), release_number)
+    }
+
+    /// Parse a formatted `$VersionNumber` value like `13.3`, `13.`, or `13`.
+    pub(crate) fn parse_version_number(
+        version_number: &str,
+        release_number: u32,
+    ) -> Option<Self> {
+        let (major, minor) = match version_number.split_once('.') {
+            Some((major, minor)) => (major, minor),
+            None => (version_number, ""),
+        };
+
+        // This can be e.g. "13.", for major versions.
+        let minor = if minor.is_empty() { "0" } else { minor };
+
+        Some(WolframVersion::new(
+            u32::from_str(major).ok()?,
+            u32::from_str(minor).ok()?,
+            release_number,
+        ))
+    }
+
+    /// Parse a version number like `13`, `13.1`, or `13.1.0`.
+    pub(crate) fn parse(value: &str) -> Option<Self> {
+        let parts: Vec<u32> = value
+            .split('.')
+            .map(str::parse)
+            .collect::<Result<_, _>>()
+            .ok()?;
+
+        match parts[..] {
+            [major] => Some(WolframVersion::new(major, 0, 0)),
+            [major, minor] => Some(WolframVersion::new(major, minor, 0)),
+            [major, minor, patch] => Some(WolframVersion::new(major, minor, patch)),
+            _ => None,
+        }
+    }
+
     /// First component of [`$VersionNumber`][ref/$VersionNumber].
     ///
     /// [ref/$VersionNumber]: https://reference.wolfram.com/language/ref/$VersionNumber.html
@@ -727,8 +1766,11 @@ impl AppVersion {
         self.minor_revision
     }
 
-    #[allow(missing_docs)]
-    pub const fn build_code(&self) -> Option<u32> {
+    /// Build code of this version, if known.
+    ///
+    /// Internal builds can have build codes like `202302011100`, which is why
+    /// this is a [`u64`].
+    pub const fn build_code(&self) -> Option<u64> {
         self.build_code
     }
 
@@ -737,6 +1779,10 @@ impl AppVersion {
             u32::from_str(s).map_err(|err| make_error(s, err))
         }
 
+        fn parse_build_code(s: &str) -> Result<u64, Error> {
+            u64::from_str(s).map_err(|err| make_error(s, err))
+        }
+
         fn make_error(s: &str, err: std::num::ParseIntError) -> Error {
             Error::other(format!(
                 "invalid application version number component: '{}': {}",
@@ -754,7 +1800,7 @@ impl AppVersion {
                 revision: parse(revision)?,
 
                 minor_revision: Some(parse(minor_revision)?),
-                build_code: Some(parse(build_code)?),
+                build_code: Some(parse_build_code(build_code)?),
             },
             // 4 components: major.minor.revision.build_code
             [major, minor, revision, build_code] => AppVersion {
@@ -763,27 +1809,7 @@ impl AppVersion {
                 revision: parse(revision)?,
 
                 minor_revision: None,
-                // build_code: Some(parse(build_code)?),
-                build_code: match u32::from_str(build_code) {
-                    Ok(code) => Some(code),
-                    // FIXME(breaking):
-                    //   Change build_code to be able to represent internal
-                    //   build codes like '202302011100' (which are technically
-                    //   numeric, but overflow u32's).
-                    //
-                    //   The code below is a workaround bugfix to avoid hard
-                    //   erroring on WolframApp's with these build codes, with
-                    //   the contraint that this fix doesn't break semantic
-                    //   versioning compatibility by changing the build_code()
-                    //   return type.
-                    //
-                    //   This fix should be changed when then next major version
-                    //   release of wolfram-app-discovery is made.
-                    Err(err) if *err.kind() == std::num::IntErrorKind::PosOverflow => {
-                        None
-                    },
-                    Err(other) => return Err(make_error(build_code, other)),
-                },
+                build_code: Some(parse_build_code(build_code)?),
             },
             // 3 components: [major.minor.revision]
             [major, minor, revision] => AppVersion {
@@ -861,7 +1887,6 @@ impl WstpSdk {
             )));
         };
 
-
         let compiler_additions = dir.join("CompilerAdditions");
 
         let wstp_h = compiler_additions.join("wstp.h");
@@ -874,10 +1899,12 @@ impl WstpSdk {
             ));
         }
 
+        let (interface, revision) = read_wstp_h_version(&wstp_h)?;
+
         // NOTE: Determine the file name based on the specified `system_id`,
         //       NOT based on the current target OS.
         let wstp_static_library = compiler_additions.join(
-            build_scripts::wstp_static_library_file_name(system_id.operating_system())?,
+            artifacts::wstp_static_library_name_for_interface(system_id, interface)?,
         );
 
         if !wstp_static_library.is_file() {
@@ -893,6 +1920,9 @@ impl WstpSdk {
             sdk_dir: dir,
             compiler_additions,
 
+            interface,
+            revision,
+
             wstp_h,
             wstp_static_library,
         })
@@ -906,6 +1936,22 @@ impl WstpSdk {
         self.sdk_dir.clone()
     }
 
+    /// WSTP library interface version of this SDK, e.g. `4`.
+    ///
+    /// This is the value of the `WSINTERFACE` macro defined in `wstp.h`, or
+    /// [`artifacts::DEFAULT_WSTP_INTERFACE`] if `wstp.h` does not define it.
+    /// The interface version determines the file names of the WSTP libraries.
+    pub fn wstp_interface(&self) -> u32 {
+        self.interface
+    }
+
+    /// WSTP library revision of this SDK, if it could be determined.
+    ///
+    /// This is the value of the `WSREVISION` macro defined in `wstp.h`.
+    pub fn wstp_revision(&self) -> Option<u32> {
+        self.revision
+    }
+
     /// Returns the location of the CompilerAdditions subdirectory of the WSTP
     /// SDK.
     pub fn wstp_compiler_additions_directory(&self) -> PathBuf {
@@ -931,151 +1977,991 @@ impl WstpSdk {
     pub fn wstp_static_library_path(&self) -> PathBuf {
         self.wstp_static_library.clone()
     }
-}
 
-impl Filter {
-    fn allow_all() -> Self {
-        Filter { app_types: None }
-    }
+    /// Returns the location of the
+    /// [WSTP](https://reference.wolfram.com/language/guide/WSTPAPI.html)
+    /// shared (dynamic) library.
+    ///
+    /// The following locations are checked in order:
+    ///
+    /// 1. The CompilerAdditions directory of this SDK.
+    /// 2. The SystemAdditions directory of this SDK.
+    /// 3. If this SDK is part of a Wolfram installation, the redistributable
+    ///    copy in `$InstallationDirectory/SystemFiles/Libraries/$SystemID/`.
+    ///
+    /// On macOS, this is the location of the `wstp.framework` bundle.
+    ///
+    /// *Note: The [wstp](https://crates.io/crates/wstp) crate provides safe Rust bindings
+    /// to WSTP.*
+    pub fn wstp_shared_library_path(&self) -> Result<PathBuf, Error> {
+        let name = artifacts::wstp_shared_library_name_for_interface(
+            self.system_id,
+            self.interface,
+        )?;
+
+        let mut candidates = vec![
+            self.compiler_additions.join(&name),
+            self.sdk_dir.join("SystemAdditions").join(&name),
+        ];
+
+        // `$InstallationDirectory/SystemFiles/Links/WSTP/DeveloperKit/$SystemID`
+        if let Some(installation_dir) = self.sdk_dir.ancestors().nth(5) {
+            candidates.push(
+                installation_dir
+                    .join("SystemFiles")
+                    .join("Libraries")
+                    .join(self.system_id.as_str())
+                    .join(&name),
+            );
+        }
 
-    fn check_app(&self, app: &WolframApp) -> Result<(), FilterError> {
-        let Filter { app_types } = self;
+        candidates
+            .iter()
+            .find(|path| path.exists())
+            .cloned()
+            .ok_or_else(|| {
+                Error::unexpected_layout(
+                    "WSTP shared library file",
+                    self.sdk_dir.clone(),
+                    candidates[0].clone(),
+                )
+            })
+    }
 
-        // Filter by application type: Mathematica, Engine, Desktop, etc.
-        if let Some(app_types) = app_types {
-            if !app_types.contains(&app.app_type()) {
-                return Err(FilterError::FilterDoesNotMatchAppType {
-                    app_type: app.app_type(),
-                    allowed: app_types.clone(),
-                });
-            }
+    /// Returns the location of the
+    /// [`wsprep`](https://reference.wolfram.com/language/ref/program/wsprep.html)
+    /// template preprocessor executable.
+    ///
+    /// Returns an error if the executable is not present in the
+    /// CompilerAdditions directory of this SDK.
+    pub fn wsprep_executable_path(&self) -> Result<PathBuf, Error> {
+        let wsprep = self
+            .compiler_additions
+            .join(artifacts::wsprep_executable_name(self.system_id)?);
+
+        if !wsprep.is_file() {
+            return Err(Error::unexpected_layout(
+                "wsprep executable",
+                self.sdk_dir.clone(),
+                wsprep,
+            ));
         }
 
-        Ok(())
+        Ok(wsprep)
     }
-}
 
-impl WolframApp {
-    /// Find the default Wolfram Language installation on this computer.
-    ///
-    /// # Discovery procedure
-    ///
-    /// 1. If the [`WOLFRAM_APP_DIRECTORY`][crate::config::env_vars::WOLFRAM_APP_DIRECTORY]
-    ///    environment variable is set, return that.
-    ///
-    ///    - Setting this environment variable may be necessary if a Wolfram application
-    ///      was installed to a location not supported by the automatic discovery
-    ///      mechanisms.
-    ///
-    ///    - This enables advanced users of programs based on `wolfram-app-discovery` to
-    ///      specify the Wolfram installation they would prefer to use.
-    ///
-    /// 2. If `wolframscript` is available on `PATH`, use it to evaluate
-    ///    [`$InstallationDirectory`][$InstallationDirectory], and return the app at
-    ///    that location.
-    ///
-    /// 3. Use operating system APIs to discover installed Wolfram applications.
-    ///    - This will discover apps installed in standard locations, like `/Applications`
-    ///      on macOS or `C:\Program Files` on Windows.
+    /// Returns the location of the
+    /// [`wscc`](https://reference.wolfram.com/language/ref/program/wscc.html)
+    /// compiler driver script.
     ///
-    /// [$InstallationDirectory]: https://reference.wolfram.com/language/ref/$InstallationDirectory.html
-    pub fn try_default() -> Result<Self, Error> {
-        let result = WolframApp::try_default_with_filter(&Filter::allow_all());
+    /// If this SDK does not contain `wscc`, the legacy `mcc` script is
+    /// returned instead. The compiler driver is not provided by the Windows
+    /// SDKs.
+    pub fn wscc_script_path(&self) -> Result<PathBuf, Error> {
+        let names = artifacts::wscc_script_names(self.system_id)?;
+
+        names
+            .iter()
+            .map(|name| self.compiler_additions.join(name))
+            .find(|path| path.is_file())
+            .ok_or_else(|| {
+                Error::unexpected_layout(
+                    "wscc compiler driver script",
+                    self.sdk_dir.clone(),
+                    self.compiler_additions.join(names[0]),
+                )
+            })
+    }
+}
 
-        match &result {
-            Ok(app) => {
-                info!("App discovery succeeded: {}", app.app_directory().display())
-            },
-            Err(err) => info!("App discovery failed: {}", err),
-        }
+/// Read the WSTP library interface version and revision from the `wstp.h`
+/// header file at `wstp_h`.
+pub(crate) fn read_wstp_h_version(wstp_h: &Path) -> Result<(u32, Option<u32>), Error> {
+    let contents = std::fs::read(wstp_h)?;
 
-        result
-    }
+    let (interface, revision) =
+        artifacts::parse_wstp_h_version(&String::from_utf8_lossy(&contents));
 
-    #[doc(hidden)]
-    pub fn try_default_with_filter(filter: &Filter) -> Result<Self, Error> {
-        //------------------------------------------------------------------------
-        // If set, use RUST_WOLFRAM_LOCATION (deprecated) or WOLFRAM_APP_DIRECTORY
-        //------------------------------------------------------------------------
+    Ok((
+        interface.unwrap_or(artifacts::DEFAULT_WSTP_INTERFACE),
+        revision,
+    ))
+}
 
-        #[allow(deprecated)]
-        if let Some(dir) = config::get_env_var(RUST_WOLFRAM_LOCATION) {
-            // This environment variable has been deprecated and will not be checked in
-            // a future version of wolfram-app-discovery. Use the
-            // WOLFRAM_APP_DIRECTORY environment variable instead.
-            config::print_deprecated_env_var_warning(RUST_WOLFRAM_LOCATION, &dir);
+impl LibraryLinkSdk {
+    /// The [`SystemID`] of the runtime libraries in this SDK.
+    pub fn system_id(&self) -> SystemID {
+        self.system_id
+    }
 
-            let dir = PathBuf::from(dir);
+    /// Returns the location of the directory containing the
+    /// [Wolfram *LibraryLink*](https://reference.wolfram.com/language/guide/LibraryLink.html)
+    /// C header files.
+    ///
+    /// See [`WolframApp::library_link_c_includes_directory()`].
+    pub fn c_includes_directory(&self) -> PathBuf {
+        self.c_includes_directory.clone()
+    }
 
-            // TODO: If an error occurs in from_path(), attach the fact that we're using
-            //       the environment variable to the error message.
-            let app = WolframApp::from_installation_directory(dir)?;
+    /// Returns the location of the `SystemFiles/Libraries/$SystemID/`
+    /// directory, which contains the Wolfram runtime libraries that
+    /// *LibraryLink* libraries can link against.
+    pub fn libraries_directory(&self) -> PathBuf {
+        self.libraries_directory.clone()
+    }
 
-            // If the app doesn't satisfy the filter, return an error. We return an error
-            // instead of silently proceeding to try the next discovery step because
-            // setting an environment variable constitutes (typically) an explicit choice
-            // by the user to use a specific installation. We can't fulfill that choice
-            // because it doesn't satisfy the filter, but we can respect it by informing
-            // them via an error instead of silently ignoring their choice.
-            if let Err(filter_err) = filter.check_app(&app) {
-                return Err(Error::app_does_not_match_filter(
-                    RUST_WOLFRAM_LOCATION,
-                    filter_err,
-                ));
-            }
+    /// *LibraryLink* interface version declared by the `WolframLibrary.h`
+    /// header file, e.g. `7`.
+    ///
+    /// This is the value of the `WolframLibraryVersion` macro, or `None` if it
+    /// could not be determined.
+    pub fn library_version(&self) -> Option<u32> {
+        self.library_version
+    }
+}
 
-            return Ok(app);
-        }
+/// Read the *LibraryLink* interface version from the `WolframLibrary.h` header
+/// file in `c_includes_directory`.
+fn read_wolfram_library_h_version(c_includes_directory: &Path) -> Option<u32> {
+    let contents = std::fs::read(c_includes_directory.join("WolframLibrary.h")).ok()?;
 
-        // TODO: WOLFRAM_(APP_)?INSTALLATION_DIRECTORY? Is this useful in any
-        //       situation where WOLFRAM_APP_DIRECTORY wouldn't be easy to set
-        //       (e.g. set based on $InstallationDirectory)?
+    String::from_utf8_lossy(&contents).lines().find_map(|line| {
+        let mut words = line.split_whitespace();
 
-        if let Some(dir) = config::get_env_var(WOLFRAM_APP_DIRECTORY) {
-            let dir = PathBuf::from(dir);
+        match (words.next(), words.next(), words.next()) {
+            (Some("#define"), Some("WolframLibraryVersion"), Some(value)) => {
+                value.parse().ok()
+            },
+            _ => None,
+        }
+    })
+}
 
-            let app = WolframApp::from_app_directory(dir)?;
+impl DiscoveryOptions {
+    /// Scan depth used when [`DiscoveryOptions::max_scan_depth`] is not
+    /// specified.
+    ///
+    /// This is deep enough for the default Linux installation layout,
+    /// `/usr/local/Wolfram/<app type>/<version>/`.
+    pub const DEFAULT_MAX_SCAN_DEPTH: usize = 2;
+
+    /// Entry count used when [`DiscoveryOptions::max_scan_entries`] is not
+    /// specified.
+    pub const DEFAULT_MAX_SCAN_ENTRIES: usize = 1000;
+
+    /// Maximum scan depth and number of scanned entries, with defaults applied.
+    pub(crate) fn scan_limits(&self) -> (usize, usize) {
+        (
+            self.max_scan_depth.unwrap_or(Self::DEFAULT_MAX_SCAN_DEPTH),
+            self.max_scan_entries
+                .unwrap_or(Self::DEFAULT_MAX_SCAN_ENTRIES),
+        )
+    }
+}
 
-            if let Err(filter_err) = filter.check_app(&app) {
-                return Err(Error::app_does_not_match_filter(
-                    WOLFRAM_APP_DIRECTORY,
-                    filter_err,
-                ));
-            }
+impl FrontEndRole {
+    /// Front end preference order used when
+    /// [`DiscoveryOptions::front_end_preference`] is not specified.
+    ///
+    /// The `Wolfram` launcher is preferred, because it is the primary launcher
+    /// in newer apps that contain both.
+    pub const DEFAULT_PREFERENCE: [FrontEndRole; 2] =
+        [FrontEndRole::Wolfram, FrontEndRole::Mathematica];
+
+    /// Get a list of all [`FrontEndRole`] variants.
+    pub fn variants() -> Vec<FrontEndRole> {
+        vec![FrontEndRole::Wolfram, FrontEndRole::Mathematica]
+    }
 
-            return Ok(app);
+    /// File name of the front end launcher with this role.
+    fn executable_name(&self) -> &'static str {
+        match (self, OperatingSystem::target_os()) {
+            // NOTE: `wolfram.exe` is a kernel launcher, and Windows file
+            //       names are case-insensitive.
+            (FrontEndRole::Wolfram, OperatingSystem::Windows) => "WolframNB.exe",
+            (FrontEndRole::Mathematica, OperatingSystem::Windows) => "Mathematica.exe",
+            (FrontEndRole::Wolfram, _) => "Wolfram",
+            (FrontEndRole::Mathematica, _) => "Mathematica",
         }
+    }
+}
 
-        //-----------------------------------------------------------------------
-        // If wolframscript is on PATH, use it to evaluate $InstallationDirectory
-        //-----------------------------------------------------------------------
+impl LinuxLauncher {
+    /// Get a list of all [`LinuxLauncher`] variants.
+    pub fn variants() -> Vec<LinuxLauncher> {
+        vec![
+            LinuxLauncher::Mathematica,
+            LinuxLauncher::Wolfram,
+            LinuxLauncher::Math,
+            LinuxLauncher::WolframKernel,
+        ]
+    }
 
-        if let Some(dir) = try_wolframscript_installation_directory()? {
-            let app = WolframApp::from_installation_directory(dir)?;
-            // If the app doesn't pass the filter, silently ignore it.
-            if !filter.check_app(&app).is_err() {
-                return Ok(app);
-            }
+    /// File name of this launcher script.
+    pub fn script_name(&self) -> &'static str {
+        match self {
+            LinuxLauncher::Mathematica => "mathematica",
+            LinuxLauncher::Wolfram => "wolfram",
+            LinuxLauncher::Math => "math",
+            LinuxLauncher::WolframKernel => "WolframKernel",
         }
+    }
+}
 
-        //--------------------------------------------------
-        // Look in the operating system applications folder.
-        //--------------------------------------------------
+impl SortStrategy {
+    /// Compare two apps according to this strategy.
+    ///
+    /// Returns [`Ordering::Less`] if `a` should be ranked before `b`.
+    pub fn compare(&self, a: &WolframApp, b: &WolframApp) -> Ordering {
+        // Note: `best_order()` ranks the best app last, so its arguments are
+        //       reversed here.
+        let newest = || WolframApp::best_order(b, a);
 
-        let apps: Vec<WolframApp> = discover_with_filter(filter);
+        match self {
+            SortStrategy::PreferNewest => newest(),
+            SortStrategy::PreferFrontEnd => {
+                let a = a.app_type().has_front_end();
+                let b = b.app_type().has_front_end();
 
-        if let Some(first) = apps.into_iter().next() {
-            return Ok(first);
-        }
+                b.cmp(&a).then_with(newest)
+            },
+            SortStrategy::PreferEngine => {
+                let a = a.app_type() == WolframAppType::Engine;
+                let b = b.app_type() == WolframAppType::Engine;
 
-        //------------------------------------------------------------
-        // No Wolfram applications could be found, so return an error.
-        //------------------------------------------------------------
+                b.cmp(&a).then_with(newest)
+            },
+            SortStrategy::PreferReleased => {
+                let a = a.is_prerelease();
+                let b = b.is_prerelease();
 
-        Err(Error::undiscoverable(
-            "default Wolfram Language installation".to_owned(),
-            Some(WOLFRAM_APP_DIRECTORY),
-        ))
+                a.cmp(&b).then_with(newest)
+            },
+            SortStrategy::Custom(compare) => compare(a, b),
+        }
+    }
+}
+
+impl WolframScriptInvocation {
+    /// The program and arguments that were executed.
+    ///
+    /// The first element is the location of the `wolframscript` executable.
+    pub fn command_line(&self) -> &[String] {
+        &self.command_line
+    }
+
+    /// How long `wolframscript` took to exit.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// The first line of output printed by `wolframscript`.
+    pub fn output(&self) -> &str {
+        &self.output
+    }
+}
+
+impl FrontEndExecutable {
+    /// Branding of this front end launcher.
+    pub fn role(&self) -> FrontEndRole {
+        self.role
+    }
+
+    /// Location of this front end launcher executable.
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+}
+
+impl Registration {
+    /// Program and arguments of a command that removes this registration.
+    ///
+    /// The command only removes the registration; it does not modify any files
+    /// of the registered application.
+    pub fn removal_command(&self) -> Vec<String> {
+        match self {
+            Registration::WindowsRegistryKey { key, wow64_32 } => {
+                let mut command = vec![
+                    "reg".to_owned(),
+                    "delete".to_owned(),
+                    key.clone(),
+                    "/f".to_owned(),
+                ];
+
+                if *wow64_32 {
+                    command.push("/reg:32".to_owned());
+                }
+
+                command
+            },
+            Registration::LaunchServices(app_directory) => vec![
+                LSREGISTER.to_owned(),
+                "-u".to_owned(),
+                app_directory.display().to_string(),
+            ],
+        }
+    }
+
+    /// Remove this registration by running [`Registration::removal_command()`].
+    pub fn remove(&self) -> Result<(), Error> {
+        let command_line = self.removal_command();
+
+        let status = process::Command::new(&command_line[0])
+            .args(&command_line[1..])
+            .status()?;
+
+        if !status.success() {
+            return Err(Error::other(format!(
+                "command `{}` exited with non-success status: {status}",
+                command_line.join(" ")
+            )));
+        }
+
+        Ok(())
+    }
+}
+
+/// Location of the macOS Launch Services registration tool.
+const LSREGISTER: &str = "/System/Library/Frameworks/CoreServices.framework/Frameworks/LaunchServices.framework/Support/lsregister";
+
+impl RegistrationReport {
+    /// Group `apps` into duplicates and orphaned registrations.
+    pub(crate) fn from_registrations(apps: Vec<WolframApp>) -> Self {
+        let mut duplicates: Vec<Vec<WolframApp>> = Vec::new();
+        let mut orphaned = Vec::new();
+
+        for app in apps {
+            if !app.app_directory.is_dir() {
+                orphaned.push(app);
+                continue;
+            }
+
+            match duplicates.iter_mut().find(|group| group[0] == app) {
+                Some(group) => group.push(app),
+                None => duplicates.push(vec![app]),
+            }
+        }
+
+        duplicates.retain(|group| group.len() > 1);
+
+        RegistrationReport {
+            duplicates,
+            orphaned,
+        }
+    }
+
+    /// Groups of registrations that refer to the same application directory.
+    ///
+    /// Each group contains at least two apps, in the order they were found.
+    pub fn duplicates(&self) -> &[Vec<WolframApp>] {
+        &self.duplicates
+    }
+
+    /// Registrations whose application directory does not exist.
+    ///
+    /// Properties that are read from the application directory, like the
+    /// application version, are not known for these apps.
+    pub fn orphaned(&self) -> &[WolframApp] {
+        &self.orphaned
+    }
+
+    /// Returns `true` if no duplicate or orphaned registrations were found.
+    pub fn is_clean(&self) -> bool {
+        self.duplicates.is_empty() && self.orphaned.is_empty()
+    }
+}
+
+impl DefaultAppSelection {
+    /// The default app.
+    pub fn app(&self) -> &WolframApp {
+        &self.app
+    }
+
+    /// The other discovered apps, ranked in the same order as [`discover()`].
+    pub fn runners_up(&self) -> &[RunnerUp] {
+        &self.runners_up
+    }
+
+    /// Get the default app, discarding the runners-up.
+    pub fn into_app(self) -> WolframApp {
+        self.app
+    }
+}
+
+impl RunnerUp {
+    /// The app that was not selected.
+    pub fn app(&self) -> &WolframApp {
+        &self.app
+    }
+
+    /// Why this app was not selected as the default app.
+    pub fn reason(&self) -> &RunnerUpReason {
+        &self.reason
+    }
+}
+
+impl RunnerUpReason {
+    /// Determine why `other` was ranked below `selected`.
+    fn compare(selected: &WolframApp, other: &WolframApp) -> Self {
+        if other.app_wolfram_version().ok() < selected.app_wolfram_version().ok() {
+            return RunnerUpReason::OlderVersion;
+        }
+
+        if selected.app_type().has_front_end() && !other.app_type().has_front_end() {
+            return RunnerUpReason::LacksFrontEnd;
+        }
+
+        RunnerUpReason::RankedLower
+    }
+}
+
+impl WindowsCaps {
+    /// Construct a set of capability bits from a raw registry `Caps` value.
+    pub const fn from_bits(bits: u32) -> Self {
+        WindowsCaps(bits)
+    }
+
+    /// The raw registry `Caps` value.
+    pub const fn bits(&self) -> u32 {
+        self.0
+    }
+
+    /// Returns `true` if every bit set in `other` is also set in `self`.
+    pub const fn contains(&self, other: WindowsCaps) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Indices of the bits that are set, in increasing order.
+    pub fn set_bits(&self) -> impl Iterator<Item = u32> {
+        let bits = self.0;
+
+        (0..u32::BITS).filter(move |index| bits & (1 << index) != 0)
+    }
+}
+
+impl Filter {
+    fn allow_all() -> Self {
+        Filter {
+            app_types: None,
+            min_version: None,
+            kernel_system_id: None,
+            requires_front_end: false,
+            requires_activation: false,
+            wstp_sdk_system_id: None,
+        }
+    }
+
+    fn check_app(&self, app: &WolframApp) -> Result<(), FilterError> {
+        let Filter {
+            app_types,
+            min_version,
+            kernel_system_id,
+            requires_front_end,
+            requires_activation,
+            wstp_sdk_system_id,
+        } = self;
+
+        // Filter by application type: Mathematica, Engine, Desktop, etc.
+        if let Some(app_types) = app_types {
+            if !app_types.contains(&app.app_type()) {
+                return Err(FilterError::FilterDoesNotMatchAppType {
+                    app_type: app.app_type(),
+                    allowed: app_types.clone(),
+                });
+            }
+        }
+
+        // Filter by Wolfram Language version. An app whose version can't be
+        // determined doesn't match.
+        if let Some(min_version) = min_version {
+            let version = app.app_wolfram_version().ok();
+
+            if version.as_ref() < Some(min_version) {
+                return Err(FilterError::FilterDoesNotMatchMinVersion {
+                    version,
+                    min_version: min_version.clone(),
+                });
+            }
+        }
+
+        // Filter by the platforms that kernel binaries are provided for.
+        if let Some(required) = kernel_system_id {
+            // An app whose kernel binaries can't be listed is treated as
+            // providing none.
+            let available = app.supported_system_ids().unwrap_or_default();
+
+            if !available.contains(required) {
+                return Err(FilterError::FilterDoesNotMatchKernelSystemID {
+                    required: *required,
+                    available,
+                });
+            }
+        }
+
+        // Filter by whether a notebook front end is available.
+        if *requires_front_end && !app.has_front_end() {
+            return Err(FilterError::FilterRequiresFrontEnd {
+                app_type: app.app_type(),
+            });
+        }
+
+        // Filter by whether the app appears to be activated.
+        if *requires_activation && !app.is_activated() {
+            return Err(FilterError::FilterRequiresActivation {
+                installation_directory: app.installation_directory(),
+            });
+        }
+
+        // Filter by the platforms that WSTP SDKs are provided for.
+        if let Some(required) = wstp_sdk_system_id {
+            // Ignore any SDK directories that are not valid.
+            let available: Vec<SystemID> = app
+                .wstp_sdks()
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(Result::ok)
+                .map(|sdk| sdk.system_id())
+                .collect();
+
+            if !available.contains(required) {
+                return Err(FilterError::FilterDoesNotMatchWstpSdkSystemID {
+                    required: *required,
+                    available,
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl WolframApp {
+    /// Find the default Wolfram Language installation on this computer.
+    ///
+    /// # Discovery procedure
+    ///
+    /// If the [`WOLFRAM_APP_MIN_VERSION`][crate::config::env_vars::WOLFRAM_APP_MIN_VERSION]
+    /// environment variable is set, every step only returns apps with at least
+    /// that version.
+    ///
+    /// 1. If the [`WOLFRAM_APP_DIRECTORY`][crate::config::env_vars::WOLFRAM_APP_DIRECTORY]
+    ///    environment variable is set, return that.
+    ///
+    ///    - The variable can list several directories, separated like `PATH`.
+    ///      The first one containing an app that matches is returned.
+    ///
+    ///    - Setting this environment variable may be necessary if a Wolfram application
+    ///      was installed to a location not supported by the automatic discovery
+    ///      mechanisms.
+    ///
+    ///    - This enables advanced users of programs based on `wolfram-app-discovery` to
+    ///      specify the Wolfram installation they would prefer to use.
+    ///
+    /// 2. If a [`wolfram-app.toml`][crate::project_config] file in the current
+    ///    directory or one of its ancestors specifies an app directory, return
+    ///    that.
+    ///
+    ///    - This enables a project to commit the Wolfram installation it
+    ///      should be built with. See `wolfram-app-discovery pin`.
+    ///
+    ///    - If the file instead specifies an app type or minimum version, only
+    ///      apps that satisfy them are returned by the following steps.
+    ///
+    /// 3. If the [user configuration file][crate::user_config] specifies a
+    ///    preferred app directory, and that app matches, return that.
+    ///
+    /// 4. If `wolframscript` is available on `PATH`, use it to evaluate
+    ///    [`$InstallationDirectory`][$InstallationDirectory], and return the app at
+    ///    that location.
+    ///
+    ///    - If that location does not exist on this computer (e.g. because
+    ///      `wolframscript` is configured to use a remote kernel), or is not a
+    ///      valid Wolfram app, a warning is printed and discovery continues.
+    ///
+    ///    - This step is skipped if disabled by the user configuration file,
+    ///      [`DiscoveryOptions::skip_wolframscript`], or the
+    ///      `WOLFRAM_APP_DISCOVERY_DISABLE_WOLFRAMSCRIPT` environment variable.
+    ///
+    /// 5. Use operating system APIs to discover installed Wolfram applications.
+    ///    - This will discover apps installed in standard locations, like `/Applications`
+    ///      on macOS or `C:\Program Files` on Windows.
+    ///    - Apps in the search roots listed in the user configuration file are
+    ///      also discovered.
+    ///
+    /// [$InstallationDirectory]: https://reference.wolfram.com/language/ref/$InstallationDirectory.html
+    pub fn try_default() -> Result<Self, Error> {
+        let result = WolframApp::try_default_with_filter(&Filter::allow_all());
+
+        match &result {
+            Ok(app) => {
+                info!("App discovery succeeded: {}", app.app_directory().display())
+            },
+            Err(err) => info!("App discovery failed: {}", err),
+        }
+
+        result
+    }
+
+    /// Get the default Wolfram Language installation on this computer, performing
+    /// discovery at most once per process.
+    ///
+    /// The first call to this function uses [`WolframApp::try_default()`] to
+    /// locate the default app. The result of that call, whether success or
+    /// failure, is returned by all subsequent calls.
+    ///
+    /// Prefer this function over [`WolframApp::try_default()`] in programs that
+    /// query the default app from many places, and do not expect the set of
+    /// installed apps or the environment to change while the program is
+    /// running.
+    ///
+    /// ```no_run
+    /// use wolfram_app_discovery::WolframApp;
+    ///
+    /// let app: &'static WolframApp = WolframApp::default_cached().unwrap();
+    /// ```
+    pub fn default_cached() -> Result<&'static WolframApp, Error> {
+        static DEFAULT: OnceLock<Result<WolframApp, Error>> = OnceLock::new();
+
+        DEFAULT
+            .get_or_init(WolframApp::try_default)
+            .as_ref()
+            .map_err(Clone::clone)
+    }
+
+    /// Async version of [`WolframApp::try_default()`].
+    ///
+    /// Discovery is performed on a background thread, so awaiting the returned
+    /// future does not block the current thread.
+    ///
+    /// *This function is only available if the `async` feature is enabled.*
+    #[cfg(feature = "async")]
+    pub async fn try_default_async() -> Result<Self, Error> {
+        blocking::spawn(WolframApp::try_default).await
+    }
+
+    /// Async version of [`WolframApp::try_default_with_options()`].
+    ///
+    /// *This function is only available if the `async` feature is enabled.*
+    #[cfg(feature = "async")]
+    pub async fn try_default_with_options_async(
+        options: &DiscoveryOptions,
+    ) -> Result<Self, Error> {
+        let options = options.clone();
+
+        blocking::spawn(move || WolframApp::try_default_with_options(&options)).await
+    }
+
+    /// Find the default Wolfram Language installation on this computer, using
+    /// the specified [`DiscoveryOptions`].
+    ///
+    /// See [`WolframApp::try_default()`] for a description of the discovery
+    /// procedure.
+    pub fn try_default_with_options(options: &DiscoveryOptions) -> Result<Self, Error> {
+        WolframApp::try_default_with_filter_and_options(&Filter::allow_all(), options)
+    }
+
+    #[doc(hidden)]
+    pub fn try_default_with_filter(filter: &Filter) -> Result<Self, Error> {
+        WolframApp::try_default_with_filter_and_options(
+            filter,
+            &DiscoveryOptions::default(),
+        )
+    }
+
+    #[doc(hidden)]
+    pub fn try_default_with_filter_and_options(
+        filter: &Filter,
+        options: &DiscoveryOptions,
+    ) -> Result<Self, Error> {
+        let (specified, filter) = WolframApp::try_specified_default(filter, options)?;
+
+        if let Some(app) = specified {
+            return Ok(app);
+        }
+
+        //--------------------------------------------------
+        // Look in the operating system applications folder.
+        //--------------------------------------------------
+
+        let apps: Vec<WolframApp> = discover_with_filter_and_options(&filter, options);
+
+        if let Some(first) = apps.into_iter().next() {
+            return Ok(first);
+        }
+
+        //------------------------------------------------------------
+        // No Wolfram applications could be found, so return an error.
+        //------------------------------------------------------------
+
+        Err(Error::undiscoverable(
+            "default Wolfram Language installation".to_owned(),
+            Some(WOLFRAM_APP_DIRECTORY),
+        ))
+    }
+
+    /// Find the default Wolfram Language installation on this computer, and
+    /// the other installations that were considered.
+    ///
+    /// The default app is the same app returned by
+    /// [`WolframApp::try_default()`]. The other discovered apps are returned
+    /// as [`RunnerUp`]s, ranked in the same order as [`discover()`], along
+    /// with the reason each one was not selected. This can be used by
+    /// interactive tools to offer to use a different installation, without
+    /// performing discovery a second time.
+    ///
+    /// ```no_run
+    /// use wolfram_app_discovery::WolframApp;
+    ///
+    /// let selection = WolframApp::try_default_detailed().unwrap();
+    ///
+    /// println!("using: {}", selection.app().app_directory().display());
+    ///
+    /// for runner_up in selection.runners_up() {
+    ///     println!(
+    ///         "not using: {} ({})",
+    ///         runner_up.app().app_directory().display(),
+    ///         runner_up.reason()
+    ///     );
+    /// }
+    /// ```
+    pub fn try_default_detailed() -> Result<DefaultAppSelection, Error> {
+        WolframApp::try_default_detailed_with_filter_and_options(
+            &Filter::allow_all(),
+            &DiscoveryOptions::default(),
+        )
+    }
+
+    #[doc(hidden)]
+    pub fn try_default_detailed_with_filter_and_options(
+        filter: &Filter,
+        options: &DiscoveryOptions,
+    ) -> Result<DefaultAppSelection, Error> {
+        let (specified, filter) = WolframApp::try_specified_default(filter, options)?;
+        let is_specified = specified.is_some();
+
+        // Discover every app, not just those that match `filter`, so that the
+        // apps that don't match can be reported as runners-up.
+        let mut apps: Vec<WolframApp> = discover_with_options(options);
+
+        let app = match specified {
+            Some(app) => app,
+            None => match apps.iter().position(|app| filter.check_app(app).is_ok()) {
+                Some(index) => apps.remove(index),
+                None => {
+                    return Err(Error::undiscoverable(
+                        "default Wolfram Language installation".to_owned(),
+                        Some(WOLFRAM_APP_DIRECTORY),
+                    ))
+                },
+            },
+        };
+
+        let app_dir = app.canonical_app_directory();
+
+        let runners_up = apps
+            .into_iter()
+            .filter(|other| other.canonical_app_directory() != app_dir)
+            .map(|other| {
+                let reason = match filter.check_app(&other) {
+                    Err(filter_err) => RunnerUpReason::Filtered(filter_err.to_string()),
+                    Ok(()) if is_specified => RunnerUpReason::Overridden,
+                    Ok(()) => RunnerUpReason::compare(&app, &other),
+                };
+
+                RunnerUp { app: other, reason }
+            })
+            .collect();
+
+        Ok(DefaultAppSelection { app, runners_up })
+    }
+
+    /// Find the default app, if it was specified by an environment variable or
+    /// project configuration file, or can be located using `wolframscript`.
+    ///
+    /// These discovery steps take precedence over operating system discovery.
+    /// See [`WolframApp::try_default()`].
+    ///
+    /// Also returns `filter` combined with any restrictions specified by a
+    /// project configuration file, which should be used by the remaining
+    /// discovery steps.
+    fn try_specified_default(
+        filter: &Filter,
+        options: &DiscoveryOptions,
+    ) -> Result<(Option<Self>, Filter), Error> {
+        // Apply WOLFRAM_APP_MIN_VERSION to every discovery step.
+        let filter = &match env_min_version()? {
+            Some(min_version) => Filter {
+                min_version: filter.min_version.clone().max(Some(min_version)),
+                ..filter.clone()
+            },
+            None => filter.clone(),
+        };
+
+        //------------------------------------------------------------------------
+        // If set, use RUST_WOLFRAM_LOCATION (deprecated) or WOLFRAM_APP_DIRECTORY
+        //------------------------------------------------------------------------
+
+        #[allow(deprecated)]
+        if let Some(dir) = config::get_env_var(RUST_WOLFRAM_LOCATION) {
+            // This environment variable has been deprecated and will not be checked in
+            // a future version of wolfram-app-discovery. Use the
+            // WOLFRAM_APP_DIRECTORY environment variable instead.
+            config::print_deprecated_env_var_warning(RUST_WOLFRAM_LOCATION, &dir);
+
+            let dir = PathBuf::from(dir);
+
+            // TODO: If an error occurs in from_path(), attach the fact that we're using
+            //       the environment variable to the error message.
+            let mut app = WolframApp::from_installation_directory(dir)?;
+            app.apply_options(options);
+
+            // If the app doesn't satisfy the filter, return an error. We return an error
+            // instead of silently proceeding to try the next discovery step because
+            // setting an environment variable constitutes (typically) an explicit choice
+            // by the user to use a specific installation. We can't fulfill that choice
+            // because it doesn't satisfy the filter, but we can respect it by informing
+            // them via an error instead of silently ignoring their choice.
+            if let Err(filter_err) = filter.check_app(&app) {
+                return Err(Error::app_does_not_match_filter(
+                    RUST_WOLFRAM_LOCATION,
+                    filter_err,
+                ));
+            }
+
+            return Ok((Some(app), filter.clone()));
+        }
+
+        // TODO: WOLFRAM_(APP_)?INSTALLATION_DIRECTORY? Is this useful in any
+        //       situation where WOLFRAM_APP_DIRECTORY wouldn't be easy to set
+        //       (e.g. set based on $InstallationDirectory)?
+
+        // If WOLFRAM_APP_DIRECTORY lists several directories, use the first one
+        // that contains an app that satisfies the filter. If none do, return
+        // the error for the first directory.
+        let mut first_err = None;
+
+        for dir in specified_app_directories() {
+            let app = WolframApp::from_app_directory(dir).and_then(|mut app| {
+                app.apply_options(options);
+
+                match filter.check_app(&app) {
+                    Ok(()) => Ok(app),
+                    Err(filter_err) => Err(Error::app_does_not_match_filter(
+                        WOLFRAM_APP_DIRECTORY,
+                        filter_err,
+                    )),
+                }
+            });
+
+            match app {
+                Ok(app) => return Ok((Some(app), filter.clone())),
+                Err(err) => {
+                    first_err.get_or_insert(err);
+                },
+            }
+        }
+
+        if let Some(err) = first_err {
+            return Err(err);
+        }
+
+        //-----------------------------------------------------------------------
+        // If a project configuration file specifies an app directory, use that;
+        // otherwise apply any restrictions it specifies to the remaining steps
+        //-----------------------------------------------------------------------
+
+        let filter = match ProjectConfig::find()? {
+            Some(project) => {
+                let filter = project.restrict(filter);
+
+                if let Some(dir) = project.app_directory() {
+                    let mut app = WolframApp::from_app_directory(dir.to_path_buf())?;
+                    app.apply_options(options);
+
+                    if let Err(filter_err) = filter.check_app(&app) {
+                        return Err(Error::configured_app_does_not_match_filter(
+                            project.path().to_path_buf(),
+                            filter_err,
+                        ));
+                    }
+
+                    return Ok((Some(app), filter));
+                }
+
+                filter
+            },
+            None => filter.clone(),
+        };
+
+        //-----------------------------------------------------------------------
+        // If the user configuration file specifies a preferred app, use that
+        //-----------------------------------------------------------------------
+
+        let user_config = UserConfig::load_or_warn();
+
+        if let Some(dir) = user_config.as_ref().and_then(UserConfig::app_directory) {
+            match WolframApp::from_app_directory(dir.to_path_buf()) {
+                Ok(mut app) => {
+                    app.apply_options(options);
+
+                    // The preferred app applies to every project, so if it
+                    // doesn't pass the filter, silently ignore it.
+                    if filter.check_app(&app).is_ok() {
+                        return Ok((Some(app), filter));
+                    }
+                },
+                Err(err) => warning(&format!(
+                    "ignoring app directory specified by user configuration file: {err}"
+                )),
+            }
+        }
+
+        //-----------------------------------------------------------------------
+        // If wolframscript is on PATH, use it to evaluate $InstallationDirectory
+        //-----------------------------------------------------------------------
+
+        let invocation = match user_config {
+            Some(user_config) if !user_config.allow_wolframscript() => None,
+            _ => try_wolframscript_installation_directory(options)?,
+        };
+
+        if let Some(invocation) = invocation {
+            let dir = PathBuf::from(invocation.output());
+
+            // If wolframscript is configured to use a remote kernel, or a kernel
+            // that has since been deleted, the $InstallationDirectory it returns
+            // will not exist on this computer.
+            let app = if dir.is_dir() {
+                WolframApp::from_installation_directory(dir)
+            } else {
+                Err(Error::other(format!(
+                    "directory does not exist on this computer (wolframscript may \
+                    be configured to use a remote or deleted kernel): {}",
+                    dir.display()
+                )))
+            };
+
+            match app {
+                Ok(mut app) => {
+                    app.apply_options(options);
+
+                    info!("wolframscript located installation directory: {invocation}");
+                    app.wolframscript_invocation = Some(invocation);
+
+                    // If the app doesn't pass the filter, silently ignore it.
+                    if !filter.check_app(&app).is_err() {
+                        return Ok((Some(app), filter));
+                    }
+                },
+                // Don't fail the whole discovery procedure; fall back to
+                // searching the operating system applications folder.
+                Err(err) => warning(&format!(
+                    "ignoring $InstallationDirectory returned by wolframscript: {err}"
+                )),
+            }
+        }
+
+        Ok((None, filter))
     }
 
     /// Construct a `WolframApp` from an application directory path.
@@ -1085,6 +2971,8 @@ impl WolframApp {
     /// Operating system | Example path
     /// -----------------|-------------
     /// macOS            | /Applications/Mathematica.app
+    /// Windows          | C:\Program Files\Wolfram Research\Mathematica\14.0
+    /// Linux            | /usr/local/Wolfram/Mathematica/14.0/
     pub fn from_app_directory(app_dir: PathBuf) -> Result<WolframApp, Error> {
         if !app_dir.is_dir() {
             return Err(Error::other(format!(
@@ -1107,6 +2995,8 @@ impl WolframApp {
     /// Operating system | Example path
     /// -----------------|-------------
     /// macOS            | /Applications/Mathematica.app/Contents/
+    /// Windows          | C:\Program Files\Wolfram Research\Wolfram Engine\14.0
+    /// Linux            | /usr/local/Wolfram/WolframEngine/14.0/
     pub fn from_installation_directory(location: PathBuf) -> Result<WolframApp, Error> {
         if !location.is_dir() {
             return Err(Error::other(format!(
@@ -1135,7 +3025,8 @@ impl WolframApp {
                 //       released in the last few years, and for all Wolfram app types?
                 location
             },
-            OperatingSystem::Linux | OperatingSystem::Other => {
+            OperatingSystem::Linux => location,
+            OperatingSystem::Other => {
                 return Err(Error::platform_unsupported(
                     "WolframApp::from_installation_directory()",
                 ));
@@ -1150,13 +3041,118 @@ impl WolframApp {
         // }
     }
 
+    /// Construct a `WolframApp` from a path to, or a path inside of, a Wolfram
+    /// application.
+    ///
+    /// `path` may be any of:
+    ///
+    /// * an application directory, as accepted by
+    ///   [`WolframApp::from_app_directory()`]
+    /// * an [`$InstallationDirectory`][ref/$InstallationDirectory], as accepted
+    ///   by [`WolframApp::from_installation_directory()`]
+    /// * a file or directory inside an application, e.g. the
+    ///   [`WolframKernel`][ref/WolframKernel] executable. Symbolic links are
+    ///   followed, so e.g. a `wolfram` launcher on the `PATH` that links to a
+    ///   kernel executable is also accepted.
+    ///
+    /// [ref/$InstallationDirectory]: https://reference.wolfram.com/language/ref/$InstallationDirectory.html
+    /// [ref/WolframKernel]: https://reference.wolfram.com/language/ref/program/WolframKernel.html
+    ///
+    /// # Example paths:
+    ///
+    /// Operating system | Example path
+    /// -----------------|-------------
+    /// macOS            | /Applications/Mathematica.app/Contents/MacOS/WolframKernel
+    /// Linux            | /usr/local/Wolfram/Mathematica/14.1/Executables/WolframKernel
+    pub fn from_path(path: PathBuf) -> Result<WolframApp, Error> {
+        if !path.exists() {
+            return Err(Error::other(format!(
+                "specified Wolfram app location does not exist: {}",
+                path.display()
+            )));
+        }
+
+        // An $InstallationDirectory on macOS is also a valid bundle directory,
+        // so it must be checked for before trying `path` as an app directory.
+        if OperatingSystem::target_os() == OperatingSystem::MacOS
+            && path.file_name() == Some(std::ffi::OsStr::new("Contents"))
+        {
+            if let Ok(app) = WolframApp::from_installation_directory(path.clone()) {
+                return Ok(app);
+            }
+        }
+
+        let app_dir_err = match path.is_dir() {
+            true => match WolframApp::from_app_directory(path.clone()) {
+                Ok(app) => return Ok(app),
+                Err(err) => Some(err),
+            },
+            false => None,
+        };
+
+        // Otherwise, `path` should be inside an app directory.
+        let canonical = path.canonicalize()?;
+
+        for ancestor in canonical.ancestors() {
+            if ancestor == path || !ancestor.is_dir() {
+                continue;
+            }
+
+            if let Ok(app) = WolframApp::from_app_directory(ancestor.to_owned()) {
+                return Ok(app);
+            }
+        }
+
+        Err(app_dir_err.unwrap_or_else(|| {
+            Error::other(format!(
+                "specified path is not inside a Wolfram app: {}",
+                path.display()
+            ))
+        }))
+    }
+
+    /// Construct a `WolframApp` from a path to, or a path inside of, a Wolfram
+    /// application, using the specified [`DiscoveryOptions`].
+    ///
+    /// See [`WolframApp::from_path()`] for the accepted paths.
+    pub fn from_path_with_options(
+        path: PathBuf,
+        options: &DiscoveryOptions,
+    ) -> Result<WolframApp, Error> {
+        let mut app = WolframApp::from_path(path)?;
+
+        app.apply_options(options);
+
+        Ok(app)
+    }
+
     // Properties
 
+    /// Get the [`SystemID`] used to resolve target-specific resources in this
+    /// app, like the SDK returned by [`WolframApp::target_wstp_sdk()`].
+    ///
+    /// The following sources are checked in order:
+    ///
+    /// 1. [`DiscoveryOptions::target_system_id`], if this app was discovered
+    ///    using [`DiscoveryOptions`].
+    /// 2. The [`WOLFRAM_TARGET_SYSTEM_ID`][crate::config::env_vars::WOLFRAM_TARGET_SYSTEM_ID]
+    ///    environment variable.
+    /// 3. [`SystemID::try_current_rust_target()`].
+    pub fn target_system_id(&self) -> Result<SystemID, Error> {
+        resolve_target_system_id(self.target_system_id)
+    }
+
     /// Get the product type of this application.
     pub fn app_type(&self) -> WolframAppType {
         self.app_type.clone()
     }
 
+    /// Get the product name of this application, as reported by the operating
+    /// system.
+    pub fn app_name(&self) -> &str {
+        &self.app_name
+    }
+
     /// Get the application version.
     ///
     /// See also [`WolframApp::wolfram_version()`], which returns the version of the
@@ -1170,6 +3166,77 @@ impl WolframApp {
         self.app_directory.clone()
     }
 
+    /// Returns `true` if this app appears to be a prototype or nightly build,
+    /// rather than a released version.
+    ///
+    /// Wolfram Research does not mark prerelease builds in a single, reliable
+    /// way, so this uses the following heuristics:
+    ///
+    /// * The [application name][WolframApp::app_name] or the name of the
+    ///   [application directory][WolframApp::app_directory] contains
+    ///   `Prototype`, `Nightly`, or `Prerelease` (ignoring case).
+    /// * The [application version][WolframApp::app_version] has a major
+    ///   version of `0`.
+    pub fn is_prerelease(&self) -> bool {
+        fn has_prerelease_marker(name: &str) -> bool {
+            let name = name.to_ascii_lowercase();
+
+            ["prototype", "nightly", "prerelease", "pre-release"]
+                .iter()
+                .any(|marker| name.contains(marker))
+        }
+
+        if self.app_version.major() == 0 {
+            return true;
+        }
+
+        has_prerelease_marker(&self.app_name)
+            || self
+                .app_directory
+                .file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(has_prerelease_marker)
+    }
+
+    /// Multi-line description of the type, version, and location of this app.
+    ///
+    /// The first line is the [`Display`] representation of this app, e.g.
+    /// `Wolfram Engine 13.3.0 at /usr/local/Wolfram/WolframEngine/13.3`. Each
+    /// following line has the form `<label>: <value>`, using the label of the
+    /// corresponding [`Property`][crate::property::Property].
+    pub fn summary(&self) -> String {
+        use crate::property::Property;
+
+        let mut lines = vec![self.to_string()];
+
+        let mut push = |property: Property, value: String| {
+            let label = format!("{}:", property.label());
+            lines.push(format!("{label:<26}{value}"));
+        };
+
+        push(Property::AppType, self.app_type().app_name().to_owned());
+        push(Property::AppName, self.app_name.clone());
+        push(Property::AppVersion, self.app_version.to_string());
+
+        if let Ok(version) = self.app_wolfram_version() {
+            push(Property::WolframVersion, version.to_string());
+        }
+
+        push(
+            Property::AppDirectory,
+            self.app_directory.display().to_string(),
+        );
+
+        if let Some(executable) = &self.app_executable {
+            push(
+                Property::AppExecutablePath,
+                executable.display().to_string(),
+            );
+        }
+
+        lines.join("\n")
+    }
+
     /// Location of the application's main executable.
     ///
     /// * **macOS:** `CFBundleCopyExecutableURL()` location.
@@ -1179,10 +3246,91 @@ impl WolframApp {
         self.app_executable.clone()
     }
 
+    /// Location of the application's main executable.
+    ///
+    /// This is the same as [`WolframApp::app_executable()`], but returns an
+    /// error if the location of the executable is not known.
+    pub fn app_executable_path(&self) -> Result<PathBuf, Error> {
+        self.app_executable().ok_or_else(|| {
+            Error::undiscoverable("application executable".to_owned(), None)
+        })
+    }
+
+    /// The `wolframscript` evaluation of
+    /// [`$InstallationDirectory`](https://reference.wolfram.com/language/ref/$InstallationDirectory.html)
+    /// used to locate this app.
+    ///
+    /// This is only set on apps returned by [`WolframApp::try_default()`] (and
+    /// related functions) that were located using `wolframscript`. It can be
+    /// used to explain why a particular installation was chosen.
+    pub fn wolframscript_invocation(&self) -> Option<&WolframScriptInvocation> {
+        self.wolframscript_invocation.as_ref()
+    }
+
+    /// The operating system registration this app was discovered from.
+    ///
+    /// This is `None` for apps that were not discovered from a registration,
+    /// e.g. apps located using an environment variable, or apps discovered by
+    /// searching the file system on Linux.
+    pub fn registration(&self) -> Option<&Registration> {
+        self.registration.as_ref()
+    }
+
+    /// Capability bits recorded for this app in the Windows registry.
+    ///
+    /// This is `None` for apps that were not discovered from the Windows
+    /// registry.
+    pub fn windows_caps(&self) -> Option<WindowsCaps> {
+        self.windows_caps
+    }
+
+    /// The platform identity of this installation.
+    ///
+    /// This is `None` on Linux, where installations have no platform
+    /// identity, and for apps whose identity could not be read.
+    pub fn installation_id(&self) -> Option<&InstallationId> {
+        self.installation_id.as_ref()
+    }
+
     /// Returns the version of the [Wolfram Language][WL] bundled with this application.
     ///
+    /// This is normally the application version read from the app metadata. If
+    /// that could not be determined and the `kernel-properties` feature is
+    /// enabled, the kernel of this app is launched to query the version
+    /// instead (see [`WolframApp::kernel_properties()`]). The kernel is
+    /// launched directly, so this does not depend on `wolframscript` being
+    /// installed. The kernel is not launched if this app was discovered with
+    /// [`DiscoveryOptions::offline`] set.
+    ///
     /// [WL]: https://wolfram.com/language
     pub fn wolfram_version(&self) -> Result<WolframVersion, Error> {
+        #[cfg(feature = "kernel-properties")]
+        if self.app_version.major == 0 && !self.offline {
+            // Launching the kernel normally takes a few seconds; don't wait
+            // indefinitely if it hangs.
+            const QUERY_TIMEOUT: Duration = Duration::from_secs(60);
+
+            let timeout = self.timeout.unwrap_or(QUERY_TIMEOUT);
+
+            return match self.kernel_properties(Some(timeout)) {
+                Ok(properties) => Ok(properties.wolfram_version().clone()),
+                Err(err) => Err(Error::other(format!(
+                    "wolfram app has invalid application version: {:?}, and querying the kernel failed: {err}  (at: {})",
+                    self.app_version,
+                    self.app_directory.display()
+                ))),
+            };
+        }
+
+        self.app_wolfram_version()
+    }
+
+    /// The Wolfram Language version implied by the application version of this
+    /// app, without launching the kernel.
+    ///
+    /// Used by discovery, sorting, formatting, and layout resolution, which
+    /// must not launch a kernel.
+    pub(crate) fn app_wolfram_version(&self) -> Result<WolframVersion, Error> {
         if self.app_version.major == 0 {
             return Err(Error::other(format!(
                 "wolfram app has invalid application version: {:?}  (at: {})",
@@ -1194,57 +3342,24 @@ impl WolframApp {
         // TODO: Are there any Wolfram products where the application version number is
         //       not the same as the Wolfram Language version it contains?
         //
-        //       What about any Wolfram apps that do not contain a Wolfram Languae instance?
-        Ok(WolframVersion {
-            major: self.app_version.major,
-            minor: self.app_version.minor,
-            patch: self.app_version.revision,
-        })
-
-        /* TODO:
-            Look into fixing or working around the `wolframscript` hang on Windows, and generally
-            improving this approach. E.g. use WSTP instead of parsing the stdout of wolframscript.
-
-        // MAJOR.MINOR
-        let major_minor = self
-            .wolframscript_output("$VersionNumber")?
-            .split(".")
-            .map(ToString::to_string)
-            .collect::<Vec<String>>();
-
-        let [major, mut minor]: [String; 2] = match <[String; 2]>::try_from(major_minor) {
-            Ok(pair @ [_, _]) => pair,
-            Err(major_minor) => {
-                return Err(Error(format!(
-                    "$VersionNumber has unexpected number of components: {:?}",
-                    major_minor
-                )))
-            },
-        };
-        // This can happen in major versions, when $VersionNumber formats as e.g. "13."
-        if minor == "" {
-            minor = String::from("0");
-        }
-
-        // PATCH
-        let patch = self.wolframscript_output("$ReleaseNumber")?;
-
-        let major = u32::from_str(&major).expect("unexpected $VersionNumber format");
-        let minor = u32::from_str(&minor).expect("unexpected $VersionNumber format");
-        let patch = u32::from_str(&patch).expect("unexpected $ReleaseNumber format");
-
+        //       What about any Wolfram apps that do not contain a Wolfram Languae instance?
         Ok(WolframVersion {
-            major,
-            minor,
-            patch,
+            major: self.app_version.major,
+            minor: self.app_version.minor,
+            patch: self.app_version.revision,
         })
-        */
     }
 
     /// The [`$InstallationDirectory`][ref/$InstallationDirectory] of this Wolfram System
     /// installation.
     ///
     /// [ref/$InstallationDirectory]: https://reference.wolfram.com/language/ref/$InstallationDirectory.html
+    ///
+    /// Operating system | Installation directory
+    /// -----------------|-----------------------
+    /// macOS            | `Contents` directory of the app bundle. For Wolfram Engine, this is inside the embedded `Wolfram Player.app`.
+    /// Windows          | The app directory
+    /// Linux            | The app directory
     pub fn installation_directory(&self) -> PathBuf {
         if let Some(ref player) = self.embedded_player {
             return player.installation_directory();
@@ -1252,9 +3367,11 @@ impl WolframApp {
 
         match OperatingSystem::target_os() {
             OperatingSystem::MacOS => self.app_directory.join("Contents"),
-            OperatingSystem::Windows => self.app_directory.clone(),
-            // FIXME: Fill this in for Linux
-            OperatingSystem::Linux => self.app_directory().clone(),
+            // Wolfram Engine does not contain an embedded Wolfram Player on
+            // Windows or Linux, so this is also true for Engine apps.
+            OperatingSystem::Windows | OperatingSystem::Linux => {
+                self.app_directory.clone()
+            },
             OperatingSystem::Other => {
                 panic!(
                     "{}",
@@ -1272,34 +3389,91 @@ impl WolframApp {
     /// [`WolframKernel`](https://reference.wolfram.com/language/ref/program/WolframKernel.html)
     /// executable.
     pub fn kernel_executable_path(&self) -> Result<PathBuf, Error> {
-        let path = match OperatingSystem::target_os() {
-            OperatingSystem::MacOS => {
-                // TODO: In older versions of the product, MacOSX was used instead of MacOS.
-                //       Look for either, depending on the version number.
-                self.installation_directory()
-                    .join("MacOS")
-                    .join("WolframKernel")
-            },
-            OperatingSystem::Windows => {
-                self.installation_directory().join("WolframKernel.exe")
-            },
-            OperatingSystem::Linux => {
-                // NOTE: This empirically is valid for:
-                //     - Mathematica    (tested: 13.1)
-                //     - Wolfram Engine (tested: 13.0, 13.3 prerelease)
-                // TODO: Is this correct for Wolfram Desktop?
-                self.installation_directory()
-                    .join("Executables")
-                    .join("WolframKernel")
-            },
-            OperatingSystem::Other => {
-                return Err(Error::platform_unsupported("kernel_executable_path()"));
-            },
+        self.resolve(LayoutEntry::KernelExecutable)
+    }
+
+    /// Get all front end launcher executables provided by this app.
+    ///
+    /// Launchers are returned in [`FrontEndRole::variants()`] order. Launchers
+    /// that are not present in this app are omitted.
+    pub fn front_end_executables(&self) -> Result<Vec<FrontEndExecutable>, Error> {
+        let dir = self.front_end_executables_directory()?;
+
+        let executables = FrontEndRole::variants()
+            .into_iter()
+            .map(|role| FrontEndExecutable {
+                role,
+                path: dir.join(role.executable_name()),
+            })
+            .filter(|exe| exe.path.is_file())
+            .collect();
+
+        Ok(executables)
+    }
+
+    /// Returns the location of the front end launcher executable.
+    ///
+    /// If this app provides more than one front end launcher, the first
+    /// launcher present in the preference order is returned. The preference
+    /// order is [`DiscoveryOptions::front_end_preference`], if this app was
+    /// discovered using [`DiscoveryOptions`], and otherwise
+    /// [`FrontEndRole::DEFAULT_PREFERENCE`].
+    pub fn front_end_executable_path(&self) -> Result<PathBuf, Error> {
+        let preference: &[FrontEndRole] = match self.front_end_preference {
+            Some(ref preference) => preference,
+            None => &FrontEndRole::DEFAULT_PREFERENCE,
         };
 
+        let executables = self.front_end_executables()?;
+
+        let preferred = preference
+            .iter()
+            .find_map(|role| executables.iter().find(|exe| exe.role == *role));
+
+        match preferred {
+            Some(exe) => Ok(exe.path.clone()),
+            None => {
+                let dir = self.front_end_executables_directory()?;
+                let role = preference.first().copied().unwrap_or(FrontEndRole::Wolfram);
+
+                Err(Error::unexpected_app_layout(
+                    "front end executable",
+                    self,
+                    dir.join(role.executable_name()),
+                ))
+            },
+        }
+    }
+
+    /// Get the Linux launcher scripts present in the `Executables/` directory
+    /// of this app.
+    ///
+    /// Launchers are returned in [`LinuxLauncher::variants()`] order.
+    ///
+    /// Returns an error if this app is not a Linux installation.
+    pub fn linux_launchers(&self) -> Result<Vec<LinuxLauncher>, Error> {
+        let dir = self.linux_executables_directory()?;
+
+        let launchers = LinuxLauncher::variants()
+            .into_iter()
+            .filter(|launcher| dir.join(launcher.script_name()).is_file())
+            .collect();
+
+        Ok(launchers)
+    }
+
+    /// Returns the location of the specified Linux launcher script.
+    ///
+    /// Returns an error if this app is not a Linux installation, or does not
+    /// provide `launcher`.
+    pub fn linux_launcher_path(&self, launcher: LinuxLauncher) -> Result<PathBuf, Error> {
+        let path = self
+            .linux_executables_directory()?
+            .join(launcher.script_name());
+
         if !path.is_file() {
             return Err(Error::unexpected_app_layout(
-                "WolframKernel executable",
+                "Linux launcher script",
                 self,
                 path,
             ));
@@ -1308,39 +3482,35 @@ impl WolframApp {
         Ok(path)
     }
 
-    /// Returns the location of the
-    /// [`wolframscript`](https://reference.wolfram.com/language/ref/program/wolframscript.html)
+    /// Returns the location of the [Wolfram Player](https://www.wolfram.com/player/)
     /// executable.
-    pub fn wolframscript_executable_path(&self) -> Result<PathBuf, Error> {
+    ///
+    /// This is available for [`WolframAppType::Player`] and
+    /// [`WolframAppType::PlayerPro`] apps, and for [`WolframAppType::Engine`]
+    /// apps that contain an embedded Wolfram Player (macOS only).
+    pub fn player_executable_path(&self) -> Result<PathBuf, Error> {
         if let Some(ref player) = self.embedded_player {
-            return player.wolframscript_executable_path();
-        }
-
-        let path = match OperatingSystem::target_os() {
-            OperatingSystem::MacOS => PathBuf::from("MacOS").join("wolframscript"),
-            OperatingSystem::Windows => PathBuf::from("wolframscript.exe"),
-            OperatingSystem::Linux => {
-                // NOTE: This empirically is valid for:
-                //     - Mathematica    (tested: 13.1)
-                //     - Wolfram Engine (tested: 13.0, 13.3 prerelease)
-                PathBuf::from("SystemFiles")
-                    .join("Kernel")
-                    .join("Binaries")
-                    .join(SystemID::current_rust_target().as_str())
-                    .join("wolframscript")
-            },
-            OperatingSystem::Other => {
-                return Err(Error::platform_unsupported(
-                    "wolframscript_executable_path()",
-                ));
+            return player.player_executable_path();
+        }
+
+        match self.app_type() {
+            WolframAppType::Player | WolframAppType::PlayerPro => (),
+            other => {
+                return Err(Error::other(format!(
+                    "{} app does not provide a Wolfram Player executable",
+                    other.app_name()
+                )))
             },
-        };
+        }
 
-        let path = self.installation_directory().join(&path);
+        let name =
+            artifacts::player_executable_name(SystemID::try_current_rust_target()?)?;
+
+        let path = self.front_end_executables_directory()?.join(name);
 
         if !path.is_file() {
             return Err(Error::unexpected_app_layout(
-                "wolframscript executable",
+                "Wolfram Player executable",
                 self,
                 path,
             ));
@@ -1349,25 +3519,91 @@ impl WolframApp {
         Ok(path)
     }
 
+    /// Returns the location of the
+    /// [`wolframscript`](https://reference.wolfram.com/language/ref/program/wolframscript.html)
+    /// executable.
+    pub fn wolframscript_executable_path(&self) -> Result<PathBuf, Error> {
+        self.resolve(LayoutEntry::WolframScriptExecutable)
+    }
+
+    /// Get the [`SystemID`]s that this app provides Wolfram Language kernel
+    /// binaries for.
+    ///
+    /// This is determined by listing the subdirectories of
+    /// `$InstallationDirectory/SystemFiles/Kernel/Binaries/`. Subdirectories
+    /// whose name is not a known [`SystemID`] are ignored.
+    ///
+    /// This can be used to check whether an app can run natively on the
+    /// current platform (e.g. whether a macOS app provides ARM64 binaries)
+    /// before using it:
+    ///
+    /// ```no_run
+    /// use wolfram_app_discovery::{SystemID, WolframApp};
+    ///
+    /// let app = WolframApp::try_default().unwrap();
+    ///
+    /// let is_native = app
+    ///     .supported_system_ids()
+    ///     .unwrap()
+    ///     .contains(&SystemID::current_rust_target());
+    /// ```
+    pub fn supported_system_ids(&self) -> Result<Vec<SystemID>, Error> {
+        let root = self.resolve(LayoutEntry::KernelBinariesDirectory)?;
+
+        let mut system_ids = Vec::new();
+
+        for entry in std::fs::read_dir(root)? {
+            let entry = entry?;
+
+            if !entry.path().is_dir() {
+                continue;
+            }
+
+            let name = entry.file_name();
+
+            if let Some(Ok(system_id)) = name.to_str().map(SystemID::from_str) {
+                system_ids.push(system_id);
+            }
+        }
+
+        system_ids.sort();
+
+        Ok(system_ids)
+    }
+
+    /// Get the [`SystemID`]s that this app provides Wolfram Language kernel
+    /// binaries for.
+    #[deprecated(note = "use `WolframApp::supported_system_ids()` instead")]
+    pub fn kernel_binaries_system_ids(&self) -> Result<Vec<SystemID>, Error> {
+        self.supported_system_ids()
+    }
+
+    /// Get the [`SystemID`] of this installation.
+    ///
+    /// This is the [`SystemID`] this app provides Wolfram Language kernel
+    /// binaries for. If binaries are provided for more than one [`SystemID`]
+    /// (e.g. a universal macOS installation), the
+    /// [target `SystemID`][WolframApp::target_system_id] is preferred.
+    pub fn system_id(&self) -> Result<SystemID, Error> {
+        let system_ids = self.supported_system_ids()?;
+
+        if let Ok(target) = self.target_system_id() {
+            if system_ids.contains(&target) {
+                return Ok(target);
+            }
+        }
+
+        system_ids.into_iter().next().ok_or_else(|| {
+            Error::undiscoverable("installation SystemID".to_owned(), None)
+        })
+    }
+
     /// Get a list of all [`WstpSdk`]s provided by this app.
     pub fn wstp_sdks(&self) -> Result<Vec<Result<WstpSdk, Error>>, Error> {
-        let root = self
-            .installation_directory()
-            .join("SystemFiles")
-            .join("Links")
-            .join("WSTP")
-            .join("DeveloperKit");
+        let root = self.resolve(LayoutEntry::WstpDeveloperKitDirectory)?;
 
         let mut sdks = Vec::new();
 
-        if !root.is_dir() {
-            return Err(Error::unexpected_app_layout(
-                "WSTP DeveloperKit directory",
-                self,
-                root,
-            ));
-        }
-
         for entry in std::fs::read_dir(root)? {
             let value: Result<WstpSdk, Error> = match entry {
                 Ok(entry) => WstpSdk::try_from_directory(entry.path()),
@@ -1382,15 +3618,19 @@ impl WolframApp {
 
     /// Get the [`WstpSdk`] for the current target platform.
     ///
-    /// This function uses [`SystemID::current_rust_target()`] to determine
+    /// This function uses [`WolframApp::target_system_id()`] to determine
     /// the appropriate entry from [`WolframApp::wstp_sdks()`] to return.
     pub fn target_wstp_sdk(&self) -> Result<WstpSdk, Error> {
+        let target_system_id = self.target_system_id()?;
+
         self.wstp_sdks()?
             .into_iter()
             .flat_map(|sdk| sdk.ok())
-            .find(|sdk| sdk.system_id() == SystemID::current_rust_target())
+            .find(|sdk| sdk.system_id() == target_system_id)
             .ok_or_else(|| {
-                Error::other(format!("unable to locate WSTP SDK for current target"))
+                Error::other(format!(
+                    "unable to locate WSTP SDK for target: {target_system_id}"
+                ))
             })
     }
 
@@ -1407,55 +3647,432 @@ impl WolframApp {
         Ok(self.target_wstp_sdk()?.wstp_c_header_path().to_path_buf())
     }
 
-    /// Returns the location of the
-    /// [WSTP](https://reference.wolfram.com/language/guide/WSTPAPI.html)
-    /// static library.
+    /// Returns the location of the
+    /// [WSTP](https://reference.wolfram.com/language/guide/WSTPAPI.html)
+    /// static library.
+    ///
+    /// *Note: The [wstp](https://crates.io/crates/wstp) crate provides safe Rust bindings
+    /// to WSTP.*
+    #[deprecated(
+        note = "use `WolframApp::target_wstp_sdk()?.wstp_static_library_path()` instead"
+    )]
+    pub fn wstp_static_library_path(&self) -> Result<PathBuf, Error> {
+        Ok(self
+            .target_wstp_sdk()?
+            .wstp_static_library_path()
+            .to_path_buf())
+    }
+
+    /// Returns the location of the directory containing the
+    /// [Wolfram *LibraryLink*](https://reference.wolfram.com/language/guide/LibraryLink.html)
+    /// C header files.
+    ///
+    /// The standard set of *LibraryLink* C header files includes:
+    ///
+    /// * WolframLibrary.h
+    /// * WolframSparseLibrary.h
+    /// * WolframImageLibrary.h
+    /// * WolframNumericArrayLibrary.h
+    ///
+    /// *Note: The [wolfram-library-link](https://crates.io/crates/wolfram-library-link) crate
+    /// provides safe Rust bindings to the Wolfram *LibraryLink* interface.*
+    pub fn library_link_c_includes_directory(&self) -> Result<PathBuf, Error> {
+        self.resolve(LayoutEntry::LibraryLinkIncludesDirectory)
+    }
+
+    /// Returns the location of the CompilerAdditions subdirectory of the legacy
+    /// MathLink SDK for the [target `SystemID`][WolframApp::target_system_id].
+    ///
+    /// MathLink is the predecessor of
+    /// [WSTP](https://reference.wolfram.com/language/guide/WSTPAPI.html). New
+    /// code should use [`WolframApp::target_wstp_sdk()`] instead.
+    pub fn mathlink_compiler_additions_directory(&self) -> Result<PathBuf, Error> {
+        self.resolve(LayoutEntry::MathLinkCompilerAdditionsDirectory)
+    }
+
+    /// Returns the location of the `SystemFiles/Libraries/$SystemID/` directory
+    /// for `system_id`, which contains the Wolfram runtime libraries.
+    ///
+    /// This directory is needed by programs that load the Wolfram runtime
+    /// libraries dynamically, or that add it to the library search path
+    /// (e.g. `LD_LIBRARY_PATH`) of an embedded kernel.
+    ///
+    /// Returns an error if the directory is not present.
+    pub fn libraries_directory(&self, system_id: SystemID) -> Result<PathBuf, Error> {
+        self.with_target_system_id(system_id)
+            .resolve(LayoutEntry::LibrariesDirectory)
+    }
+
+    /// Get the Wolfram *LibraryLink* developer resources of this app for
+    /// `system_id`.
+    ///
+    /// Returns an error if the C includes directory or the
+    /// `SystemFiles/Libraries/$SystemID/` directory is not present.
+    pub fn library_link_sdk(&self, system_id: SystemID) -> Result<LibraryLinkSdk, Error> {
+        let c_includes_directory = self.library_link_c_includes_directory()?;
+
+        let libraries_directory = self.libraries_directory(system_id)?;
+
+        let library_version = read_wolfram_library_h_version(&c_includes_directory);
+
+        Ok(LibraryLinkSdk {
+            system_id,
+            c_includes_directory,
+            libraries_directory,
+            library_version,
+        })
+    }
+
+    /// Get the [`LibraryLinkSdk`] for the current target platform.
+    ///
+    /// This function uses [`WolframApp::target_system_id()`] to determine the
+    /// [`SystemID`] passed to [`WolframApp::library_link_sdk()`].
+    pub fn target_library_link_sdk(&self) -> Result<LibraryLinkSdk, Error> {
+        self.library_link_sdk(self.target_system_id()?)
+    }
+
+    /// Returns the location of the
+    /// [.NET/Link](https://reference.wolfram.com/language/NETLink/tutorial/Overview.html)
+    /// directory, `$InstallationDirectory/SystemFiles/Links/NETLink/`.
+    ///
+    /// Returns an error if the directory does not contain the
+    /// `Wolfram.NETLink.dll` assembly. .NET/Link is typically only provided by
+    /// Windows installations.
+    pub fn netlink_directory(&self) -> Result<PathBuf, Error> {
+        let dir = self.resolve(LayoutEntry::NetLinkDirectory)?;
+
+        let assembly = dir.join("Wolfram.NETLink.dll");
+
+        if !assembly.is_file() {
+            return Err(Error::unexpected_app_layout(
+                ".NET/Link assembly",
+                self,
+                assembly,
+            ));
+        }
+
+        Ok(dir)
+    }
+
+    /// Returns the location of the `Wolfram.NETLink.dll`
+    /// [.NET/Link](https://reference.wolfram.com/language/NETLink/tutorial/Overview.html)
+    /// assembly.
+    pub fn netlink_assembly_path(&self) -> Result<PathBuf, Error> {
+        Ok(self.netlink_directory()?.join("Wolfram.NETLink.dll"))
+    }
+
+    /// Returns the location of the local copy of the Wolfram Language
+    /// documentation provided by this app.
+    ///
+    /// The documentation is expected in the `$InstallationDirectory/Documentation/`
+    /// directory. On Windows, where the documentation can be installed as a
+    /// separate package, the `Wolfram Documentation/<version>/Documentation/`
+    /// directory next to the app is also checked.
+    ///
+    /// Returns an error if no documentation is installed, e.g. for
+    /// [Wolfram Engine][WolframAppType::Engine] apps, which don't include the
+    /// documentation.
+    pub fn documentation_directory(&self) -> Result<PathBuf, Error> {
+        let err = match self.resolve(LayoutEntry::DocumentationDirectory) {
+            Ok(dir) if dir.join("English").is_dir() => return Ok(dir),
+            Ok(dir) => Error::unexpected_app_layout(
+                "documentation directory",
+                self,
+                dir.join("English"),
+            ),
+            Err(err) => err,
+        };
+
+        if OperatingSystem::target_os() == OperatingSystem::Windows {
+            // E.g. `C:\Program Files\Wolfram Research\Wolfram Documentation\14.0\`
+            let package_dir = self
+                .installation_directory()
+                .parent()
+                .and_then(Path::parent)
+                .zip(self.installation_directory().file_name())
+                .map(|(vendor_dir, version)| {
+                    vendor_dir.join("Wolfram Documentation").join(version)
+                });
+
+            if let Some(package_dir) = package_dir {
+                let dir = package_dir.join("Documentation");
+
+                if dir.join("English").is_dir() {
+                    return Ok(dir);
+                }
+            }
+        }
+
+        Err(err)
+    }
+
+    /// Returns the location of the `AddOns/Applications` directory of this
+    /// app, which contains Wolfram Language packages bundled with the
+    /// installation.
+    ///
+    /// See also [`directories::applications_directory()`] and
+    /// [`directories::user_applications_directory()`] for the directories
+    /// containing packages installed by users.
+    pub fn add_ons_applications_directory(&self) -> Result<PathBuf, Error> {
+        self.resolve(LayoutEntry::AddOnsApplicationsDirectory)
+    }
+
+    /// List the paclets bundled with this app.
+    ///
+    /// Paclets are found in the `SystemFiles/Paclets` and `SystemFiles/Links`
+    /// directories of the installation. Paclets are sorted by name, and then by
+    /// version.
+    ///
+    /// This can be used to check whether an installation bundles a particular
+    /// component:
+    ///
+    /// ```no_run
+    /// use wolfram_app_discovery::WolframApp;
+    ///
+    /// let app = WolframApp::try_default().unwrap();
+    ///
+    /// let has_jlink = app
+    ///     .layout_paclets()
+    ///     .unwrap()
+    ///     .iter()
+    ///     .any(|paclet| paclet.name() == "JLink");
+    /// ```
+    pub fn layout_paclets(&self) -> Result<Vec<paclet::Paclet>, Error> {
+        let mut paclets = Vec::new();
+
+        for entry in [LayoutEntry::PacletsDirectory, LayoutEntry::LinksDirectory] {
+            let dir = self
+                .installation_directory()
+                .join(self.relative_path(entry)?);
+
+            if dir.is_dir() {
+                paclets.extend(paclet::paclets_in_directory(&dir)?);
+            }
+        }
+
+        paclets.sort_by(|a, b| (a.name(), a.version()).cmp(&(b.name(), b.version())));
+
+        Ok(paclets)
+    }
+
+    /// Whether a local copy of the Wolfram Language documentation is installed
+    /// for this app.
+    ///
+    /// See [`WolframApp::documentation_directory()`].
+    pub fn is_documentation_installed(&self) -> bool {
+        self.documentation_directory().is_ok()
+    }
+
+    //----------------------------------
+    // Licensing
+    //----------------------------------
+
+    /// Check whether this app appears to be activated.
+    ///
+    /// An app is considered activated if a license server is configured using
+    /// the `WOLFRAM_LICENSE_SERVER` environment variable, or if a password or
+    /// license server entry is present in a `mathpass` file that applies to
+    /// this app: the one in `$InstallationDirectory/Configuration/Licensing`,
+    /// or the one in the user's `Licensing` directory for this type of app
+    /// (e.g. `~/.WolframEngine/Licensing` for Wolfram Engine on Linux).
+    ///
+    /// This is a quick check that does not launch a Wolfram kernel, and so it
+    /// cannot detect expired or otherwise invalid licenses. Use
+    /// `WolframApp::check_activation()`, available with the
+    /// `kernel-properties` feature, to verify that the kernel actually starts.
+    ///
+    /// A freshly installed Wolfram Engine is not activated. Programs can use
+    /// this function to tell the user to activate it, instead of failing when
+    /// the kernel exits unexpectedly.
+    pub fn is_activated(&self) -> bool {
+        license::is_activated(self)
+    }
+
+    /// Verify that the kernel of this app can start, by launching it and
+    /// querying its [properties][WolframApp::kernel_properties].
+    ///
+    /// An unactivated kernel exits without evaluating any code, so this
+    /// returns an error that suggests activating the app. The kernel is
+    /// launched without an interactive prompt, and so never waits for the
+    /// user to enter activation details.
+    ///
+    /// *This function is only available if the `kernel-properties` feature is
+    /// enabled.*
+    #[cfg(feature = "kernel-properties")]
+    pub fn check_activation(&self, timeout: Option<Duration>) -> Result<(), Error> {
+        match self.kernel_properties(timeout) {
+            Ok(_) => Ok(()),
+            Err(err) => Err(Error::other(format!(
+                "Wolfram kernel failed to start; {} may not be activated \
+                (activate it by running `wolframscript -activate`): {err}",
+                self.app_type().app_name()
+            ))),
+        }
+    }
+
+    //----------------------------------
+    // Commands
+    //----------------------------------
+
+    /// Construct a [`Command`][process::Command] that launches the
+    /// [`WolframKernel`](https://reference.wolfram.com/language/ref/program/WolframKernel.html)
+    /// executable of this app.
+    ///
+    /// The returned command has its working directory set to the
+    /// [installation directory][WolframApp::installation_directory], and the
+    /// platform dynamic library search path (`LD_LIBRARY_PATH` on Linux,
+    /// `DYLD_LIBRARY_PATH` on macOS, and `PATH` on Windows) prefixed with the
+    /// Wolfram runtime [libraries directory][WolframApp::libraries_directory],
+    /// if present.
+    ///
+    /// No arguments are added. Callers can add arguments like `-wstp` as
+    /// needed before spawning the command.
+    pub fn kernel_command(&self) -> Result<process::Command, Error> {
+        let mut command = process::Command::new(self.kernel_executable_path()?);
+
+        command.current_dir(self.installation_directory());
+
+        if let Some((key, value)) = self.library_path_environment() {
+            command.env(key, value);
+        }
+
+        Ok(command)
+    }
+
+    /// Construct a [`Command`][process::Command] that launches the
+    /// [`wolframscript`](https://reference.wolfram.com/language/ref/program/wolframscript.html)
+    /// executable of this app.
+    ///
+    /// If this app provides a kernel executable, the command is passed
+    /// `-local <kernel>`, so that `wolframscript` evaluates code using this
+    /// app's kernel, instead of the kernel `wolframscript` would otherwise
+    /// choose.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use wolfram_app_discovery::WolframApp;
+    ///
+    /// let app = WolframApp::try_default().unwrap();
+    ///
+    /// let output = app
+    ///     .wolframscript_command()
+    ///     .unwrap()
+    ///     .args(["-code", "$Version"])
+    ///     .output()
+    ///     .unwrap();
+    /// ```
+    pub fn wolframscript_command(&self) -> Result<process::Command, Error> {
+        let mut command = process::Command::new(self.wolframscript_executable_path()?);
+
+        if let Ok(kernel) = self.kernel_executable_path() {
+            command.arg("-local").arg(kernel);
+        }
+
+        Ok(command)
+    }
+
+    /// Evaluate `expr` using the [`wolframscript`][WolframApp::wolframscript_command]
+    /// executable of this app, and return the printed result.
+    ///
+    /// Trailing newlines are removed from the returned output.
+    ///
+    /// If `timeout` is specified and the evaluation does not finish within
+    /// that duration, the `wolframscript` process is killed and an error is
+    /// returned. An error is also returned if `wolframscript` exits with a
+    /// non-success status, or if its output is not valid UTF-8.
+    ///
+    /// *This function is only available if the `evaluate` feature is enabled.*
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    /// use wolfram_app_discovery::WolframApp;
+    ///
+    /// let app = WolframApp::try_default().unwrap();
+    ///
+    /// let version = app
+    ///     .evaluate("$VersionNumber", Some(Duration::from_secs(60)))
+    ///     .unwrap();
+    /// ```
+    #[cfg(feature = "evaluate")]
+    pub fn evaluate(
+        &self,
+        expr: &str,
+        timeout: Option<Duration>,
+    ) -> Result<String, Error> {
+        let mut command = self.wolframscript_command()?;
+        command.arg("-code").arg(expr);
+
+        let output = match command_output(command, timeout) {
+            Ok(output) => output,
+            Err(err) if err.kind() == std::io::ErrorKind::TimedOut => {
+                return Err(Error::other(format!("evaluation timed out: {err}")));
+            },
+            Err(err) => {
+                return Err(Error::other(format!(
+                    "unable to execute wolframscript command: {err}"
+                )));
+            },
+        };
+
+        // NOTE: See the note in `wolframscript_invocation()` on why exit
+        //       code 3 is allowed.
+        if !output.status.success() && output.status.code() != Some(3) {
+            return Err(Error::other(format!(
+                "wolframscript exited with non-success status code: {}: {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        let stdout = String::from_utf8(output.stdout).map_err(|err| {
+            Error::other(format!("wolframscript output is not valid UTF-8: {err}"))
+        })?;
+
+        Ok(stdout.trim_end_matches(['\r', '\n']).to_owned())
+    }
+
+    /// Async version of [`WolframApp::evaluate()`].
     ///
-    /// *Note: The [wstp](https://crates.io/crates/wstp) crate provides safe Rust bindings
-    /// to WSTP.*
-    #[deprecated(
-        note = "use `WolframApp::target_wstp_sdk()?.wstp_static_library_path()` instead"
-    )]
-    pub fn wstp_static_library_path(&self) -> Result<PathBuf, Error> {
-        Ok(self
-            .target_wstp_sdk()?
-            .wstp_static_library_path()
-            .to_path_buf())
+    /// `wolframscript` is run on a background thread, so awaiting the returned
+    /// future does not block the current thread.
+    ///
+    /// *This function is only available if the `async` and `evaluate` features
+    /// are enabled.*
+    #[cfg(all(feature = "async", feature = "evaluate"))]
+    pub async fn evaluate_async(
+        &self,
+        expr: &str,
+        timeout: Option<Duration>,
+    ) -> Result<String, Error> {
+        let app = self.clone();
+        let expr = expr.to_owned();
+
+        blocking::spawn(move || app.evaluate(&expr, timeout)).await
     }
 
-    /// Returns the location of the directory containing the
-    /// [Wolfram *LibraryLink*](https://reference.wolfram.com/language/guide/LibraryLink.html)
-    /// C header files.
+    /// Launch the kernel of this app and query its
+    /// [properties][kernel::KernelProperties], like `$Version` and
+    /// `$LicenseID`.
     ///
-    /// The standard set of *LibraryLink* C header files includes:
+    /// The kernel is launched directly using
+    /// [`WolframApp::kernel_command()`], and writes the queried values to a
+    /// temporary file. Unlike parsing the standard output of `wolframscript`,
+    /// this is not affected by kernel startup messages or localized output.
     ///
-    /// * WolframLibrary.h
-    /// * WolframSparseLibrary.h
-    /// * WolframImageLibrary.h
-    /// * WolframNumericArrayLibrary.h
+    /// If `timeout` is specified and the kernel does not exit within that
+    /// duration, the kernel process is killed and an error is returned.
     ///
-    /// *Note: The [wolfram-library-link](https://crates.io/crates/wolfram-library-link) crate
-    /// provides safe Rust bindings to the Wolfram *LibraryLink* interface.*
-    pub fn library_link_c_includes_directory(&self) -> Result<PathBuf, Error> {
-        if let Some(ref player) = self.embedded_player {
-            return player.library_link_c_includes_directory();
-        }
-
-        let path = self
-            .installation_directory()
-            .join("SystemFiles")
-            .join("IncludeFiles")
-            .join("C");
-
-        if !path.is_dir() {
-            return Err(Error::unexpected_app_layout(
-                "LibraryLink C header includes directory",
-                self,
-                path,
-            ));
-        }
-
-        Ok(path)
+    /// *This function is only available if the `kernel-properties` feature is
+    /// enabled.*
+    #[cfg(feature = "kernel-properties")]
+    pub fn kernel_properties(
+        &self,
+        timeout: Option<Duration>,
+    ) -> Result<kernel::KernelProperties, Error> {
+        kernel::KernelProperties::query(self, timeout)
     }
 
     //----------------------------------
@@ -1479,12 +4096,13 @@ impl WolframApp {
         // First, sort by Wolfram Language version.
         //
 
-        let version_order = match (a.wolfram_version().ok(), b.wolfram_version().ok()) {
-            (Some(a), Some(b)) => a.cmp(&b),
-            (Some(_), None) => Ordering::Greater,
-            (None, Some(_)) => Ordering::Less,
-            (None, None) => Ordering::Equal,
-        };
+        let version_order =
+            match (a.app_wolfram_version().ok(), b.app_wolfram_version().ok()) {
+                (Some(a), Some(b)) => a.cmp(&b),
+                (Some(_), None) => Ordering::Greater,
+                (None, Some(_)) => Ordering::Less,
+                (None, None) => Ordering::Equal,
+            };
 
         if version_order != Ordering::Equal {
             return version_order;
@@ -1506,11 +4124,23 @@ impl WolframApp {
             return app_type_order;
         }
 
-        debug_assert_eq!(a.wolfram_version().ok(), b.wolfram_version().ok());
+        debug_assert_eq!(a.app_wolfram_version().ok(), b.app_wolfram_version().ok());
         debug_assert_eq!(a.app_type().ordering_value(), b.app_type().ordering_value());
 
+        //
+        // Then, prefer released builds over prototype and nightly builds.
+        //
+
+        // Note: The best app is ranked last, so a released build must compare
+        //       greater than a prerelease build.
+        let release_order = b.is_prerelease().cmp(&a.is_prerelease());
+
+        if release_order != Ordering::Equal {
+            return release_order;
+        }
+
         // TODO: Are there any other metrics by which we could sort this apps?
-        //       Installation location? Released build vs Prototype/nightly?
+        //       Installation location?
         Ordering::Equal
     }
 
@@ -1540,86 +4170,238 @@ impl WolframApp {
 
         Ok(path)
     }
-
-    #[allow(dead_code)]
-    fn wolframscript_output(&self, input: &str) -> Result<String, Error> {
-        let mut args = vec!["-code".to_owned(), input.to_owned()];
-
-        args.push("-local".to_owned());
-        args.push(self.kernel_executable_path().unwrap().display().to_string());
-
-        wolframscript_output(&self.wolframscript_executable_path()?, &args)
-    }
 }
 
 //----------------------------------
 // Utilities
 //----------------------------------
 
+/// Record a [`DiscoveryWarning::UnimplementedOnPlatform`] warning, and print it
+/// to stderr.
 pub(crate) fn print_platform_unimplemented_warning(op: &str) {
-    eprintln!(
-        "warning: operation '{}' is not yet implemented on this platform",
-        op
-    )
+    let warning = record_platform_unimplemented_warning(op);
+
+    self::warning(&warning.to_string())
+}
+
+/// Record a [`DiscoveryWarning::UnimplementedOnPlatform`] warning without
+/// printing it.
+///
+/// This is used for capabilities that are known to be missing on a platform,
+/// where printing a warning on every discovery would be noise.
+pub(crate) fn record_platform_unimplemented_warning(op: &str) -> DiscoveryWarning {
+    let warning = DiscoveryWarning::UnimplementedOnPlatform {
+        operation: op.to_owned(),
+        os: OperatingSystem::target_os().name(),
+    };
+
+    record_warning(warning)
+}
+
+pub(crate) fn print_scan_truncated_warning(root: &Path, limit: ScanLimit) {
+    let warning = record_warning(DiscoveryWarning::ScanTruncated {
+        root: root.to_path_buf(),
+        limit,
+    });
+
+    self::warning(&warning.to_string())
+}
+
+fn record_warning(warning: DiscoveryWarning) -> DiscoveryWarning {
+    let mut warnings = DISCOVERY_WARNINGS
+        .lock()
+        .unwrap_or_else(|err| err.into_inner());
+
+    if !warnings.contains(&warning) {
+        warnings.push(warning.clone());
+    }
+
+    warning
 }
 
-#[cfg_attr(target_os = "windows", allow(dead_code))]
 fn warning(message: &str) {
-    eprintln!("warning: {}", message)
+    warning_sink(&format!("warning: {}", message))
+}
+
+/// Emit a diagnostic message.
+///
+/// All diagnostic output from this library is routed through this function.
+/// If a [`log`] logger that accepts warnings has been installed, the message is
+/// logged to it. Otherwise, the message is written to stderr (never stdout, so
+/// that it cannot be misinterpreted as a `cargo:` directive when this library
+/// is used from a build script).
+pub(crate) fn warning_sink(message: &str) {
+    if log::log_enabled!(log::Level::Warn) {
+        log::warn!("{message}");
+    } else {
+        eprintln!("{message}");
+    }
+}
+
+/// Run `command` to completion and collect its output.
+///
+/// If `timeout` is specified and `command` has not exited after that duration,
+/// it is killed and an error of kind [`std::io::ErrorKind::TimedOut`] is
+/// returned.
+pub(crate) fn command_output(
+    mut command: process::Command,
+    timeout: Option<Duration>,
+) -> Result<process::Output, std::io::Error> {
+    let Some(timeout) = timeout else {
+        return command.output();
+    };
+
+    let mut child = command
+        .stdin(process::Stdio::null())
+        .stdout(process::Stdio::piped())
+        .stderr(process::Stdio::piped())
+        .spawn()?;
+
+    // Read the output pipes on separate threads, so that a child process that
+    // writes a lot of output can't block on a full pipe while we wait on it.
+    fn read_pipe(
+        pipe: Option<impl Read + Send + 'static>,
+    ) -> std::thread::JoinHandle<Vec<u8>> {
+        std::thread::spawn(move || {
+            let mut buffer = Vec::new();
+            if let Some(mut pipe) = pipe {
+                let _ = pipe.read_to_end(&mut buffer);
+            }
+            buffer
+        })
+    }
+
+    let stdout = read_pipe(child.stdout.take());
+    let stderr = read_pipe(child.stderr.take());
+
+    let deadline = Instant::now() + timeout;
+
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                format!(
+                    "command {:?} did not exit within {timeout:?}",
+                    command.get_program()
+                ),
+            ));
+        }
+
+        std::thread::sleep(Duration::from_millis(10));
+    };
+
+    Ok(process::Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    })
 }
 
-fn wolframscript_output(
+/// Run `wolframscript` with the specified arguments, and record the command
+/// line, duration, and first line of output.
+fn wolframscript_invocation(
     wolframscript_command: &PathBuf,
     args: &[String],
-) -> Result<String, Error> {
-    let output: process::Output = process::Command::new(wolframscript_command)
-        .args(args)
-        .output()
-        .expect("unable to execute wolframscript command");
+    timeout: Option<Duration>,
+) -> Result<WolframScriptInvocation, Error> {
+    let mut command = process::Command::new(wolframscript_command);
+    command.args(args);
+
+    let start = Instant::now();
+
+    let output: process::Output = match command_output(command, timeout) {
+        Ok(output) => output,
+        Err(err) if err.kind() == std::io::ErrorKind::TimedOut => {
+            return Err(Error::other(format!("wolframscript timed out: {err}")));
+        },
+        Err(err) => {
+            return Err(Error::other(format!(
+                "unable to execute wolframscript command: {err}"
+            )));
+        },
+    };
 
     // NOTE: The purpose of the 2nd clause here checking for exit code 3 is to work around
     //       a mis-feature of wolframscript to return the same exit code as the Kernel.
     // TODO: Fix the bug in wolframscript which makes this necessary and remove the check
     //       for `3`.
     if !output.status.success() && output.status.code() != Some(3) {
-        panic!(
+        return Err(Error::other(format!(
             "wolframscript exited with non-success status code: {}",
             output.status
-        );
+        )));
     }
 
     let stdout = match String::from_utf8(output.stdout.clone()) {
         Ok(s) => s,
         Err(err) => {
-            panic!(
+            return Err(Error::other(format!(
                 "wolframscript output is not valid UTF-8: {}: {}",
                 err,
                 String::from_utf8_lossy(&output.stdout)
-            );
+            )));
         },
     };
 
     let first_line = stdout
         .lines()
         .next()
-        .expect("wolframscript output was empty");
+        .ok_or_else(|| Error::other("wolframscript output was empty".to_owned()))?;
+
+    let command_line = std::iter::once(wolframscript_command.display().to_string())
+        .chain(args.iter().cloned())
+        .collect();
 
-    Ok(first_line.to_owned())
+    Ok(WolframScriptInvocation {
+        command_line,
+        duration: start.elapsed(),
+        output: first_line.to_owned(),
+    })
 }
 
 /// If `wolframscript` is available on the users PATH, use it to evaluate
 /// `$InstallationDirectory` to locate the default Wolfram Language installation.
 ///
-/// If `wolframscript` is not on PATH, return `Ok(None)`.
-fn try_wolframscript_installation_directory() -> Result<Option<PathBuf>, Error> {
+/// The returned [`WolframScriptInvocation`] records the evaluation; its output
+/// is the installation directory.
+///
+/// If `wolframscript` is not on PATH, or `options` does not allow spawning
+/// subprocesses, return `Ok(None)`.
+fn try_wolframscript_installation_directory(
+    options: &DiscoveryOptions,
+) -> Result<Option<WolframScriptInvocation>, Error> {
     use std::process::Command;
 
+    if options.offline || options.skip_wolframscript {
+        return Ok(None);
+    }
+
+    if let Some(value) = config::get_env_var(WOLFRAM_APP_DISCOVERY_DISABLE_WOLFRAMSCRIPT)
+    {
+        if value == "1" || value.eq_ignore_ascii_case("true") {
+            return Ok(None);
+        }
+    }
+
     // Use `wolframscript` if it's on PATH.
-    let wolframscript = PathBuf::from("wolframscript");
+    let wolframscript = match discovery::resolve_on_path(WolframTool::WolframScript) {
+        Some(path) => path,
+        None => return Ok(None),
+    };
+
+    let mut help_command = Command::new(&wolframscript);
+    help_command.args(["-h"]);
 
     // Run `wolframscript -h` to test whether `wolframscript` exists. `-h` because it
     // should never fail, never block, and only ever print to stdout.
-    if let Err(err) = Command::new(&wolframscript).args(&["-h"]).output() {
+    if let Err(err) = command_output(help_command, options.timeout) {
         if err.kind() == std::io::ErrorKind::NotFound {
             // wolframscript executable is not available on PATH
             return Ok(None);
@@ -1631,17 +4413,153 @@ fn try_wolframscript_installation_directory() -> Result<Option<PathBuf>, Error>
         }
     };
 
-    // FIXME: Check if `wolframscript` is on the PATH first. If it isn't, we should
-    //        give a nicer error message.
-    let location = wolframscript_output(
+    let invocation = wolframscript_invocation(
         &wolframscript,
         &["-code".to_owned(), "$InstallationDirectory".to_owned()],
+        options.timeout,
     )?;
 
-    Ok(Some(PathBuf::from(location)))
+    Ok(Some(invocation))
 }
 
 impl WolframApp {
+    /// Record the settings from `options` that affect how resources within this
+    /// app are resolved.
+    fn apply_options(&mut self, options: &DiscoveryOptions) {
+        let DiscoveryOptions {
+            target_system_id,
+            parallel: _,
+            timeout,
+            offline,
+            skip_wolframscript: _,
+            front_end_preference,
+            sort_strategy: _,
+            max_scan_depth: _,
+            max_scan_entries: _,
+            use_cache: _,
+        } = options;
+
+        self.target_system_id = *target_system_id;
+        self.front_end_preference = front_end_preference.clone();
+        self.timeout = *timeout;
+        self.offline = *offline;
+
+        if let Some(ref mut player) = self.embedded_player {
+            player.apply_options(options);
+        }
+    }
+
+    /// Placeholder for an operating system registration of an app of type
+    /// `app_type` whose application directory no longer exists.
+    ///
+    /// Because the app can't be read, its version is unknown (`0.0.0`). Used
+    /// to report [orphaned registrations][RegistrationReport::orphaned].
+    #[cfg_attr(
+        not(any(
+            test,
+            all(target_os = "macos", feature = "discovery-macos-launchservices"),
+            target_os = "windows",
+        )),
+        allow(dead_code)
+    )]
+    pub(crate) fn orphaned(
+        app_type: WolframAppType,
+        app_directory: PathBuf,
+    ) -> WolframApp {
+        WolframApp {
+            app_name: app_type.app_name().to_owned(),
+            app_type,
+            app_version: AppVersion {
+                major: 0,
+                minor: 0,
+                revision: 0,
+                minor_revision: None,
+                build_code: None,
+            },
+            app_directory,
+            app_executable: None,
+            embedded_player: None,
+            windows_caps: None,
+            installation_id: None,
+            target_system_id: None,
+            front_end_preference: None,
+            timeout: None,
+            offline: false,
+            wolframscript_invocation: None,
+            registration: None,
+        }
+    }
+
+    /// Copy of this app that resolves target-specific resources for
+    /// `system_id`, instead of for its current
+    /// [target `SystemID`][WolframApp::target_system_id].
+    pub(crate) fn with_target_system_id(&self, system_id: SystemID) -> WolframApp {
+        let mut app = self.clone();
+
+        app.target_system_id = Some(system_id);
+
+        if let Some(ref mut player) = app.embedded_player {
+            **player = player.with_target_system_id(system_id);
+        }
+
+        app
+    }
+
+    /// Whether this app provides a notebook front end that is present on disk.
+    fn has_front_end(&self) -> bool {
+        if !self.app_type.has_front_end() {
+            return false;
+        }
+
+        // The main executable of apps that have a front end is the front end.
+        let has_app_executable = match self.app_executable {
+            Some(ref path) => path.is_file(),
+            None => false,
+        };
+
+        has_app_executable || self.front_end_executable_path().is_ok()
+    }
+
+    /// Directory containing the front end launcher executables of this app.
+    fn front_end_executables_directory(&self) -> Result<PathBuf, Error> {
+        let relative = self.relative_path(LayoutEntry::FrontEndExecutablesDirectory)?;
+
+        Ok(self.installation_directory().join(relative))
+    }
+
+    fn linux_executables_directory(&self) -> Result<PathBuf, Error> {
+        if OperatingSystem::target_os() != OperatingSystem::Linux {
+            return Err(Error::platform_unsupported("WolframApp::linux_launchers()"));
+        }
+
+        self.front_end_executables_directory()
+    }
+
+    /// Get the dynamic library search path environment variable, with the
+    /// libraries directory of this app prepended to the current value.
+    ///
+    /// Returns `None` if the libraries directory does not exist.
+    fn library_path_environment(&self) -> Option<(&'static str, std::ffi::OsString)> {
+        let var = match OperatingSystem::target_os() {
+            OperatingSystem::Linux => "LD_LIBRARY_PATH",
+            OperatingSystem::MacOS => "DYLD_LIBRARY_PATH",
+            OperatingSystem::Windows => "PATH",
+            OperatingSystem::Other => return None,
+        };
+
+        let libraries = self.libraries_directory(self.system_id().ok()?).ok()?;
+
+        let mut paths = vec![libraries];
+
+        if let Some(current) = std::env::var_os(var) {
+            paths.extend(std::env::split_paths(&current));
+        }
+
+        let value = std::env::join_paths(paths).ok()?;
+
+        Some((var, value))
+    }
+
     /// If `app` represents a Wolfram Engine app, set the `embedded_player` field to be
     /// the WolframApp representation of the embedded Wolfram Player.app that backs WE.
     fn set_engine_embedded_player(mut self) -> Result<Self, Error> {
@@ -1655,14 +4573,13 @@ impl WolframApp {
                 .join("Contents")
                 .join("Resources")
                 .join("Wolfram Player.app"),
-            // Wolfram Engine does not contain an embedded Wolfram Player
-            // on Windows.
+            // Wolfram Engine does not contain an embedded Wolfram Player on
+            // Windows or Linux. Its app directory is the $InstallationDirectory,
+            // with the same layout as other Wolfram apps.
             OperatingSystem::Windows | OperatingSystem::Linux => {
                 return Ok(self);
             },
             OperatingSystem::Other => {
-                // TODO: Does Wolfram Engine on Linux/Windows contain an embedded Wolfram Player,
-                //       or is that only done on macOS?
                 print_platform_unimplemented_warning(
                     "determine Wolfram Engine path to embedded Wolfram Player",
                 );
@@ -1691,13 +4608,44 @@ impl WolframApp {
     }
 }
 
+//======================================
+// Comparison Impls
+//======================================
+
+impl WolframApp {
+    /// The canonical form of [`WolframApp::app_directory()`], used to determine
+    /// whether two `WolframApp` instances refer to the same installation.
+    ///
+    /// If the app directory can't be canonicalized (e.g. because it no longer
+    /// exists), the app directory is returned unchanged.
+    fn canonical_app_directory(&self) -> PathBuf {
+        self.app_directory
+            .canonicalize()
+            .unwrap_or_else(|_| self.app_directory.clone())
+    }
+}
+
+impl PartialEq for WolframApp {
+    fn eq(&self, other: &WolframApp) -> bool {
+        self.canonical_app_directory() == other.canonical_app_directory()
+    }
+}
+
+impl Eq for WolframApp {}
+
+impl Hash for WolframApp {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.canonical_app_directory().hash(state)
+    }
+}
+
 //======================================
 // Conversion Impls
 //======================================
 
 impl From<std::io::Error> for Error {
     fn from(err: std::io::Error) -> Error {
-        Error(ErrorKind::IO(err.to_string()))
+        Error(ErrorKind::IO(IoError(Arc::new(err))))
     }
 }
 
@@ -1766,16 +4714,76 @@ impl Display for ErrorKind {
                 f,
                 "app specified by environment variable '{env_var}' does not match filter: {filter_err}",
             ),
+            ErrorKind::ConfiguredAppDoesNotMatchFilter {
+                config_file,
+                filter_err,
+            } => write!(
+                f,
+                "app specified by '{}' does not match filter: {filter_err}",
+                config_file.display()
+            ),
             ErrorKind::UnsupportedPlatform { operation, target_os } => write!(
                 f,
                 "operation '{operation}' is not yet implemented for this platform: {target_os:?}",
             ),
-            ErrorKind::IO(io_err) => write!(f, "IO error during discovery: {}", io_err),
+            ErrorKind::IO(IoError(io_err)) => {
+                write!(f, "IO error during discovery: {}", io_err)
+            },
             ErrorKind::Other(message) => write!(f, "{message}"),
         }
     }
 }
 
+impl Display for DiscoveryWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DiscoveryWarning::UnimplementedOnPlatform { operation, os } => {
+                write!(
+                    f,
+                    "operation '{operation}' is not yet implemented on this platform ({os})"
+                )
+            },
+            DiscoveryWarning::ScanTruncated { root, limit } => {
+                write!(
+                    f,
+                    "scan of '{}' stopped early: {limit}; some apps may not have been found",
+                    root.display()
+                )
+            },
+        }
+    }
+}
+
+impl Display for RunnerUpReason {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RunnerUpReason::Overridden => write!(
+                f,
+                "default app was specified explicitly or located using wolframscript"
+            ),
+            RunnerUpReason::OlderVersion => write!(f, "version is older"),
+            RunnerUpReason::LacksFrontEnd => write!(f, "does not provide a front end"),
+            RunnerUpReason::RankedLower => write!(f, "ranked lower"),
+            RunnerUpReason::Filtered(message) => {
+                write!(f, "does not match filter: {message}")
+            },
+        }
+    }
+}
+
+impl Display for ScanLimit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ScanLimit::Depth(depth) => {
+                write!(f, "reached maximum scan depth of {depth} directory levels")
+            },
+            ScanLimit::Entries(entries) => {
+                write!(f, "reached maximum of {entries} scanned directory entries")
+            },
+        }
+    }
+}
+
 impl Display for FilterError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -1785,11 +4793,61 @@ impl Display for FilterError {
                     app_type, allowed
                 )
             },
+            FilterError::FilterDoesNotMatchMinVersion {
+                version: Some(version),
+                min_version,
+            } => {
+                write!(
+                    f,
+                    "application version {version} is older than the minimum version {min_version}"
+                )
+            },
+            FilterError::FilterDoesNotMatchMinVersion {
+                version: None,
+                min_version,
+            } => {
+                write!(
+                    f,
+                    "application version could not be determined (minimum version: {min_version})"
+                )
+            },
+            FilterError::FilterDoesNotMatchKernelSystemID {
+                required,
+                available,
+            } => {
+                write!(f,
+                    "application does not provide kernel binaries for SystemID '{}' (available: {:?})",
+                    required, available
+                )
+            },
+            FilterError::FilterRequiresFrontEnd { app_type } => {
+                write!(f,
+                    "application type '{:?}' does not provide a notebook front end, or its front end executable is missing",
+                    app_type
+                )
+            },
+            FilterError::FilterDoesNotMatchWstpSdkSystemID {
+                required,
+                available,
+            } => {
+                write!(f,
+                    "application does not contain a WSTP SDK for SystemID '{}' (available: {:?})",
+                    required, available
+                )
+            },
+            FilterError::FilterRequiresActivation {
+                installation_directory,
+            } => {
+                write!(
+                    f,
+                    "application does not appear to be activated: {}",
+                    installation_directory.display()
+                )
+            },
         }
     }
 }
 
-
 impl Display for WolframVersion {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let WolframVersion {
@@ -1802,6 +4860,62 @@ impl Display for WolframVersion {
     }
 }
 
+impl Display for WolframApp {
+    /// Format the product name, version, and location of this app, e.g.
+    /// `Wolfram Engine 13.3.0 at /usr/local/Wolfram/WolframEngine/13.3`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = self.app_type().app_name();
+        let directory = self.app_directory.display();
+
+        match self.app_wolfram_version() {
+            Ok(version) => write!(f, "{name} {version} at {directory}"),
+            Err(_) => write!(f, "{name} {} at {directory}", self.app_version),
+        }
+    }
+}
+
+impl Display for AppVersion {
+    /// Format this version with as many components as are known, e.g.
+    /// `13.2.0` or `13.2.0.0.5829781`.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let AppVersion {
+            major,
+            minor,
+            revision,
+            minor_revision,
+            build_code,
+        } = *self;
+
+        write!(f, "{}.{}.{}", major, minor, revision)?;
+
+        if let Some(minor_revision) = minor_revision {
+            write!(f, ".{}", minor_revision)?;
+        }
+
+        if let Some(build_code) = build_code {
+            write!(f, ".{}", build_code)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for WolframScriptInvocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let WolframScriptInvocation {
+            command_line,
+            duration,
+            output,
+        } = self;
+
+        write!(
+            f,
+            "`{}` returned '{output}' in {duration:?}",
+            command_line.join(" ")
+        )
+    }
+}
+
 impl Display for SystemID {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.as_str())
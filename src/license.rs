@@ -0,0 +1,711 @@
+//! Discovery of Wolfram license configuration.
+//!
+//! Wolfram applications that use network licensing obtain a license from a
+//! [MathLM](https://reference.wolfram.com/language/tutorial/MathLMOverview.html)
+//! license server. The server to use is typically configured by a line of the
+//! form `!<host>` in a `mathpass` file.
+//!
+//! Use [`license_servers()`] to find the license servers configured on this
+//! computer, and [`LicenseServer::check_reachable()`] to test whether a server
+//! is accepting connections.
+//!
+//! Wolfram applications that are activated locally store a password for the
+//! computer in a `mathpass` file. Use [`mathpass_entries()`] to list these
+//! passwords, and [`license_expiration()`] to find when the installed licenses
+//! expire.
+
+use std::{
+    fmt::{self, Display},
+    net::{TcpStream, ToSocketAddrs},
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::Duration,
+};
+
+use crate::{
+    config::{self, env_vars::WOLFRAM_LICENSE_SERVER},
+    os::OperatingSystem,
+    Error, WolframApp, WolframAppType,
+};
+
+/// Default TCP port that MathLM license servers listen on.
+pub const DEFAULT_MATHLM_PORT: u16 = 16286;
+
+/// A configured MathLM network license server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LicenseServer {
+    host: String,
+    port: u16,
+    source: LicenseServerSource,
+}
+
+/// Where the configuration of a [`LicenseServer`] was found.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum LicenseServerSource {
+    /// Server was specified by an environment variable.
+    Env {
+        /// The environment variable that was read from.
+        ///
+        /// This will be a value from [`crate::config::env_vars`].
+        variable: &'static str,
+    },
+    /// Server was specified by a `!<host>` line in a `mathpass` file.
+    Mathpass(PathBuf),
+}
+
+/// A password entry in a `mathpass` file.
+///
+/// Password entries have the form `<machine> <MathID> <key> <password> ...`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MathpassEntry {
+    machine_name: String,
+    math_id: String,
+    expiration: Option<LicenseDate>,
+    source: PathBuf,
+}
+
+/// A calendar date on which a license expires.
+///
+/// The [`Display`] representation of a date is `YYYY-MM-DD`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LicenseDate {
+    year: u32,
+    month: u32,
+    day: u32,
+}
+
+//======================================
+// API
+//======================================
+
+/// Find the MathLM license servers configured on this computer.
+///
+/// The following locations are searched in order:
+///
+/// 1. The [`WOLFRAM_LICENSE_SERVER`] environment variable.
+/// 2. The `mathpass` files in the user's `Licensing` directories (within
+///    [`$UserBaseDirectory`](https://reference.wolfram.com/language/ref/$UserBaseDirectory.html)).
+/// 3. The `mathpass` files in the system-wide `Licensing` directories (within
+///    [`$BaseDirectory`](https://reference.wolfram.com/language/ref/$BaseDirectory.html)).
+/// 4. If `app` contains a value, the `mathpass` file in the app's
+///    `Configuration/Licensing` directory.
+///
+/// Each Wolfram product uses its own base directory. If `app` contains a
+/// value, only the `Licensing` directories used by that type of app are
+/// searched (e.g. `~/.WolframEngine/Licensing` for Wolfram Engine on Linux).
+/// Otherwise, the directories of every product are searched.
+///
+/// Servers are returned in the order they were found. `mathpass` files that do
+/// not exist or cannot be read are skipped.
+pub fn license_servers(app: Option<&WolframApp>) -> Vec<LicenseServer> {
+    let mut servers = Vec::new();
+
+    if let Some(value) = config::get_env_var(WOLFRAM_LICENSE_SERVER) {
+        let source = LicenseServerSource::Env {
+            variable: WOLFRAM_LICENSE_SERVER,
+        };
+
+        match LicenseServer::parse(&value, source) {
+            Ok(server) => servers.push(server),
+            Err(err) => crate::warning(&format!(
+                "ignoring invalid value of {WOLFRAM_LICENSE_SERVER}: {err}"
+            )),
+        }
+    }
+
+    for mathpass in mathpass_files(app) {
+        if !mathpass.is_file() {
+            continue;
+        }
+
+        match std::fs::read_to_string(&mathpass) {
+            Ok(contents) => servers.extend(parse_mathpass_servers(&contents, &mathpass)),
+            Err(err) => crate::warning(&format!(
+                "unable to read mathpass file '{}': {err}",
+                mathpass.display()
+            )),
+        }
+    }
+
+    servers
+}
+
+impl LicenseServer {
+    /// Host name or IP address of the license server.
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    /// TCP port of the license server.
+    ///
+    /// If no port was specified in the configuration, this is
+    /// [`DEFAULT_MATHLM_PORT`].
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+
+    /// Where the configuration of this license server was found.
+    pub fn source(&self) -> &LicenseServerSource {
+        &self.source
+    }
+
+    /// Check whether this license server is accepting TCP connections.
+    ///
+    /// Each address that the server host name resolves to is tried in turn,
+    /// waiting at most `timeout` for each connection attempt.
+    ///
+    /// This only checks that the server can be reached over the network, not
+    /// that it has licenses available.
+    pub fn check_reachable(&self, timeout: Duration) -> Result<(), Error> {
+        let addrs = (self.host.as_str(), self.port)
+            .to_socket_addrs()
+            .map_err(|err| {
+                Error::other(format!(
+                    "unable to resolve license server address '{self}': {err}"
+                ))
+            })?;
+
+        let mut last_err = None;
+
+        for addr in addrs {
+            match TcpStream::connect_timeout(&addr, timeout) {
+                Ok(_) => return Ok(()),
+                Err(err) => last_err = Some(err),
+            }
+        }
+
+        Err(match last_err {
+            Some(err) => Error::other(format!(
+                "unable to connect to license server '{self}': {err}"
+            )),
+            None => Error::other(format!(
+                "license server address '{self}' did not resolve to any addresses"
+            )),
+        })
+    }
+
+    /// Parse a server specification of the form `host` or `host:port`.
+    ///
+    /// IPv6 addresses may be written as `[addr]:port` or `[addr]`. An
+    /// unbracketed specification containing more than one `:` is treated as a
+    /// bare IPv6 address that uses the default port.
+    fn parse(spec: &str, source: LicenseServerSource) -> Result<Self, Error> {
+        let spec = spec.trim();
+
+        let parse_port = |port: &str| {
+            u16::from_str(port).map_err(|err| {
+                Error::other(format!(
+                    "invalid port in license server specification '{spec}': {err}"
+                ))
+            })
+        };
+
+        let (host, port) = if let Some(rest) = spec.strip_prefix('[') {
+            let (host, rest) = rest.split_once(']').ok_or_else(|| {
+                Error::other(format!(
+                    "missing ']' in license server specification '{spec}'"
+                ))
+            })?;
+
+            let port = if rest.is_empty() {
+                DEFAULT_MATHLM_PORT
+            } else if let Some(port) = rest.strip_prefix(':') {
+                parse_port(port)?
+            } else {
+                return Err(Error::other(format!(
+                    "unexpected characters after ']' in license server specification '{spec}'"
+                )));
+            };
+
+            (host, port)
+        } else {
+            match spec.split_once(':') {
+                Some((host, port)) if !port.contains(':') => (host, parse_port(port)?),
+                _ => (spec, DEFAULT_MATHLM_PORT),
+            }
+        };
+
+        if host.is_empty() {
+            return Err(Error::other(format!(
+                "license server specification has empty host name: '{spec}'"
+            )));
+        }
+
+        Ok(LicenseServer {
+            host: host.to_owned(),
+            port,
+            source,
+        })
+    }
+}
+
+/// Find the password entries in the `mathpass` files on this computer.
+///
+/// The same `mathpass` files as [`license_servers()`] are searched, in the
+/// same order. Files that do not exist or cannot be read are skipped.
+pub fn mathpass_entries(app: Option<&WolframApp>) -> Vec<MathpassEntry> {
+    mathpass_files(app)
+        .into_iter()
+        .filter_map(|mathpass| {
+            let contents = std::fs::read_to_string(&mathpass).ok()?;
+
+            Some(parse_mathpass_entries(&contents, &mathpass))
+        })
+        .flatten()
+        .collect()
+}
+
+/// Get the date on which the earliest expiring license of `app` expires.
+///
+/// Returns `Ok(None)` if `app` is activated with passwords that do not expire.
+/// Returns an error if no `mathpass` password entries could be found, e.g.
+/// because `app` uses a network license server.
+pub fn license_expiration(app: &WolframApp) -> Result<Option<LicenseDate>, Error> {
+    let entries = mathpass_entries(Some(app));
+
+    if entries.is_empty() {
+        return Err(Error::undiscoverable(
+            "mathpass license password entry".to_owned(),
+            None,
+        ));
+    }
+
+    Ok(entries.iter().filter_map(MathpassEntry::expiration).min())
+}
+
+impl MathpassEntry {
+    /// Name of the computer this password was issued for.
+    pub fn machine_name(&self) -> &str {
+        &self.machine_name
+    }
+
+    /// [`$MachineID`](https://reference.wolfram.com/language/ref/$MachineID.html)
+    /// of the computer this password was issued for.
+    pub fn math_id(&self) -> &str {
+        &self.math_id
+    }
+
+    /// Date on which this password expires, if it is time-limited.
+    pub fn expiration(&self) -> Option<LicenseDate> {
+        self.expiration
+    }
+
+    /// Location of the `mathpass` file this entry was read from.
+    pub fn source(&self) -> &PathBuf {
+        &self.source
+    }
+
+    /// Parse a password line of a `mathpass` file.
+    ///
+    /// Time-limited passwords contain `:`-separated fields, the last of which
+    /// is the expiration date in `YYYYMMDD` form.
+    fn parse(line: &str, source: &Path) -> Option<Self> {
+        let line = line.trim();
+
+        if !is_mathpass_entry(line) || line.starts_with('!') {
+            return None;
+        }
+
+        let mut fields = line.split_whitespace();
+
+        let machine_name = fields.next()?.to_owned();
+        let math_id = fields.next()?.to_owned();
+
+        let expiration = fields
+            .filter_map(|field| field.rsplit_once(':'))
+            .find_map(|(_, date)| LicenseDate::parse(date));
+
+        Some(MathpassEntry {
+            machine_name,
+            math_id,
+            expiration,
+            source: source.to_path_buf(),
+        })
+    }
+}
+
+impl LicenseDate {
+    /// Year component of this date.
+    pub fn year(&self) -> u32 {
+        self.year
+    }
+
+    /// Month component of this date, from 1 to 12.
+    pub fn month(&self) -> u32 {
+        self.month
+    }
+
+    /// Day component of this date, from 1 to 31.
+    pub fn day(&self) -> u32 {
+        self.day
+    }
+
+    /// Parse a date of the form `YYYYMMDD`.
+    fn parse(date: &str) -> Option<Self> {
+        if date.len() != 8 || !date.bytes().all(|byte| byte.is_ascii_digit()) {
+            return None;
+        }
+
+        let year = u32::from_str(&date[0..4]).ok()?;
+        let month = u32::from_str(&date[4..6]).ok()?;
+        let day = u32::from_str(&date[6..8]).ok()?;
+
+        if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+            return None;
+        }
+
+        Some(LicenseDate { year, month, day })
+    }
+}
+
+/// Check whether `app` appears to be activated.
+///
+/// An app is considered activated if a license server is configured using the
+/// [`WOLFRAM_LICENSE_SERVER`] environment variable, or if a password or license
+/// server entry is present in either the `mathpass` file in the app's
+/// `Configuration/Licensing` directory, or the `mathpass` file in the user's
+/// `Licensing` directory for that type of app.
+///
+/// Licensing files of other products are not considered, so that e.g. an
+/// activated Mathematica does not cause an unactivated Wolfram Engine to be
+/// reported as activated.
+///
+/// This is a quick heuristic that does not launch a Wolfram kernel, and so it
+/// cannot detect expired or otherwise invalid licenses.
+pub(crate) fn is_activated(app: &WolframApp) -> bool {
+    if config::get_env_var(WOLFRAM_LICENSE_SERVER).is_some() {
+        return true;
+    }
+
+    let mut files = vec![app_mathpass_file(app)];
+
+    files.extend(
+        mathpass_directories(Some(app.app_type()), false)
+            .into_iter()
+            .map(|dir| dir.join("mathpass")),
+    );
+
+    files
+        .into_iter()
+        .any(|mathpass| match std::fs::read_to_string(&mathpass) {
+            Ok(contents) => contents.lines().any(is_mathpass_entry),
+            Err(_) => false,
+        })
+}
+
+//======================================
+// Helpers
+//======================================
+
+/// `mathpass` files that may contain license configuration, in the order
+/// documented by [`license_servers()`].
+fn mathpass_files(app: Option<&WolframApp>) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> =
+        mathpass_directories(app.map(WolframApp::app_type), true)
+            .into_iter()
+            .map(|dir| dir.join("mathpass"))
+            .collect();
+
+    if let Some(app) = app {
+        files.push(app_mathpass_file(app));
+    }
+
+    files
+}
+
+/// The `mathpass` file in the `Configuration/Licensing` directory of `app`.
+fn app_mathpass_file(app: &WolframApp) -> PathBuf {
+    app.installation_directory()
+        .join("Configuration")
+        .join("Licensing")
+        .join("mathpass")
+}
+
+/// Whether `line` of a `mathpass` file is a password (`<machine> <key>
+/// <password> ...`) or license server (`!<host>`) entry.
+fn is_mathpass_entry(line: &str) -> bool {
+    let line = line.trim();
+
+    if line.is_empty() || line.starts_with('#') {
+        return false;
+    }
+
+    line.starts_with('!') || line.split_whitespace().count() >= 3
+}
+
+/// Parse the `!<host>` license server lines from the contents of a `mathpass`
+/// file.
+fn parse_mathpass_servers(contents: &str, path: &Path) -> Vec<LicenseServer> {
+    contents
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix('!'))
+        .filter_map(|spec| {
+            let source = LicenseServerSource::Mathpass(path.to_path_buf());
+
+            match LicenseServer::parse(spec, source) {
+                Ok(server) => Some(server),
+                Err(err) => {
+                    crate::warning(&format!(
+                        "ignoring invalid license server line in '{}': {err}",
+                        path.display()
+                    ));
+                    None
+                },
+            }
+        })
+        .collect()
+}
+
+/// Parse the password lines from the contents of a `mathpass` file.
+fn parse_mathpass_entries(contents: &str, path: &Path) -> Vec<MathpassEntry> {
+    contents
+        .lines()
+        .filter_map(|line| MathpassEntry::parse(line, path))
+        .collect()
+}
+
+/// Directories that may contain a `mathpass` file for apps of type `app_type`,
+/// or for any type of app if `app_type` is `None`.
+///
+/// The user directories are returned first. System-wide directories are only
+/// included if `system` is `true`.
+fn mathpass_directories(app_type: Option<WolframAppType>, system: bool) -> Vec<PathBuf> {
+    let os = OperatingSystem::target_os();
+
+    let env = |var: &str| std::env::var_os(var).map(PathBuf::from);
+
+    let user_root = match os {
+        OperatingSystem::MacOS => env("HOME").map(|home| home.join("Library")),
+        OperatingSystem::Windows => env("APPDATA"),
+        OperatingSystem::Linux => env("HOME"),
+        OperatingSystem::Other => None,
+    };
+
+    let system_root = match os {
+        OperatingSystem::MacOS => Some(PathBuf::from("/Library")),
+        OperatingSystem::Windows => env("PROGRAMDATA"),
+        OperatingSystem::Linux => Some(PathBuf::from("/usr/share")),
+        OperatingSystem::Other => None,
+    };
+
+    let mut dirs = Vec::new();
+
+    for (root, user) in [(user_root, true), (system_root.filter(|_| system), false)] {
+        let Some(root) = root else {
+            continue;
+        };
+
+        for name in product_directory_names(app_type.as_ref()) {
+            dirs.push(licensing_directory(os, &root, name, user));
+        }
+    }
+
+    dirs
+}
+
+/// Names of the base directories used by apps of type `app_type`, or by any
+/// type of app if `app_type` is `None`.
+fn product_directory_names(app_type: Option<&WolframAppType>) -> &'static [&'static str] {
+    match app_type {
+        Some(WolframAppType::Engine) => &["WolframEngine"],
+        Some(_) => &["Wolfram", "Mathematica"],
+        None => &["Wolfram", "Mathematica", "WolframEngine"],
+    }
+}
+
+/// The `Licensing` directory of the base directory `name` within `root`.
+///
+/// If `user` is `true`, `root` is a per-user directory, like `$HOME` on Linux.
+fn licensing_directory(
+    os: OperatingSystem,
+    root: &Path,
+    name: &str,
+    user: bool,
+) -> PathBuf {
+    let name = match (os, user) {
+        (OperatingSystem::Linux, true) => format!(".{name}"),
+        _ => name.to_owned(),
+    };
+
+    root.join(name).join("Licensing")
+}
+
+//======================================
+// Formatting Impls
+//======================================
+
+impl Display for LicenseServer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.host.contains(':') {
+            write!(f, "[{}]:{}", self.host, self.port)
+        } else {
+            write!(f, "{}:{}", self.host, self.port)
+        }
+    }
+}
+
+impl Display for LicenseDate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+//======================================
+// Tests
+//======================================
+
+#[test]
+fn test_parse_mathpass_servers() {
+    let path = Path::new("/Licensing/mathpass");
+
+    let contents = "\
+        !mathlm.example.com\n\
+        my-machine  1234-56789-01234  5678-901-234  1234567  Mathematica\n\
+        !192.0.2.1:17000\n";
+
+    let mathpass = LicenseServerSource::Mathpass(path.to_path_buf());
+
+    assert_eq!(
+        parse_mathpass_servers(contents, path),
+        vec![
+            LicenseServer {
+                host: "mathlm.example.com".to_owned(),
+                port: DEFAULT_MATHLM_PORT,
+                source: mathpass.clone(),
+            },
+            LicenseServer {
+                host: "192.0.2.1".to_owned(),
+                port: 17000,
+                source: mathpass,
+            },
+        ]
+    );
+
+    // Invalid lines are skipped.
+    assert_eq!(parse_mathpass_servers("!host:notaport\n!\n", path), vec![]);
+}
+
+#[test]
+fn test_parse_license_server_ipv6() {
+    let parse = |spec| {
+        let source = LicenseServerSource::Mathpass(PathBuf::from("mathpass"));
+
+        LicenseServer::parse(spec, source)
+            .ok()
+            .map(|server| (server.host, server.port))
+    };
+
+    assert_eq!(
+        parse("fe80::1"),
+        Some(("fe80::1".to_owned(), DEFAULT_MATHLM_PORT))
+    );
+    assert_eq!(
+        parse("[fe80::1]"),
+        Some(("fe80::1".to_owned(), DEFAULT_MATHLM_PORT))
+    );
+    assert_eq!(
+        parse("[fe80::1]:17000"),
+        Some(("fe80::1".to_owned(), 17000))
+    );
+    assert_eq!(
+        parse("192.0.2.1:17000"),
+        Some(("192.0.2.1".to_owned(), 17000))
+    );
+
+    assert!(parse("[fe80::1").is_none());
+    assert!(parse("[fe80::1]17000").is_none());
+    assert!(parse("[fe80::1]:notaport").is_none());
+    assert!(parse("[]:17000").is_none());
+
+    let server = LicenseServer {
+        host: "fe80::1".to_owned(),
+        port: 17000,
+        source: LicenseServerSource::Mathpass(PathBuf::from("mathpass")),
+    };
+    assert_eq!(server.to_string(), "[fe80::1]:17000");
+}
+
+#[test]
+fn test_is_mathpass_entry() {
+    assert!(is_mathpass_entry("!mathlm.example.com"));
+    assert!(is_mathpass_entry(
+        "my-machine  1234-56789-01234  5678-901-234  1234567  Mathematica"
+    ));
+
+    assert!(!is_mathpass_entry(""));
+    assert!(!is_mathpass_entry("   "));
+    assert!(!is_mathpass_entry("# comment"));
+}
+
+#[test]
+fn test_parse_mathpass_entries() {
+    let path = Path::new("/Licensing/mathpass");
+
+    let contents = "\
+        !mathlm.example.com\n\
+        # comment\n\
+        my-machine  1234-56789-01234  5678-901-234  1234567  Mathematica\n\
+        my-machine  1234-56789-01234  4321-987-654:2,0,8,8:80001:20250131\n";
+
+    let entries = parse_mathpass_entries(contents, path);
+
+    assert_eq!(entries.len(), 2);
+
+    assert_eq!(entries[0].machine_name(), "my-machine");
+    assert_eq!(entries[0].math_id(), "1234-56789-01234");
+    assert_eq!(entries[0].expiration(), None);
+    assert_eq!(entries[0].source(), path);
+
+    let expiration = entries[1].expiration().unwrap();
+
+    assert_eq!(
+        (expiration.year(), expiration.month(), expiration.day()),
+        (2025, 1, 31)
+    );
+    assert_eq!(expiration.to_string(), "2025-01-31");
+}
+
+#[test]
+fn test_licensing_directory() {
+    let home = Path::new("/home/user");
+
+    assert_eq!(
+        licensing_directory(OperatingSystem::Linux, home, "WolframEngine", true),
+        Path::new("/home/user/.WolframEngine/Licensing")
+    );
+    assert_eq!(
+        licensing_directory(
+            OperatingSystem::Linux,
+            Path::new("/usr/share"),
+            "WolframEngine",
+            false
+        ),
+        Path::new("/usr/share/WolframEngine/Licensing")
+    );
+    assert_eq!(
+        licensing_directory(
+            OperatingSystem::MacOS,
+            Path::new("/Users/user/Library"),
+            "WolframEngine",
+            true
+        ),
+        Path::new("/Users/user/Library/WolframEngine/Licensing")
+    );
+
+    assert_eq!(
+        product_directory_names(Some(&WolframAppType::Engine)),
+        &["WolframEngine"]
+    );
+    assert!(product_directory_names(None).contains(&"WolframEngine"));
+}
+
+#[test]
+fn test_parse_license_date() {
+    assert!(LicenseDate::parse("20250131").is_some());
+    assert!(LicenseDate::parse("20251301").is_none());
+    assert!(LicenseDate::parse("2025013").is_none());
+    assert!(LicenseDate::parse("80001").is_none());
+
+    assert!(LicenseDate::parse("20240101") < LicenseDate::parse("20250101"));
+}
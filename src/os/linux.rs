@@ -1,103 +1,23 @@
-use std::{
-    fs,
-    path::{Path, PathBuf},
-};
-
-use crate::{AppVersion, Error, WolframApp, WolframAppType};
-
-pub fn discover_all() -> Vec<WolframApp> {
-    match do_discover_all() {
-        Ok(apps) => apps,
-        Err(err) => {
-            crate::warning(&format!("IO error discovering apps: {err}"));
-            Vec::new()
-        },
-    }
-}
+#[cfg(feature = "discovery-linux-scan")]
+mod scan;
 
-fn do_discover_all() -> Result<Vec<WolframApp>, std::io::Error> {
-    // Wolfram apps on Linux are by default installed to a location with the
-    // following structure:
-    //
-    //     /usr/local/Wolfram/<Mathematica|WolframEngine|...>/<MAJOR.MINOR>/
-
-    // TODO(polish): Are there any other root locations that Wolfram products
-    //               are or used to be installed to by default on Linux?
-    #[rustfmt::skip]
-    let roots = [
-        Path::new("/usr/local/Wolfram"),
-        Path::new("/opt/Wolfram"),
-    ];
-
-    let mut apps = Vec::new();
-
-    for apps_dir in roots {
-        match get_apps_in_wolfram_apps_dir(apps_dir, &mut apps) {
-            Ok(()) => (),
-            Err(io_err) => {
-                // Log this error as a warning, and continue looking in
-                // other directories for potentially valid Wolfram apps.
-                crate::warning(&format!(
-                    "error looking for Wolfram apps in '{}': {io_err}",
-                    apps_dir.display()
-                ))
-            },
-        }
-    }
+use std::path::PathBuf;
 
-    Ok(apps)
-}
+use crate::{AppVersion, DiscoveryOptions, Error, WolframApp, WolframAppType};
 
-/// Find Wolfram apps installed into a shared Wolfram "apps directory".
-///
-/// Wolfram apps on Linux are by default installed to a location with the
-/// following structure:
-///
-/// ```text
-/// /usr/local/Wolfram/<Mathematica|WolframEngine|...>/<MAJOR.MINOR>/
-/// ```
-///
-/// where `/usr/local/Wolfram` is an "apps directory" that itself contains
-/// other Wolfram applications, where the application type and version number
-/// is encoded in their location inside the apps directory.
-///
-/// Some concrete examples:
-///
-/// * `/usr/local/Wolfram/Mathematica/13.1/` — the `$InstallationDirectory` for a Mathematica v13.1 app
-/// * `/usr/local/Wolfram/WolframEngine/13.2/` — the `$InstallationDirectory` for a Wolfram Engine v13.2 app
-fn get_apps_in_wolfram_apps_dir(
-    apps_dir: &Path,
-    apps: &mut Vec<WolframApp>,
-) -> Result<(), std::io::Error> {
-    for app_type_dir in fs::read_dir(&apps_dir)? {
-        let app_type_dir = app_type_dir?.path();
-
-        if !app_type_dir.is_dir() {
-            continue;
-        }
-
-        for app_version_dir in fs::read_dir(&app_type_dir)? {
-            let app_version_dir = app_version_dir?.path();
-
-            if !app_version_dir.is_dir() {
-                continue;
-            }
-
-            match from_app_directory(&app_version_dir) {
-                Ok(app) => apps.push(app),
-                Err(err) => {
-                    // Log this error as a warning, but continue looking in
-                    // other directories for potentially valid Wolfram apps.
-                    crate::warning(&format!(
-                        "unable to interpret directory '{}' as Wolfram app: {err}",
-                        app_version_dir.display()
-                    ))
-                },
-            }
-        }
-    }
+pub fn discover_all(
+    options: &DiscoveryOptions,
+    app_types: Option<&[WolframAppType]>,
+) -> Vec<WolframApp> {
+    #[cfg(feature = "discovery-linux-scan")]
+    return scan::discover_all(options, app_types);
+
+    #[allow(unreachable_code)]
+    {
+        let _ = (options, app_types);
 
-    Ok(())
+        Vec::new()
+    }
 }
 
 //======================================
@@ -107,6 +27,9 @@ fn get_apps_in_wolfram_apps_dir(
 pub fn from_app_directory(path: &PathBuf) -> Result<WolframApp, Error> {
     let (app_type, app_version) = parse_app_info_from_files(path)?;
 
+    // TODO: Determine the main executable of Linux apps.
+    crate::record_platform_unimplemented_warning("determine application main executable");
+
     Ok(WolframApp {
         app_name: app_type.app_name().to_owned(),
         app_type,
@@ -117,6 +40,14 @@ pub fn from_app_directory(path: &PathBuf) -> Result<WolframApp, Error> {
         app_executable: None,
 
         embedded_player: None,
+        windows_caps: None,
+        installation_id: None,
+        target_system_id: None,
+        front_end_preference: None,
+        timeout: None,
+        offline: false,
+        wolframscript_invocation: None,
+        registration: None,
     })
 }
 
@@ -147,13 +78,11 @@ fn parse_app_info_from_files(
     // TODO(cleanup): Find a better way of determining the WolframAppType than
     //                parsing LICENSE.txt.
     let app_type = match contents.lines().next() {
-        Some("Wolfram Mathematica License Agreement") => WolframAppType::Mathematica,
-        Some("Wolfram Mathematica® License Agreement") => WolframAppType::Mathematica,
-        Some("Free Wolfram Engine(TM) for Developers: Terms and Conditions of Use") => WolframAppType::Engine,
-        Some("Free Wolfram Engine™ for Developers: Terms and Conditions of Use") => WolframAppType::Engine,
-        Some(other) => return Err(Error::other(format!(
-            "Unable to determine Wolfram app type from LICENSE.txt: first line was: {other:?}"
-        ))),
+        Some(title) => app_type_from_license_title(title).ok_or_else(|| {
+            Error::other(format!(
+                "Unable to determine Wolfram app type from LICENSE.txt: first line was: {title:?}"
+            ))
+        })?,
         None => return Err(Error::other("Unable to determine Wolfram app type from LICENSE.txt: file is empty.".to_owned())),
     };
 
@@ -161,15 +90,24 @@ fn parse_app_info_from_files(
     // Parse the Wolfram version from the WolframKernel launch script
     //
 
-    let wolfram_kernel = app_directory.join("Executables").join("WolframKernel");
+    // Legacy Mathematica versions may only provide the MathKernel or math
+    // launch scripts, which have the same format.
+    let executables = app_directory.join("Executables");
 
-    if !wolfram_kernel.is_file() {
-        return Err(Error::unexpected_app_layout_2(
-            "WolframKernel executable",
-            app_directory.clone(),
-            wolfram_kernel,
-        ));
-    }
+    let wolfram_kernel = match ["WolframKernel", "MathKernel", "math"]
+        .iter()
+        .map(|name| executables.join(name))
+        .find(|path| path.is_file())
+    {
+        Some(path) => path,
+        None => {
+            return Err(Error::unexpected_app_layout_2(
+                "WolframKernel executable",
+                app_directory.clone(),
+                executables.join("WolframKernel"),
+            ))
+        },
+    };
 
     let contents: String = std::fs::read_to_string(&wolfram_kernel).map_err(|err| {
         Error::other(format!("Error reading WolframKernel executable: {err}"))
@@ -214,7 +152,28 @@ fn parse_wolfram_kernel_script_contents(
         _other => return Ok(None),
     };
 
-    let app_version = AppVersion::parse(version_string)?;
+    // Legacy Mathematica versions may omit the revision, e.g. "10.0".
+    let app_version = match version_string.split('.').count() {
+        2 => AppVersion::parse(&format!("{version_string}.0"))?,
+        _ => AppVersion::parse(version_string)?,
+    };
 
     Ok(Some(app_version))
 }
+
+/// Determine the app type from the title on the first line of `LICENSE.txt`.
+///
+/// The exact title varies between versions, e.g.
+/// `Wolfram Mathematica® License Agreement` or
+/// `Free Wolfram Engine™ for Developers: Terms and Conditions of Use`.
+fn app_type_from_license_title(title: &str) -> Option<WolframAppType> {
+    let title = title.trim().to_lowercase();
+
+    if title.contains("wolfram engine") {
+        Some(WolframAppType::Engine)
+    } else if title.contains("mathematica") && title.contains("license agreement") {
+        Some(WolframAppType::Mathematica)
+    } else {
+        None
+    }
+}
@@ -0,0 +1,202 @@
+//! Discovery of Wolfram apps installed into the default Linux install
+//! locations.
+//!
+//! Enabled by the `discovery-linux-scan` crate feature.
+
+use std::{fs, path::Path};
+
+use crate::{os::is_allowed, DiscoveryOptions, ScanLimit, WolframApp, WolframAppType};
+
+use super::from_app_directory;
+
+pub(super) fn discover_all(
+    options: &DiscoveryOptions,
+    app_types: Option<&[WolframAppType]>,
+) -> Vec<WolframApp> {
+    // Wolfram apps on Linux are by default installed to a location with the
+    // following structure:
+    //
+    //     /usr/local/Wolfram/<Mathematica|WolframEngine|...>/<MAJOR.MINOR>/
+
+    // TODO(polish): Are there any other root locations that Wolfram products
+    //               are or used to be installed to by default on Linux?
+    #[rustfmt::skip]
+    let roots = [
+        Path::new("/usr/local/Wolfram"),
+        Path::new("/opt/Wolfram"),
+    ];
+
+    let limits = options.scan_limits();
+
+    let backends = roots.map(|apps_dir| {
+        move || {
+            let mut apps = Vec::new();
+
+            match get_apps_in_wolfram_apps_dir(apps_dir, limits, app_types, &mut apps) {
+                Ok(None) => (),
+                Ok(Some(limit)) => crate::print_scan_truncated_warning(apps_dir, limit),
+                Err(io_err) => {
+                    // Log this error as a warning, and continue looking in
+                    // other directories for potentially valid Wolfram apps.
+                    crate::warning(&format!(
+                        "error looking for Wolfram apps in '{}': {io_err}",
+                        apps_dir.display()
+                    ))
+                },
+            }
+
+            apps
+        }
+    });
+
+    crate::os::run_backends(options.parallel, backends)
+}
+
+/// Find Wolfram apps installed into a shared Wolfram "apps directory".
+///
+/// Wolfram apps on Linux are by default installed to a location with the
+/// following structure:
+///
+/// ```text
+/// /usr/local/Wolfram/<Mathematica|WolframEngine|...>/<MAJOR.MINOR>/
+/// ```
+///
+/// where `/usr/local/Wolfram` is an "apps directory" that itself contains
+/// other Wolfram applications, where the application type and version number
+/// is encoded in their location inside the apps directory.
+///
+/// Some concrete examples:
+///
+/// * `/usr/local/Wolfram/Mathematica/13.1/` — the `$InstallationDirectory` for a Mathematica v13.1 app
+/// * `/usr/local/Wolfram/WolframEngine/13.2/` — the `$InstallationDirectory` for a Wolfram Engine v13.2 app
+///
+/// `limits` is the maximum scan depth and number of directory entries read,
+/// as returned by [`DiscoveryOptions::scan_limits()`]. If the scan stopped
+/// early because of one of these limits, the limit is returned.
+///
+/// If `app_types` is specified, directories of apps of any other type are
+/// skipped without being inspected.
+fn get_apps_in_wolfram_apps_dir(
+    apps_dir: &Path,
+    (max_depth, max_entries): (usize, usize),
+    app_types: Option<&[WolframAppType]>,
+    apps: &mut Vec<WolframApp>,
+) -> Result<Option<ScanLimit>, std::io::Error> {
+    if max_depth == 0 {
+        return Ok(apps_dir.is_dir().then_some(ScanLimit::Depth(max_depth)));
+    }
+
+    let mut entries: usize = 0;
+    let mut truncated = None;
+
+    for app_type_dir in fs::read_dir(apps_dir)? {
+        if entries == max_entries {
+            return Ok(Some(ScanLimit::Entries(max_entries)));
+        }
+        entries += 1;
+
+        let app_type_dir = app_type_dir?.path();
+
+        if !app_type_dir.is_dir() {
+            continue;
+        }
+
+        if let Some(app_type) = app_type_dir
+            .file_name()
+            .and_then(|name| app_type_from_dir_name(name.to_str()?))
+        {
+            if !is_allowed(app_types, &app_type) {
+                continue;
+            }
+        }
+
+        if max_depth < 2 {
+            truncated = Some(ScanLimit::Depth(max_depth));
+            continue;
+        }
+
+        for app_version_dir in fs::read_dir(&app_type_dir)? {
+            if entries == max_entries {
+                return Ok(Some(ScanLimit::Entries(max_entries)));
+            }
+            entries += 1;
+
+            let app_version_dir = app_version_dir?.path();
+
+            if !app_version_dir.is_dir() {
+                continue;
+            }
+
+            match from_app_directory(&app_version_dir) {
+                Ok(app) if !is_allowed(app_types, &app.app_type()) => (),
+                Ok(app) => apps.push(app),
+                Err(err) => {
+                    // Log this error as a warning, but continue looking in
+                    // other directories for potentially valid Wolfram apps.
+                    crate::warning(&format!(
+                        "unable to interpret directory '{}' as Wolfram app: {err}",
+                        app_version_dir.display()
+                    ))
+                },
+            }
+        }
+    }
+
+    Ok(truncated)
+}
+
+/// Get the app type of the apps installed in the `<Mathematica|WolframEngine|...>`
+/// directory named `name`.
+fn app_type_from_dir_name(name: &str) -> Option<WolframAppType> {
+    match name {
+        "Mathematica" => Some(WolframAppType::Mathematica),
+        "WolframEngine" => Some(WolframAppType::Engine),
+        _ => None,
+    }
+}
+
+//======================================
+// Tests
+//======================================
+
+#[test]
+fn test_scan_limits() {
+    let root = crate::test_support::temp_dir("scan-limits");
+
+    for (app_type, version) in [("Mathematica", "13.1.0"), ("Mathematica", "13.2.0")] {
+        let dir = root.join(app_type).join(&version[..4]);
+        fs::create_dir_all(dir.join("Executables")).unwrap();
+        fs::write(
+            dir.join("LICENSE.txt"),
+            "Wolfram Mathematica License Agreement",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("Executables").join("WolframKernel"),
+            format!("#!/bin/sh\n#\n#  Mathematica {version} Kernel command file\n"),
+        )
+        .unwrap();
+    }
+
+    let scan_types = |limits, app_types: Option<&[WolframAppType]>| {
+        let mut apps = Vec::new();
+        let truncated =
+            get_apps_in_wolfram_apps_dir(&root, limits, app_types, &mut apps).unwrap();
+        (apps.len(), truncated)
+    };
+    let scan = |limits| scan_types(limits, None);
+
+    assert_eq!(scan((2, 1000)), (2, None));
+    assert_eq!(scan((1, 1000)), (0, Some(ScanLimit::Depth(1))));
+    assert_eq!(scan((0, 1000)), (0, Some(ScanLimit::Depth(0))));
+    assert_eq!(scan((2, 3)), (2, None));
+    assert_eq!(scan((2, 2)), (1, Some(ScanLimit::Entries(2))));
+
+    // Directories of apps of other types are skipped.
+    let engines = [WolframAppType::Engine];
+    let mathematica = [WolframAppType::Mathematica];
+    assert_eq!(scan_types((2, 1000), Some(&engines[..])), (0, None));
+    assert_eq!(scan_types((2, 1000), Some(&mathematica[..])), (2, None));
+
+    fs::remove_dir_all(&root).unwrap();
+}
@@ -1,21 +1,47 @@
 mod cf_exts;
 
+#[cfg(feature = "discovery-macos-launchservices")]
+mod launch_services;
+
 use std::path::PathBuf;
 
 use core_foundation::{
-    array::{CFArrayGetCount, CFArrayGetValueAtIndex, CFArrayRef},
     base::CFRelease,
-    base::TCFType,
     bundle::{CFBundleCopyExecutableURL, CFBundleCreate, CFBundleRef},
-    error::{CFError, CFErrorRef},
-    string::CFStringRef,
     url::CFURLRef,
 };
 
-use crate::{AppVersion, Error, WolframApp, WolframAppType};
+use crate::{
+    AppVersion, DiscoveryOptions, Error, InstallationId, WolframApp, WolframAppType,
+};
+
+pub fn discover_all(
+    options: &DiscoveryOptions,
+    app_types: Option<&[WolframAppType]>,
+) -> Vec<WolframApp> {
+    #[cfg(feature = "discovery-macos-launchservices")]
+    return launch_services::discover_all(options.parallel, app_types, false);
+
+    #[allow(unreachable_code)]
+    {
+        let _ = (options, app_types);
+
+        Vec::new()
+    }
+}
+
+/// Find every app registered with Launch Services, including registrations
+/// whose app bundle no longer exists.
+pub fn discover_registrations(options: &DiscoveryOptions) -> Vec<WolframApp> {
+    #[cfg(feature = "discovery-macos-launchservices")]
+    return launch_services::discover_all(options.parallel, None, true);
 
-pub fn discover_all() -> Vec<WolframApp> {
-    load_installed_products_from_launch_services()
+    #[allow(unreachable_code)]
+    {
+        let _ = options;
+
+        Vec::new()
+    }
 }
 
 pub fn from_app_directory(path: &PathBuf) -> Result<WolframApp, Error> {
@@ -184,71 +210,14 @@ unsafe fn get_app_from_url(
         app_executable,
         app_version,
         embedded_player: None,
+        windows_caps: None,
+        installation_id: Some(InstallationId::MacOSBundleIdentifier(bundle_id)),
+        target_system_id: None,
+        front_end_preference: None,
+        timeout: None,
+        offline: false,
+        wolframscript_invocation: None,
+        registration: None,
     }
     .set_engine_embedded_player()
 }
-
-fn load_installed_products_from_launch_services() -> Vec<WolframApp> {
-    let mut app_bundles = Vec::new();
-
-    for app_type in WolframAppType::variants() {
-        let bundle_id: CFStringRef = cf_exts::cf_string_from_str(app_type.bundle_id());
-
-        unsafe {
-            let mut err: CFErrorRef = std::ptr::null_mut();
-            let app_urls: CFArrayRef =
-                cf_exts::LSCopyApplicationURLsForBundleIdentifier(bundle_id, &mut err);
-
-            // Assume that if an error occurs, it is kLSApplicationNotFoundErr.
-            // TODO: core_foundation doesn't currently expose
-            //       kLSApplicationNotFoundErr as a constant; if that is added,
-            //       check for it here.
-            if !err.is_null() {
-                // Deallocate the error object.
-                let _err = CFError::wrap_under_create_rule(err);
-
-                /*
-                crate::warning(&format!(
-                    "warning: error searching for '{:?}' application instances",
-                    app_type
-                ));
-                */
-
-                continue;
-            }
-
-            let count: isize = CFArrayGetCount(app_urls);
-
-            for index in 0..count {
-                let url: CFURLRef = CFArrayGetValueAtIndex(app_urls, index) as CFURLRef;
-                if url.is_null() {
-                    // This shouldn't happen, so ignore it.
-                    crate::warning("CFURLRef was unexpectedly NULL");
-                    continue;
-                }
-
-                match get_app_from_url(url, Some(app_type.clone())) {
-                    Ok(app) => app_bundles.push(app),
-                    Err(err) => {
-                        // TODO: Do something else here?
-                        //       We don't want this to be a catastrophic error,
-                        //       because one "corrupted" app installation shouldn't
-                        //       prevent us from returning a list of other valid
-                        //       installations. But we should inform the user of this
-                        //       somehow.
-                        crate::warning(&format!(
-                            "warning: wolfram app had unexpected or invalid\
-                            structure: {}",
-                            err
-                        ))
-                    },
-                }
-            }
-
-            CFRelease(app_urls as *const _);
-            CFRelease(bundle_id as *const _);
-        }
-    }
-
-    app_bundles
-}
@@ -0,0 +1,123 @@
+//! Discovery of Wolfram apps registered with macOS Launch Services.
+//!
+//! Enabled by the `discovery-macos-launchservices` crate feature.
+
+use core_foundation::{
+    array::{CFArrayGetCount, CFArrayGetValueAtIndex, CFArrayRef},
+    base::{CFRelease, TCFType},
+    error::{CFError, CFErrorRef},
+    string::CFStringRef,
+    url::CFURLRef,
+};
+
+use std::path::PathBuf;
+
+use crate::{Registration, WolframApp, WolframAppType};
+
+use super::{cf_exts, get_app_from_url};
+
+/// If `include_orphaned` is true, registrations whose app bundle no longer
+/// exists are returned as [orphaned][WolframApp::orphaned] placeholder apps
+/// instead of being skipped.
+pub(super) fn discover_all(
+    parallel: bool,
+    app_types: Option<&[WolframAppType]>,
+    include_orphaned: bool,
+) -> Vec<WolframApp> {
+    // Each application type is looked up independently, so these lookups can
+    // be performed concurrently. Bundle identifiers of app types that aren't
+    // wanted are never looked up.
+    let backends = WolframAppType::variants()
+        .into_iter()
+        .filter(|app_type| crate::os::is_allowed(app_types, app_type))
+        .map(|app_type| {
+            move || load_installed_products_of_type(app_type, include_orphaned)
+        });
+
+    crate::os::run_backends(parallel, backends)
+}
+
+fn load_installed_products_of_type(
+    app_type: WolframAppType,
+    include_orphaned: bool,
+) -> Vec<WolframApp> {
+    let mut app_bundles = Vec::new();
+
+    let bundle_id: CFStringRef = cf_exts::cf_string_from_str(app_type.bundle_id());
+
+    unsafe {
+        let mut err: CFErrorRef = std::ptr::null_mut();
+        let app_urls: CFArrayRef =
+            cf_exts::LSCopyApplicationURLsForBundleIdentifier(bundle_id, &mut err);
+
+        // Assume that if an error occurs, it is kLSApplicationNotFoundErr.
+        // TODO: core_foundation doesn't currently expose
+        //       kLSApplicationNotFoundErr as a constant; if that is added,
+        //       check for it here.
+        if !err.is_null() {
+            // Deallocate the error object.
+            let _err = CFError::wrap_under_create_rule(err);
+
+            /*
+            crate::warning(&format!(
+                "warning: error searching for '{:?}' application instances",
+                app_type
+            ));
+            */
+
+            CFRelease(bundle_id as *const _);
+
+            return app_bundles;
+        }
+
+        let count: isize = CFArrayGetCount(app_urls);
+
+        for index in 0..count {
+            let url: CFURLRef = CFArrayGetValueAtIndex(app_urls, index) as CFURLRef;
+            if url.is_null() {
+                // This shouldn't happen, so ignore it.
+                crate::warning("CFURLRef was unexpectedly NULL");
+                continue;
+            }
+
+            match get_app_from_url(url, Some(app_type.clone())) {
+                Ok(mut app) => {
+                    app.registration =
+                        Some(Registration::LaunchServices(app.app_directory()));
+                    app_bundles.push(app)
+                },
+                Err(_) if include_orphaned && !url_directory(url).is_dir() => {
+                    // The registered app bundle no longer exists.
+                    let app_directory = url_directory(url);
+                    let mut app = WolframApp::orphaned(app_type.clone(), app_directory);
+                    app.registration =
+                        Some(Registration::LaunchServices(app.app_directory()));
+                    app_bundles.push(app)
+                },
+                Err(err) => {
+                    // TODO: Do something else here?
+                    //       We don't want this to be a catastrophic error,
+                    //       because one "corrupted" app installation shouldn't
+                    //       prevent us from returning a list of other valid
+                    //       installations. But we should inform the user of this
+                    //       somehow.
+                    crate::warning(&format!(
+                        "warning: wolfram app had unexpected or invalid\
+                        structure: {}",
+                        err
+                    ))
+                },
+            }
+        }
+
+        CFRelease(app_urls as *const _);
+        CFRelease(bundle_id as *const _);
+    }
+
+    app_bundles
+}
+
+/// File system path of the app bundle referred to by `url`.
+unsafe fn url_directory(url: CFURLRef) -> PathBuf {
+    PathBuf::from(cf_exts::url_file_system_path(url))
+}
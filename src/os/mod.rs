@@ -10,20 +10,30 @@ pub mod linux;
 
 use std::path::PathBuf;
 
-use crate::{Error, WolframApp};
+use crate::{DiscoveryOptions, Error, WolframApp, WolframAppType};
 
-pub fn discover_all() -> Vec<WolframApp> {
+/// Discover installed Wolfram applications.
+///
+/// If `app_types` is specified, discovery backends may skip apps of any other
+/// type before doing the work to fully load them. Apps of other types may still
+/// be returned.
+pub fn discover_all(
+    options: &DiscoveryOptions,
+    app_types: Option<&[WolframAppType]>,
+) -> Vec<WolframApp> {
     #[cfg(target_os = "macos")]
-    return macos::discover_all();
+    return macos::discover_all(options, app_types);
 
     #[cfg(target_os = "windows")]
-    return windows::discover_all();
+    return windows::discover_all(options, app_types);
 
     #[cfg(target_os = "linux")]
-    return linux::discover_all();
+    return linux::discover_all(options, app_types);
 
     #[allow(unreachable_code)]
     {
+        let _ = (options, app_types);
+
         crate::print_platform_unimplemented_warning(
             "discover all installed Wolfram applications",
         );
@@ -32,6 +42,25 @@ pub fn discover_all() -> Vec<WolframApp> {
     }
 }
 
+/// Find every Wolfram app registration recorded by the operating system.
+///
+/// Unlike [`discover_all()`], registrations whose application directory no
+/// longer exists are not skipped. They are returned as placeholder apps (see
+/// [`WolframApp::orphaned()`]), so that they can be reported and removed.
+///
+/// On platforms where apps are not registered with the operating system, this
+/// is the same as [`discover_all()`].
+pub fn discover_registrations(options: &DiscoveryOptions) -> Vec<WolframApp> {
+    #[cfg(target_os = "macos")]
+    return macos::discover_registrations(options);
+
+    #[cfg(target_os = "windows")]
+    return windows::discover_registrations(options);
+
+    #[allow(unreachable_code)]
+    discover_all(options, None)
+}
+
 pub fn from_app_directory(dir: &PathBuf) -> Result<WolframApp, Error> {
     #[cfg(target_os = "macos")]
     return macos::from_app_directory(dir);
@@ -52,7 +81,67 @@ pub fn from_app_directory(dir: &PathBuf) -> Result<WolframApp, Error> {
 // Utilities
 //======================================
 
-/// Operating systems supported by supported by `wolfram-app-discovery`.
+/// Run each of the independent discovery `backends`, and concatenate the apps
+/// they discover.
+///
+/// If `parallel` is true, each backend is run on its own scoped thread.
+/// Otherwise, backends are run sequentially on the current thread. In either
+/// case, the returned apps are in the same order as `backends`.
+#[cfg_attr(
+    not(any(
+        all(target_os = "macos", feature = "discovery-macos-launchservices"),
+        target_os = "windows",
+        all(target_os = "linux", feature = "discovery-linux-scan"),
+    )),
+    allow(dead_code)
+)]
+pub(crate) fn run_backends<I, F>(parallel: bool, backends: I) -> Vec<WolframApp>
+where
+    I: IntoIterator<Item = F>,
+    F: FnOnce() -> Vec<WolframApp> + Send,
+{
+    if !parallel {
+        return backends.into_iter().flat_map(|backend| backend()).collect();
+    }
+
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = backends
+            .into_iter()
+            .map(|backend| scope.spawn(backend))
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| match handle.join() {
+                Ok(apps) => apps,
+                // Propagate panics from discovery backends to the caller.
+                Err(payload) => std::panic::resume_unwind(payload),
+            })
+            .collect()
+    })
+}
+
+/// Whether apps of type `app_type` should be discovered, if discovery is
+/// restricted to `app_types`.
+#[cfg_attr(
+    not(any(
+        all(target_os = "macos", feature = "discovery-macos-launchservices"),
+        target_os = "windows",
+        all(target_os = "linux", feature = "discovery-linux-scan"),
+    )),
+    allow(dead_code)
+)]
+pub(crate) fn is_allowed(
+    app_types: Option<&[WolframAppType]>,
+    app_type: &WolframAppType,
+) -> bool {
+    match app_types {
+        Some(app_types) => app_types.contains(app_type),
+        None => true,
+    }
+}
+
+/// Operating systems supported by `wolfram-app-discovery`.
 ///
 /// This enum and [`OperatingSystem::target_os()`] exist to be a less fragile
 /// alternative to code like:
@@ -71,11 +160,17 @@ pub fn from_app_directory(dir: &PathBuf) -> Result<WolframApp, Error> {
 ///
 /// Using an enum ensures that all variants are handled in any place where
 /// platform-specific logic is required.
-#[derive(Debug, Clone, PartialEq)]
-pub(crate) enum OperatingSystem {
+///
+/// Use [`SystemID::operating_system()`][crate::SystemID::operating_system] to
+/// get the operating system of a particular Wolfram system ID.
+#[allow(missing_docs)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum OperatingSystem {
     MacOS,
     Windows,
     Linux,
+    /// Any operating system not listed above, e.g. iOS or Android.
     Other,
 }
 
@@ -93,4 +188,14 @@ impl OperatingSystem {
             OperatingSystem::Other
         }
     }
+
+    /// Human-readable name of this operating system.
+    pub fn name(&self) -> &'static str {
+        match self {
+            OperatingSystem::MacOS => "macOS",
+            OperatingSystem::Windows => "Windows",
+            OperatingSystem::Linux => "Linux",
+            OperatingSystem::Other => "other",
+        }
+    }
 }
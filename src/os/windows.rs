@@ -1,25 +1,12 @@
-use std::{
-    collections::HashMap, ffi::c_void, path::PathBuf, ptr::null_mut as nullptr,
-    str::FromStr,
-};
+#[cfg(feature = "discovery-windows-appx")]
+mod appx;
+
+use std::{ffi::c_void, path::PathBuf, ptr::null_mut as nullptr, str::FromStr};
 
 use windows::Win32::{
-    Foundation::{
-        BOOL, ERROR_INSUFFICIENT_BUFFER, ERROR_NO_MORE_ITEMS, ERROR_SUCCESS, MAX_PATH,
-        PWSTR,
-    },
-    Storage::{
-        FileSystem::{Wow64DisableWow64FsRedirection, Wow64RevertWow64FsRedirection},
-        Packaging::Appx::{
-            ClosePackageInfo, GetPackageInfo, GetPackagesByPackageFamily,
-            GetStagedPackageOrigin, OpenPackageInfoByFullName, PackageOrigin,
-            PackageOrigin_DeveloperSigned, PackageOrigin_DeveloperUnsigned,
-            PackageOrigin_Inbox, PackageOrigin_LineOfBusiness, PackageOrigin_Store,
-            PackageOrigin_Unknown, PackageOrigin_Unsigned, APPX_PACKAGE_ARCHITECTURE,
-            APPX_PACKAGE_ARCHITECTURE_ARM, APPX_PACKAGE_ARCHITECTURE_ARM64,
-            APPX_PACKAGE_ARCHITECTURE_X64, APPX_PACKAGE_ARCHITECTURE_X86, PACKAGE_INFO,
-            PACKAGE_INFORMATION_FULL, _PACKAGE_INFO_REFERENCE,
-        },
+    Foundation::{BOOL, ERROR_NO_MORE_ITEMS, ERROR_SUCCESS, MAX_PATH, PWSTR},
+    Storage::FileSystem::{
+        Wow64DisableWow64FsRedirection, Wow64RevertWow64FsRedirection,
     },
     System::{
         Diagnostics::Debug::{
@@ -37,21 +24,68 @@ use windows::Win32::{
     },
 };
 
-use once_cell::sync::Lazy;
 use regex::Regex;
 
-use crate::{AppVersion, Error, WolframApp, WolframAppType};
+use crate::{
+    AppVersion, DiscoveryOptions, Error, InstallationId, Registration, WindowsCaps,
+    WolframApp, WolframAppType,
+};
 
 //======================================
 // Public Interface
 //======================================
 
-pub fn discover_all() -> Vec<WolframApp> {
-    unsafe { load_apps_from_registry() }
+pub fn discover_all(
+    options: &DiscoveryOptions,
+    app_types: Option<&[WolframAppType]>,
+) -> Vec<WolframApp> {
+    discover(options, app_types, false)
+}
+
+/// Find every app registered in the Windows registry, including registrations
+/// whose installation directory no longer exists.
+///
+/// Apps installed as MSIX packages are also returned. Packages are managed by
+/// Windows, and so are never orphaned.
+pub fn discover_registrations(options: &DiscoveryOptions) -> Vec<WolframApp> {
+    discover(options, None, true)
+}
+
+/// If `include_orphaned` is true, registry entries whose installation directory
+/// no longer exists are returned as [orphaned][WolframApp::orphaned]
+/// placeholder apps instead of being skipped.
+fn discover(
+    options: &DiscoveryOptions,
+    app_types: Option<&[WolframAppType]>,
+    include_orphaned: bool,
+) -> Vec<WolframApp> {
+    type Backend<'a> = Box<dyn FnOnce() -> Vec<WolframApp> + Send + 'a>;
+
+    // The registry and each per-user package family are independent sources
+    // of installed apps, so they can be searched concurrently.
+    let registry: Backend =
+        Box::new(move || unsafe { load_apps_from_registry(app_types, include_orphaned) });
+
+    // Package families of app types that aren't wanted are never looked up.
+    #[cfg(feature = "discovery-windows-appx")]
+    let packages = appx::PRODUCTS
+        .iter()
+        .filter(|product| match appx::product_app_type(product) {
+            Some(app_type) => super::is_allowed(app_types, &app_type),
+            None => true,
+        })
+        .map(|product| -> Backend {
+            Box::new(move || unsafe { appx::get_user_packages(product) })
+        });
+
+    #[cfg(not(feature = "discovery-windows-appx"))]
+    let packages = std::iter::empty::<Backend>();
+
+    super::run_backends(options.parallel, std::iter::once(registry).chain(packages))
 }
 
 pub fn from_app_directory(dir: &PathBuf) -> Result<WolframApp, Error> {
-    if let Some(app) = discover_all()
+    if let Some(app) = discover_all(&DiscoveryOptions::default(), None)
         .into_iter()
         .find(|app| &app.app_directory() == dir)
     {
@@ -89,7 +123,7 @@ struct WolframAppBuilder {
 
     system_id: Option<String>,
 
-    id: Option<String>,
+    id: Option<InstallationId>,
 
     installation_directory: Option<PathBuf>,
 
@@ -97,6 +131,8 @@ struct WolframAppBuilder {
 
     executable_path: Option<PathBuf>,
 
+    caps: Option<WindowsCaps>,
+
     digitally_signed: Option<bool>,
 
     origin: Option<Origin>,
@@ -104,6 +140,7 @@ struct WolframAppBuilder {
 
 #[non_exhaustive]
 #[derive(Debug)]
+#[cfg_attr(not(feature = "discovery-windows-appx"), allow(dead_code))]
 enum Origin {
     Sideloaded,
     Store,
@@ -118,9 +155,10 @@ impl WolframAppBuilder {
             app_type,
             installation_directory,
             executable_path,
+            caps,
+            id,
             // TODO: Expose these fields?
             system_id: _,
-            id: _,
             language_tag: _,
             digitally_signed: _,
             origin: _,
@@ -136,6 +174,14 @@ impl WolframAppBuilder {
             app_executable: executable_path,
 
             embedded_player: None,
+            windows_caps: caps,
+            installation_id: id,
+            target_system_id: None,
+            front_end_preference: None,
+            timeout: None,
+            offline: false,
+            wolframscript_invocation: None,
+            registration: None,
         }
         .set_engine_embedded_player()
         .map_err(|_| ())?)
@@ -163,7 +209,7 @@ impl AppVersion {
                 revision: parse(revision)?,
 
                 minor_revision: Some(parse(minor_revision)?),
-                build_code: Some(build_number),
+                build_code: Some(u64::from(build_number)),
             },
             // 3 components: major.minor.revision
             [major, minor, revision] => AppVersion {
@@ -172,7 +218,7 @@ impl AppVersion {
                 revision: parse(revision)?,
 
                 minor_revision: None,
-                build_code: Some(build_number),
+                build_code: Some(u64::from(build_number)),
             },
             _ => {
                 return Err(Error::other(format!(
@@ -189,56 +235,6 @@ impl AppVersion {
 type DWORD = u32;
 type WCHAR = u16;
 
-const PRODUCTS: &[&str] = &[
-    "Wolfram.Mathematica_ztr62y9da0nfr",
-    "Wolfram.Desktop_ztr62y9da0nfr",
-    "Wolfram.Player_ztr62y9da0nfr",
-    "Wolfram.FinancePlatform_ztr62y9da0nfr",
-    "Wolfram.ProgrammingLab_ztr62y9da0nfr",
-    "Wolfram.AlphaNotebook_ztr62y9da0nfr",
-    "Wolfram.Engine_ztr62y9da0nfr",
-];
-
-#[rustfmt::skip]
-static PACKAGE_FAMILY_TO_PRODUCT_NAMES: Lazy<HashMap<&str, &str>> = Lazy::new(|| {
-    HashMap::from_iter([
-        ("Wolfram.Mathematica",                    "Wolfram Mathematica"),
-        ("Wolfram.Mathematica.Documentation",      "Wolfram Mathematica Documentation"),
-        ("Wolfram.Desktop",                        "Wolfram Desktop"),
-        ("Wolfram.Desktop.Documentation",          "Wolfram Desktop Documentation"),
-        ("Wolfram.Player",                         "Wolfram Player"),
-        ("Wolfram.FinancePlatform",                "Wolfram Finance Platform"),
-        ("Wolfram.FinancePlatform.Documentation",  "Wolfram Finance Platform Documentation"),
-        ("Wolfram.ProgrammingLab",                 "Wolfram Programming Lab"),
-        ("Wolfram.ProgrammingLab.Documentation",   "Wolfram Programming Lab Documentation"),
-        ("Wolfram.AlphaNotebook",                  "Wolfram|Alpha Notebook Edition"),
-        ("Wolfram.AlphaNotebook.Documentation",    "Wolfram|Alpha Notebook Edition Documentation"),
-        ("Wolfram.Engine",                         "Wolfram Engine"),
-    ])
-});
-
-#[rustfmt::skip]
-static PACKAGE_FAMILY_TO_APP_TYPE: Lazy<HashMap<&str, WolframAppType>> = Lazy::new(|| {
-    // FIXME: How should documentation installations be handled? Modeling them as
-    //        independent `WolframApp` instances doesn't seem quite optimal, since
-    //        nominally most Wolfram apps provide a copy of the Wolfram Language
-    //        runtime, which documentation does not.
-    HashMap::from_iter([
-        ("Wolfram.Mathematica",                    WolframAppType::Mathematica),
-        // ("Wolfram.Mathematica.Documentation",      PRODUCT_MATHEMATICA),
-        ("Wolfram.Desktop",                        WolframAppType::Desktop),
-        // ("Wolfram.Desktop.Documentation",          PRODUCT_WOLFRAMDESKTOP),
-        ("Wolfram.Player",                         WolframAppType::Player),
-        ("Wolfram.FinancePlatform",                WolframAppType::FinancePlatform),
-        // ("Wolfram.FinancePlatform.Documentation",  PRODUCT_WOLFRAMFINANCE),
-        ("Wolfram.ProgrammingLab",                 WolframAppType::ProgrammingLab),
-        // ("Wolfram.ProgrammingLab.Documentation",   PRODUCT_WOLFRAMPROGLAB),
-        ("Wolfram.AlphaNotebook",                  WolframAppType::WolframAlphaNotebookEdition),
-        // ("Wolfram.AlphaNotebook.Documentation",    PRODUCT_WOLFRAMALPHANB),
-        ("Wolfram.Engine",                         WolframAppType::Engine)
-    ])
-});
-
 fn parse_build_number(build_number: &str) -> Option<u32> {
     let regex = Regex::new(
         "^[a-zA-Z]-[a-zA-Z0-9]+-[a-zA-Z]+(?:\\.[-a-zA-Z]+)?\\.[0-9]+\\.[0-9]+\\.[0-9]+\\.([0-9]+)$"
@@ -294,6 +290,8 @@ unsafe fn load_app_from_registry(
     build_key: HKEY,
     system_id: &str,
     build_number: *const WCHAR,
+    app_types: Option<&[WolframAppType]>,
+    include_orphaned: bool,
 ) -> Result<WolframApp, ()> {
     let mut app_builder: WolframAppBuilder = Default::default();
 
@@ -328,9 +326,7 @@ unsafe fn load_app_from_registry(
         return Err(());
     }
 
-    // TODO: This appears to be some kind of bit field. Parse out the fields and
-    //       store them.
-    // app_builder.setCaps(caps);
+    app_builder.caps = Some(WindowsCaps::from_bits(caps));
 
     size = std::mem::size_of::<DWORD>() as u32;
     if RegGetValueW(
@@ -348,8 +344,16 @@ unsafe fn load_app_from_registry(
 
     app_builder.app_type = WolframAppType::from_windows_product_type(product);
 
+    // Skip apps that would be discarded by the caller before doing any more
+    // work, e.g. locating the Wolfram Player embedded in a Wolfram Engine.
+    if let Some(ref app_type) = app_builder.app_type {
+        if !super::is_allowed(app_types, app_type) {
+            return Err(());
+        }
+    }
+
     if let Some(id) = reg_get_value_string(build_key, "CLSID") {
-        app_builder.id = Some(id);
+        app_builder.id = Some(InstallationId::WindowsClsid(id));
     }
 
     if let Some(dir) = reg_get_value_string(build_key, "InstallationDirectory") {
@@ -381,7 +385,20 @@ unsafe fn load_app_from_registry(
         };
 
         if !has_exec_path && !has_install_dir {
-            return Err(());
+            // The registration refers to an installation that no longer
+            // exists.
+            let app_directory =
+                app_builder.installation_directory.clone().or_else(|| {
+                    let exec_path = app_builder.executable_path.as_ref()?;
+                    Some(exec_path.parent()?.to_path_buf())
+                });
+
+            return match (include_orphaned, app_builder.app_type, app_directory) {
+                (true, Some(app_type), Some(app_directory)) => {
+                    Ok(WolframApp::orphaned(app_type, app_directory))
+                },
+                _ => Err(()),
+            };
         }
     }
 
@@ -432,7 +449,7 @@ unsafe fn load_app_from_registry(
                 revision: u32::from(revision),
                 minor_revision: Some(u32::from(minor_revision)),
 
-                build_code: Some(build_number),
+                build_code: Some(u64::from(build_number)),
             });
         }
     }
@@ -470,252 +487,10 @@ unsafe fn load_app_from_registry(
     return app_builder.finish();
 }
 
-unsafe fn load_app_from_package_info(
-    package_info: &PACKAGE_INFO,
-    app_builder: &mut WolframAppBuilder,
-) -> Result<(), String> {
-    app_builder.id = Some(utf16_ptr_to_string(package_info.packageFullName.0));
-
-    // FIXME:
-    // app_builder.setFullVersion(package_info.packageId.version.Anonymous.Version);
-
-    let package_id_name = utf16_ptr_to_string(package_info.packageId.name.0);
-
-    {
-        // because we cannot get our hands on the display name...
-        let mut product_title = String::from("Unknown");
-
-        if let Some(iter) = PACKAGE_FAMILY_TO_PRODUCT_NAMES.get(package_id_name.as_str())
-        {
-            let app_version = app_builder.app_version.clone().unwrap();
-
-            let iter: &str = iter;
-            product_title = iter.to_owned() + " " + &app_version.major().to_string();
-
-            if app_version.minor() != 0 {
-                product_title += &format!(".{}", &app_version.minor());
-            }
-        }
-
-        app_builder.app_name = Some(product_title);
-    }
-
-    if let Some(app_type) = PACKAGE_FAMILY_TO_APP_TYPE.get(package_id_name.as_str()) {
-        app_builder.app_type = Some(app_type.clone());
-    } else {
-        return Err(format!("unrecognized package id name: {}", package_id_name));
-    }
-
-    let system_id = match APPX_PACKAGE_ARCHITECTURE(
-        package_info
-            .packageId
-            .processorArchitecture
-            .try_into()
-            .unwrap(),
-    ) {
-        APPX_PACKAGE_ARCHITECTURE_ARM => "Windows-ARM",
-        APPX_PACKAGE_ARCHITECTURE_ARM64 => "Windows-ARM64",
-        APPX_PACKAGE_ARCHITECTURE_X86 => "Windows",
-        APPX_PACKAGE_ARCHITECTURE_X64 => "Windows-x86-64",
-        _ => "Unknown",
-    };
-
-    app_builder.system_id = Some(String::from(system_id));
-
-    let mut raw_origin = PackageOrigin::default();
-
-    #[allow(non_upper_case_globals)]
-    if GetStagedPackageOrigin(package_info.packageFullName, &mut raw_origin)
-        == ERROR_SUCCESS.0 as i32
-    {
-        let origin = match raw_origin {
-            PackageOrigin_DeveloperUnsigned
-            | PackageOrigin_DeveloperSigned
-            | PackageOrigin_Inbox
-            | PackageOrigin_LineOfBusiness
-            | PackageOrigin_Unsigned => Origin::Sideloaded,
-            PackageOrigin_Store => Origin::Store,
-            PackageOrigin_Unknown | _ => Origin::Unknown,
-        };
-
-        app_builder.origin = Some(origin);
-
-        match raw_origin {
-            PackageOrigin_Inbox
-            | PackageOrigin_DeveloperSigned
-            | PackageOrigin_LineOfBusiness
-            | PackageOrigin_Store => {
-                app_builder.digitally_signed = Some(true);
-            },
-
-            PackageOrigin_DeveloperUnsigned
-            | PackageOrigin_Unknown
-            | PackageOrigin_Unsigned
-            | _ => {
-                app_builder.digitally_signed = Some(false);
-            },
-        }
-    }
-
-    // TODO: Set language tag to None in this case?
-    app_builder.language_tag = Some(String::from("Neutral"));
-    app_builder.installation_directory =
-        Some(PathBuf::from(utf16_ptr_to_string(package_info.path.0)));
-
-    // FIXME:
-    // app_builder.setBuildNumber(ReadCreationIDFileFromLayout(package_info.path));
-
-    Ok(())
-}
-
-fn merge_user_installed_packages(apps: &mut Vec<WolframApp>) {
-    for product in PRODUCTS {
-        let product_apps = unsafe { get_user_packages(product) };
-        apps.extend(product_apps);
-    }
-}
-
-unsafe fn get_user_packages(product: &str) -> Vec<WolframApp> {
-    let mut count: u32 = 0;
-    let mut buffer_length: u32 = 0;
-
-    let error: i32 = GetPackagesByPackageFamily(
-        product,
-        &mut count,
-        nullptr(),
-        &mut buffer_length,
-        PWSTR(nullptr()),
-    );
-
-    if count == 0 || error != ERROR_INSUFFICIENT_BUFFER.0 as i32 {
-        return vec![];
-    }
-
-    // let buffer: PWSTR = malloc(size_of::<WCHAR>() * buffer_length) as *mut WCHAR;
-    let mut buffer_vec: Vec<u16> =
-        Vec::with_capacity(usize::try_from(buffer_length).unwrap());
-    let buffer: *mut u16 = buffer_vec.as_mut_ptr();
-
-    // let packageFullNames: *mut PWSTR = malloc(size_of::<PWSTR>() * count) as *mut PWSTR;
-    let mut package_full_names: Vec<PWSTR> =
-        Vec::with_capacity(usize::try_from(count).unwrap());
-
-    if GetPackagesByPackageFamily(
-        product,
-        &mut count,
-        package_full_names.as_mut_ptr(),
-        &mut buffer_length,
-        PWSTR(buffer),
-    ) != ERROR_SUCCESS.0 as i32
-    {
-        return vec![];
-    }
-
-    package_full_names.set_len(usize::try_from(count).unwrap());
-
-    let mut apps = Vec::new();
-
-    for package_full_name in package_full_names {
-        let mut piref: *mut _PACKAGE_INFO_REFERENCE = nullptr();
-
-        if OpenPackageInfoByFullName(package_full_name, 0, &mut piref)
-            != ERROR_SUCCESS.0 as i32
-        {
-            continue;
-        }
-
-        let mut app_builder = WolframAppBuilder::default();
-
-        let mut pack_length: u32 = 0;
-        let mut pack_count: u32 = 0;
-
-        if GetPackageInfo(
-            piref,
-            PACKAGE_INFORMATION_FULL,
-            &mut pack_length,
-            nullptr(),
-            &mut pack_count,
-        ) == ERROR_INSUFFICIENT_BUFFER.0 as i32
-        {
-            let mut pack_info_buffer: Vec<u8> =
-                Vec::with_capacity(usize::try_from(pack_length).unwrap());
-
-            if GetPackageInfo(
-                piref,
-                PACKAGE_INFORMATION_FULL,
-                &mut pack_length,
-                pack_info_buffer.as_mut_ptr(),
-                &mut pack_count,
-            ) == ERROR_SUCCESS.0 as i32
-            {
-                // FIXME: Is this safe? We're casting a Vec's buffer to a struct instance. Is this
-                //        well-aligned?
-                let package_info: *const PACKAGE_INFO =
-                    pack_info_buffer.as_ptr() as *const PACKAGE_INFO;
-
-                match load_app_from_package_info(&*package_info, &mut app_builder) {
-                    Ok(()) => (),
-                    Err(err) => {
-                        crate::warning(&format!(
-                            "unable to process Wolfram application package '{}': {}",
-                            utf16_ptr_to_string(package_full_name.0),
-                            err
-                        ));
-
-                        ClosePackageInfo(piref);
-                        continue;
-                    },
-                }
-
-                // TODO:
-                // UpdateCapsFromApplicationIds(piref, package_info, app_builder);
-            }
-        }
-
-        // UINT32 optPackLength = 0, optPackCount = 0;
-        // if (GetPackageInfo(piref, PACKAGE_FILTER_OPTIONAL, &optPackLength, nullptr, &optPackCount)
-        // 	== ERROR_INSUFFICIENT_BUFFER)
-        // {
-        // 	LPBYTE optPackInfoBuffer = (LPBYTE)malloc(optPackLength);
-        // 	if (GetPackageInfo(piref, PACKAGE_FILTER_OPTIONAL, &optPackLength, optPackInfoBuffer, &optPackCount)
-        // 		== ERROR_SUCCESS)
-        // 	{
-        // 		std::vector<Wolfram::Apps::InstalledProduct> theOptionalProducts;
-        // 		for (UINT32 i = 0; i < optPackCount; i++)
-        // 		{
-        // 			PACKAGE_INFO_REFERENCE optpiref = nullptr;
-        // 			PACKAGE_INFO* package_info = (PACKAGE_INFO*)optPackInfoBuffer;
-        // 			Wolfram::Apps::InstalledProduct theOptionalProduct;
-
-        // 			if (OpenPackageInfoByFullName(package_info->packageFullName, 0, &optpiref) == ERROR_SUCCESS)
-        // 			{
-        // 				LoadInstalledProductInfoFromPackageInfo(package_info, theOptionalProduct);
-        // 				UpdateCapsFromApplicationIds(optpiref, package_info, theOptionalProduct);
-        // 				cpi(optpiref);
-        // 			}
-
-        // 			theOptionalProducts.push(theOptionalProduct);
-        // 		}
-
-        // 		app_builder.setOptionalPackages(theOptionalProducts);
-        // 	}
-
-        // 	free(optPackInfoBuffer);
-        // }
-
-        match app_builder.finish() {
-            Ok(app) => apps.push(app),
-            Err(()) => crate::warning("WolframAppBuilder had incomplete information"),
-        };
-
-        ClosePackageInfo(piref);
-    }
-
-    apps
-}
-
-
-unsafe fn load_apps_from_registry() -> Vec<WolframApp> {
+unsafe fn load_apps_from_registry(
+    app_types: Option<&[WolframAppType]>,
+    include_orphaned: bool,
+) -> Vec<WolframApp> {
     let mut installations: Vec<WolframApp> = Vec::new();
 
     let mut the_root_key: HKEY = HKEY(0);
@@ -766,8 +541,14 @@ unsafe fn load_apps_from_registry() -> Vec<WolframApp> {
         );
     }
 
+    const INSTALLATIONS_KEY: &str = "Software\\Wolfram Research\\Installations";
+
     let mut load_products_from_registry_key =
-        |the_key: HKEY, access_type: REG_SAM_FLAGS, system_id: &str| {
+        |the_key: HKEY,
+         access_type: REG_SAM_FLAGS,
+         system_id: &str,
+         hive: &str,
+         wow64_32: bool| {
             let mut build_number: [u16; MAX_PATH as usize] = [0; MAX_PATH as usize];
             let mut index: DWORD = 0;
 
@@ -783,11 +564,21 @@ unsafe fn load_apps_from_registry() -> Vec<WolframApp> {
                     &mut build_key,
                 ) == ERROR_SUCCESS
                 {
-                    if let Ok(app) = load_app_from_registry(
+                    if let Ok(mut app) = load_app_from_registry(
                         build_key,
                         system_id,
                         build_number.as_ptr(),
+                        app_types,
+                        include_orphaned,
                     ) {
+                        app.registration = Some(Registration::WindowsRegistryKey {
+                            key: format!(
+                                "{hive}\\{INSTALLATIONS_KEY}\\{}",
+                                utf16_ptr_to_string(build_number.as_ptr())
+                            ),
+                            wow64_32,
+                        });
+
                         installations.push(app);
                     }
 
@@ -807,12 +598,20 @@ unsafe fn load_apps_from_registry() -> Vec<WolframApp> {
             } else {
                 "Windows"
             },
+            "HKEY_LOCAL_MACHINE",
+            false,
         );
         RegCloseKey(the_root_key);
     }
 
     if needs_alt && the_alt_root_key != HKEY(0) {
-        load_products_from_registry_key(the_alt_root_key, alt_access_type, "Windows");
+        load_products_from_registry_key(
+            the_alt_root_key,
+            alt_access_type,
+            "Windows",
+            "HKEY_LOCAL_MACHINE",
+            true,
+        );
         RegCloseKey(the_alt_root_key);
     }
 
@@ -825,12 +624,12 @@ unsafe fn load_apps_from_registry() -> Vec<WolframApp> {
             } else {
                 "Windows"
             },
+            "HKEY_CURRENT_USER",
+            false,
         );
         RegCloseKey(the_user_key);
     }
 
-    merge_user_installed_packages(&mut installations);
-
     return installations;
 }
 
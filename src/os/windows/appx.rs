@@ -0,0 +1,322 @@
+//! Discovery of Wolfram apps installed as per-user Appx packages.
+//!
+//! Enabled by the `discovery-windows-appx` crate feature.
+
+use std::{collections::HashMap, path::PathBuf, ptr::null_mut as nullptr};
+
+use windows::Win32::{
+    Foundation::{ERROR_INSUFFICIENT_BUFFER, ERROR_SUCCESS, PWSTR},
+    Storage::Packaging::Appx::{
+        ClosePackageInfo, GetPackageInfo, GetPackagesByPackageFamily,
+        GetStagedPackageOrigin, OpenPackageInfoByFullName, PackageOrigin,
+        PackageOrigin_DeveloperSigned, PackageOrigin_DeveloperUnsigned,
+        PackageOrigin_Inbox, PackageOrigin_LineOfBusiness, PackageOrigin_Store,
+        PackageOrigin_Unknown, PackageOrigin_Unsigned, _PACKAGE_INFO_REFERENCE,
+        APPX_PACKAGE_ARCHITECTURE, APPX_PACKAGE_ARCHITECTURE_ARM,
+        APPX_PACKAGE_ARCHITECTURE_ARM64, APPX_PACKAGE_ARCHITECTURE_X64,
+        APPX_PACKAGE_ARCHITECTURE_X86, PACKAGE_INFO, PACKAGE_INFORMATION_FULL,
+    },
+};
+
+use once_cell::sync::Lazy;
+
+use crate::{InstallationId, WolframApp, WolframAppType};
+
+use super::{utf16_ptr_to_string, Origin, WolframAppBuilder};
+
+pub(super) const PRODUCTS: &[&str] = &[
+    "Wolfram.Mathematica_ztr62y9da0nfr",
+    "Wolfram.Desktop_ztr62y9da0nfr",
+    "Wolfram.Player_ztr62y9da0nfr",
+    "Wolfram.FinancePlatform_ztr62y9da0nfr",
+    "Wolfram.ProgrammingLab_ztr62y9da0nfr",
+    "Wolfram.AlphaNotebook_ztr62y9da0nfr",
+    "Wolfram.Engine_ztr62y9da0nfr",
+];
+
+#[rustfmt::skip]
+static PACKAGE_FAMILY_TO_PRODUCT_NAMES: Lazy<HashMap<&str, &str>> = Lazy::new(|| {
+    HashMap::from_iter([
+        ("Wolfram.Mathematica",                    "Wolfram Mathematica"),
+        ("Wolfram.Mathematica.Documentation",      "Wolfram Mathematica Documentation"),
+        ("Wolfram.Desktop",                        "Wolfram Desktop"),
+        ("Wolfram.Desktop.Documentation",          "Wolfram Desktop Documentation"),
+        ("Wolfram.Player",                         "Wolfram Player"),
+        ("Wolfram.FinancePlatform",                "Wolfram Finance Platform"),
+        ("Wolfram.FinancePlatform.Documentation",  "Wolfram Finance Platform Documentation"),
+        ("Wolfram.ProgrammingLab",                 "Wolfram Programming Lab"),
+        ("Wolfram.ProgrammingLab.Documentation",   "Wolfram Programming Lab Documentation"),
+        ("Wolfram.AlphaNotebook",                  "Wolfram|Alpha Notebook Edition"),
+        ("Wolfram.AlphaNotebook.Documentation",    "Wolfram|Alpha Notebook Edition Documentation"),
+        ("Wolfram.Engine",                         "Wolfram Engine"),
+    ])
+});
+
+#[rustfmt::skip]
+static PACKAGE_FAMILY_TO_APP_TYPE: Lazy<HashMap<&str, WolframAppType>> = Lazy::new(|| {
+    // FIXME: How should documentation installations be handled? Modeling them as
+    //        independent `WolframApp` instances doesn't seem quite optimal, since
+    //        nominally most Wolfram apps provide a copy of the Wolfram Language
+    //        runtime, which documentation does not.
+    HashMap::from_iter([
+        ("Wolfram.Mathematica",                    WolframAppType::Mathematica),
+        // ("Wolfram.Mathematica.Documentation",      PRODUCT_MATHEMATICA),
+        ("Wolfram.Desktop",                        WolframAppType::Desktop),
+        // ("Wolfram.Desktop.Documentation",          PRODUCT_WOLFRAMDESKTOP),
+        ("Wolfram.Player",                         WolframAppType::Player),
+        ("Wolfram.FinancePlatform",                WolframAppType::FinancePlatform),
+        // ("Wolfram.FinancePlatform.Documentation",  PRODUCT_WOLFRAMFINANCE),
+        ("Wolfram.ProgrammingLab",                 WolframAppType::ProgrammingLab),
+        // ("Wolfram.ProgrammingLab.Documentation",   PRODUCT_WOLFRAMPROGLAB),
+        ("Wolfram.AlphaNotebook",                  WolframAppType::WolframAlphaNotebookEdition),
+        // ("Wolfram.AlphaNotebook.Documentation",    PRODUCT_WOLFRAMALPHANB),
+        ("Wolfram.Engine",                         WolframAppType::Engine)
+    ])
+});
+
+/// Get the app type of the apps in the package family `product`, an element of
+/// [`PRODUCTS`].
+pub(super) fn product_app_type(product: &str) -> Option<WolframAppType> {
+    let family_name = product.split('_').next()?;
+
+    PACKAGE_FAMILY_TO_APP_TYPE.get(family_name).cloned()
+}
+
+unsafe fn load_app_from_package_info(
+    package_info: &PACKAGE_INFO,
+    app_builder: &mut WolframAppBuilder,
+) -> Result<(), String> {
+    app_builder.id = Some(InstallationId::WindowsPackageFullName(utf16_ptr_to_string(
+        package_info.packageFullName.0,
+    )));
+
+    // FIXME:
+    // app_builder.setFullVersion(package_info.packageId.version.Anonymous.Version);
+
+    let package_id_name = utf16_ptr_to_string(package_info.packageId.name.0);
+
+    {
+        // because we cannot get our hands on the display name...
+        let mut product_title = String::from("Unknown");
+
+        if let Some(iter) = PACKAGE_FAMILY_TO_PRODUCT_NAMES.get(package_id_name.as_str())
+        {
+            let app_version = app_builder.app_version.clone().unwrap();
+
+            let iter: &str = iter;
+            product_title = iter.to_owned() + " " + &app_version.major().to_string();
+
+            if app_version.minor() != 0 {
+                product_title += &format!(".{}", &app_version.minor());
+            }
+        }
+
+        app_builder.app_name = Some(product_title);
+    }
+
+    if let Some(app_type) = PACKAGE_FAMILY_TO_APP_TYPE.get(package_id_name.as_str()) {
+        app_builder.app_type = Some(app_type.clone());
+    } else {
+        return Err(format!("unrecognized package id name: {}", package_id_name));
+    }
+
+    let system_id = match APPX_PACKAGE_ARCHITECTURE(
+        package_info
+            .packageId
+            .processorArchitecture
+            .try_into()
+            .unwrap(),
+    ) {
+        APPX_PACKAGE_ARCHITECTURE_ARM => "Windows-ARM",
+        APPX_PACKAGE_ARCHITECTURE_ARM64 => "Windows-ARM64",
+        APPX_PACKAGE_ARCHITECTURE_X86 => "Windows",
+        APPX_PACKAGE_ARCHITECTURE_X64 => "Windows-x86-64",
+        _ => "Unknown",
+    };
+
+    app_builder.system_id = Some(String::from(system_id));
+
+    let mut raw_origin = PackageOrigin::default();
+
+    #[allow(non_upper_case_globals)]
+    if GetStagedPackageOrigin(package_info.packageFullName, &mut raw_origin)
+        == ERROR_SUCCESS.0 as i32
+    {
+        let origin = match raw_origin {
+            PackageOrigin_DeveloperUnsigned
+            | PackageOrigin_DeveloperSigned
+            | PackageOrigin_Inbox
+            | PackageOrigin_LineOfBusiness
+            | PackageOrigin_Unsigned => Origin::Sideloaded,
+            PackageOrigin_Store => Origin::Store,
+            PackageOrigin_Unknown | _ => Origin::Unknown,
+        };
+
+        app_builder.origin = Some(origin);
+
+        match raw_origin {
+            PackageOrigin_Inbox
+            | PackageOrigin_DeveloperSigned
+            | PackageOrigin_LineOfBusiness
+            | PackageOrigin_Store => {
+                app_builder.digitally_signed = Some(true);
+            },
+
+            PackageOrigin_DeveloperUnsigned
+            | PackageOrigin_Unknown
+            | PackageOrigin_Unsigned
+            | _ => {
+                app_builder.digitally_signed = Some(false);
+            },
+        }
+    }
+
+    // TODO: Set language tag to None in this case?
+    app_builder.language_tag = Some(String::from("Neutral"));
+    app_builder.installation_directory =
+        Some(PathBuf::from(utf16_ptr_to_string(package_info.path.0)));
+
+    // FIXME:
+    // app_builder.setBuildNumber(ReadCreationIDFileFromLayout(package_info.path));
+
+    Ok(())
+}
+
+pub(super) unsafe fn get_user_packages(product: &str) -> Vec<WolframApp> {
+    let mut count: u32 = 0;
+    let mut buffer_length: u32 = 0;
+
+    let error: i32 = GetPackagesByPackageFamily(
+        product,
+        &mut count,
+        nullptr(),
+        &mut buffer_length,
+        PWSTR(nullptr()),
+    );
+
+    if count == 0 || error != ERROR_INSUFFICIENT_BUFFER.0 as i32 {
+        return vec![];
+    }
+
+    // let buffer: PWSTR = malloc(size_of::<WCHAR>() * buffer_length) as *mut WCHAR;
+    let mut buffer_vec: Vec<u16> =
+        Vec::with_capacity(usize::try_from(buffer_length).unwrap());
+    let buffer: *mut u16 = buffer_vec.as_mut_ptr();
+
+    // let packageFullNames: *mut PWSTR = malloc(size_of::<PWSTR>() * count) as *mut PWSTR;
+    let mut package_full_names: Vec<PWSTR> =
+        Vec::with_capacity(usize::try_from(count).unwrap());
+
+    if GetPackagesByPackageFamily(
+        product,
+        &mut count,
+        package_full_names.as_mut_ptr(),
+        &mut buffer_length,
+        PWSTR(buffer),
+    ) != ERROR_SUCCESS.0 as i32
+    {
+        return vec![];
+    }
+
+    package_full_names.set_len(usize::try_from(count).unwrap());
+
+    let mut apps = Vec::new();
+
+    for package_full_name in package_full_names {
+        let mut piref: *mut _PACKAGE_INFO_REFERENCE = nullptr();
+
+        if OpenPackageInfoByFullName(package_full_name, 0, &mut piref)
+            != ERROR_SUCCESS.0 as i32
+        {
+            continue;
+        }
+
+        let mut app_builder = WolframAppBuilder::default();
+
+        let mut pack_length: u32 = 0;
+        let mut pack_count: u32 = 0;
+
+        if GetPackageInfo(
+            piref,
+            PACKAGE_INFORMATION_FULL,
+            &mut pack_length,
+            nullptr(),
+            &mut pack_count,
+        ) == ERROR_INSUFFICIENT_BUFFER.0 as i32
+        {
+            let mut pack_info_buffer: Vec<u8> =
+                Vec::with_capacity(usize::try_from(pack_length).unwrap());
+
+            if GetPackageInfo(
+                piref,
+                PACKAGE_INFORMATION_FULL,
+                &mut pack_length,
+                pack_info_buffer.as_mut_ptr(),
+                &mut pack_count,
+            ) == ERROR_SUCCESS.0 as i32
+            {
+                // FIXME: Is this safe? We're casting a Vec's buffer to a struct instance. Is this
+                //        well-aligned?
+                let package_info: *const PACKAGE_INFO =
+                    pack_info_buffer.as_ptr() as *const PACKAGE_INFO;
+
+                match load_app_from_package_info(&*package_info, &mut app_builder) {
+                    Ok(()) => (),
+                    Err(err) => {
+                        crate::warning(&format!(
+                            "unable to process Wolfram application package '{}': {}",
+                            utf16_ptr_to_string(package_full_name.0),
+                            err
+                        ));
+
+                        ClosePackageInfo(piref);
+                        continue;
+                    },
+                }
+
+                // TODO:
+                // UpdateCapsFromApplicationIds(piref, package_info, app_builder);
+            }
+        }
+
+        // UINT32 optPackLength = 0, optPackCount = 0;
+        // if (GetPackageInfo(piref, PACKAGE_FILTER_OPTIONAL, &optPackLength, nullptr, &optPackCount)
+        // 	== ERROR_INSUFFICIENT_BUFFER)
+        // {
+        // 	LPBYTE optPackInfoBuffer = (LPBYTE)malloc(optPackLength);
+        // 	if (GetPackageInfo(piref, PACKAGE_FILTER_OPTIONAL, &optPackLength, optPackInfoBuffer, &optPackCount)
+        // 		== ERROR_SUCCESS)
+        // 	{
+        // 		std::vector<Wolfram::Apps::InstalledProduct> theOptionalProducts;
+        // 		for (UINT32 i = 0; i < optPackCount; i++)
+        // 		{
+        // 			PACKAGE_INFO_REFERENCE optpiref = nullptr;
+        // 			PACKAGE_INFO* package_info = (PACKAGE_INFO*)optPackInfoBuffer;
+        // 			Wolfram::Apps::InstalledProduct theOptionalProduct;
+
+        // 			if (OpenPackageInfoByFullName(package_info->packageFullName, 0, &optpiref) == ERROR_SUCCESS)
+        // 			{
+        // 				LoadInstalledProductInfoFromPackageInfo(package_info, theOptionalProduct);
+        // 				UpdateCapsFromApplicationIds(optpiref, package_info, theOptionalProduct);
+        // 				cpi(optpiref);
+        // 			}
+
+        // 			theOptionalProducts.push(theOptionalProduct);
+        // 		}
+
+        // 		app_builder.setOptionalPackages(theOptionalProducts);
+        // 	}
+
+        // 	free(optPackInfoBuffer);
+        // }
+
+        match app_builder.finish() {
+            Ok(app) => apps.push(app),
+            Err(()) => crate::warning("WolframAppBuilder had incomplete information"),
+        };
+
+        ClosePackageInfo(piref);
+    }
+
+    apps
+}
@@ -0,0 +1,484 @@
+//! Discovery of installed Wolfram paclets.
+//!
+//! A [paclet](https://reference.wolfram.com/language/guide/Paclets.html) is a
+//! unit of Wolfram functionality, like a package, library, or data collection.
+//! Each paclet is a directory containing a `PacletInfo.wl` (or legacy
+//! `PacletInfo.m`) file that describes it.
+//!
+//! Use [`user_paclets()`] to list the paclets installed in the paclet
+//! repository of the current user,
+//! [`WolframApp::layout_paclets()`][crate::WolframApp::layout_paclets] to list
+//! the paclets bundled with an installation, and [`PacletInfo`] to read the
+//! paclet info file of a paclet.
+
+use std::path::{Path, PathBuf};
+
+use log::info;
+
+use crate::{directories, wl::Expr, Error};
+
+/// An installed paclet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Paclet {
+    name: String,
+    version: String,
+    location: PathBuf,
+    info: Option<PacletInfo>,
+}
+
+/// Contents of a `PacletInfo.wl` or `PacletInfo.m` file.
+///
+/// Only the properties needed to identify a paclet and the components it
+/// provides are parsed.
+///
+/// # Examples
+///
+/// ```
+/// use wolfram_app_discovery::paclet::PacletInfo;
+///
+/// let info = PacletInfo::parse(r#"
+///     PacletObject[<|
+///         "Name" -> "Wolfram/Example",
+///         "Version" -> "1.2.0",
+///         "WolframVersion" -> "13.0+",
+///         "Extensions" -> {
+///             {"Kernel", "Root" -> "Kernel", "Context" -> "Wolfram`Example`"}
+///         }
+///     |>]
+/// "#).unwrap();
+///
+/// assert_eq!(info.name(), "Wolfram/Example");
+/// assert_eq!(info.version(), "1.2.0");
+/// assert_eq!(info.wolfram_version(), Some("13.0+"));
+/// assert_eq!(info.extensions()[0].kind(), "Kernel");
+/// assert_eq!(info.extensions()[0].option("Root"), Some("Kernel"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PacletInfo {
+    name: String,
+    version: String,
+    wolfram_version: Option<String>,
+    extensions: Vec<PacletExtension>,
+}
+
+/// An extension declared in a [`PacletInfo`] file, like `{"Kernel", ...}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PacletExtension {
+    kind: String,
+    options: Vec<(String, String)>,
+}
+
+//======================================
+// API
+//======================================
+
+/// Get the directory that paclets installed by the current user are stored
+/// in.
+///
+/// This is `$UserBasePacletsDirectory/Repository`, where
+/// `$UserBasePacletsDirectory` is the `Paclets` subdirectory of
+/// [`user_base_directory()`][directories::user_base_directory].
+///
+/// The returned directory is not guaranteed to exist.
+pub fn user_paclet_repository_directory() -> Result<PathBuf, Error> {
+    let user_base = directories::user_base_directory()?;
+
+    Ok(user_base.join("Paclets").join("Repository"))
+}
+
+/// List the paclets installed in the paclet repository of the current user.
+///
+/// See [`user_paclet_repository_directory()`]. If the repository directory
+/// does not exist, an empty list is returned.
+///
+/// Paclets are sorted by name, and then by version.
+pub fn user_paclets() -> Result<Vec<Paclet>, Error> {
+    let repository = user_paclet_repository_directory()?;
+
+    if !repository.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    paclets_in_repository(&repository)
+}
+
+impl Paclet {
+    /// Name of this paclet, e.g. `"WolframAlphaClient"`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Version of this paclet, e.g. `"1.2.3"`.
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Directory containing this paclet.
+    pub fn location(&self) -> &PathBuf {
+        &self.location
+    }
+
+    /// Contents of the paclet info file of this paclet, if it could be parsed.
+    pub fn info(&self) -> Option<&PacletInfo> {
+        self.info.as_ref()
+    }
+}
+
+impl PacletInfo {
+    /// Read and parse the `PacletInfo.wl` or `PacletInfo.m` file at `path`.
+    pub fn from_file(path: &Path) -> Result<Self, Error> {
+        let contents = std::fs::read_to_string(path)?;
+
+        PacletInfo::parse(&contents).map_err(|err| {
+            Error::other(format!(
+                "invalid paclet info file '{}': {err}",
+                path.display()
+            ))
+        })
+    }
+
+    /// Read the paclet info file of the paclet in directory `dir`.
+    ///
+    /// `PacletInfo.wl` is preferred over the legacy `PacletInfo.m`.
+    pub fn from_paclet_directory(dir: &Path) -> Result<Self, Error> {
+        match paclet_info_file(dir) {
+            Some(path) => PacletInfo::from_file(&path),
+            None => Err(Error::unexpected_layout(
+                "paclet info file",
+                dir.to_path_buf(),
+                dir.join("PacletInfo.wl"),
+            )),
+        }
+    }
+
+    /// Parse the contents of a paclet info file.
+    ///
+    /// Both the `PacletObject[<|"Name" -> ..., ...|>]` form and the legacy
+    /// `Paclet[Name -> ..., ...]` form are supported.
+    pub fn parse(contents: &str) -> Result<Self, Error> {
+        let invalid =
+            |message: String| Error::other(format!("invalid PacletInfo: {message}"));
+
+        let expr = Expr::parse(contents).map_err(|err| invalid(err.to_string()))?;
+
+        let rules: &[Expr] = match expr {
+            Expr::Normal(ref head, ref args) => match (head.as_key(), args.as_slice()) {
+                (Some("PacletObject"), [Expr::Association(rules)]) => rules,
+                (Some("Paclet"), rules) => rules,
+                _ => {
+                    return Err(invalid(
+                        "expected PacletObject[...] or Paclet[...]".to_owned(),
+                    ))
+                },
+            },
+            _ => {
+                return Err(invalid(
+                    "expected PacletObject[...] or Paclet[...]".to_owned(),
+                ))
+            },
+        };
+
+        let get = |keys: &[&str]| {
+            rules
+                .iter()
+                .filter_map(Expr::as_rule)
+                .find(|(key, _)| key.as_key().is_some_and(|key| keys.contains(&key)))
+                .map(|(_, value)| value)
+        };
+
+        let get_string = |keys: &[&str]| -> Option<String> {
+            match get(keys)? {
+                Expr::String(value) | Expr::Number(value) => Some(value.clone()),
+                _ => None,
+            }
+        };
+
+        let name =
+            get_string(&["Name"]).ok_or_else(|| invalid("missing Name".to_owned()))?;
+        let version = get_string(&["Version"])
+            .ok_or_else(|| invalid("missing Version".to_owned()))?;
+        let wolfram_version = get_string(&["WolframVersion", "MathematicaVersion"]);
+
+        let extensions = match get(&["Extensions"]) {
+            Some(Expr::List(extensions)) => extensions
+                .iter()
+                .filter_map(PacletExtension::from_expr)
+                .collect(),
+            _ => Vec::new(),
+        };
+
+        Ok(PacletInfo {
+            name,
+            version,
+            wolfram_version,
+            extensions,
+        })
+    }
+
+    /// Name of the paclet.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Version of the paclet.
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Wolfram Language versions this paclet is compatible with, e.g.
+    /// `"13.0+"`.
+    ///
+    /// This is read from the `WolframVersion` property, or the legacy
+    /// `MathematicaVersion` property.
+    pub fn wolfram_version(&self) -> Option<&str> {
+        self.wolfram_version.as_deref()
+    }
+
+    /// Extensions declared by the paclet.
+    pub fn extensions(&self) -> &[PacletExtension] {
+        &self.extensions
+    }
+}
+
+impl PacletExtension {
+    /// Parse an extension of the form `{"Kind", "Key" -> "value", ...}`.
+    fn from_expr(expr: &Expr) -> Option<Self> {
+        let Expr::List(elements) = expr else {
+            return None;
+        };
+
+        let (kind, options) = elements.split_first()?;
+
+        let options = options
+            .iter()
+            .filter_map(Expr::as_rule)
+            .filter_map(|(key, value)| {
+                let value = match value {
+                    Expr::String(value) | Expr::Symbol(value) | Expr::Number(value) => {
+                        value
+                    },
+                    _ => return None,
+                };
+
+                Some((key.as_key()?.to_owned(), value.clone()))
+            })
+            .collect();
+
+        Some(PacletExtension {
+            kind: kind.as_str()?.to_owned(),
+            options,
+        })
+    }
+
+    /// Kind of this extension, e.g. `"Kernel"`, `"LibraryLink"`, or
+    /// `"Documentation"`.
+    pub fn kind(&self) -> &str {
+        &self.kind
+    }
+
+    /// Get the value of the option `key` of this extension.
+    ///
+    /// Only options whose value is a string, symbol, or number are available.
+    pub fn option(&self, key: &str) -> Option<&str> {
+        self.options
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+//======================================
+// Helpers
+//======================================
+
+/// List the paclets in a paclet repository directory.
+///
+/// Each paclet in a repository is stored in a subdirectory named
+/// `<name>-<version>`. The name and version are read from the paclet info file
+/// of each paclet, if it can be parsed, and otherwise from the subdirectory
+/// name. Subdirectories that do not contain a paclet info file, or whose name
+/// does not have that form, are skipped.
+pub(crate) fn paclets_in_repository(repository: &Path) -> Result<Vec<Paclet>, Error> {
+    let mut paclets = Vec::new();
+
+    for entry in std::fs::read_dir(repository)? {
+        let location = entry?.path();
+
+        let Some(info_file) = paclet_info_file(&location) else {
+            continue;
+        };
+
+        let info = PacletInfo::from_file(&info_file).ok();
+
+        let (name, version) = match info {
+            Some(ref info) => (info.name.clone(), info.version.clone()),
+            None => {
+                let Some((name, version)) = location
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .and_then(parse_repository_directory_name)
+                else {
+                    continue;
+                };
+
+                (name.to_owned(), version.to_owned())
+            },
+        };
+
+        paclets.push(Paclet {
+            name,
+            version,
+            location,
+            info,
+        });
+    }
+
+    paclets.sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+
+    Ok(paclets)
+}
+
+/// List the paclets in the immediate subdirectories of `dir`.
+///
+/// Unlike a paclet repository, the subdirectory names do not include the
+/// paclet version, so the name and version are always read from the paclet
+/// info file. Subdirectories whose paclet info file is missing or cannot be
+/// parsed are skipped.
+pub(crate) fn paclets_in_directory(dir: &Path) -> Result<Vec<Paclet>, Error> {
+    let mut paclets = Vec::new();
+
+    for entry in std::fs::read_dir(dir)? {
+        let location = entry?.path();
+
+        let Some(info_file) = paclet_info_file(&location) else {
+            continue;
+        };
+
+        let info = match PacletInfo::from_file(&info_file) {
+            Ok(info) => info,
+            Err(err) => {
+                info!("skipping paclet: {err}");
+                continue;
+            },
+        };
+
+        paclets.push(Paclet {
+            name: info.name.clone(),
+            version: info.version.clone(),
+            location,
+            info: Some(info),
+        });
+    }
+
+    paclets.sort_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+
+    Ok(paclets)
+}
+
+/// Get the `PacletInfo.wl` or `PacletInfo.m` file of the paclet in `dir`, if
+/// present.
+fn paclet_info_file(dir: &Path) -> Option<PathBuf> {
+    ["PacletInfo.wl", "PacletInfo.m"]
+        .into_iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.is_file())
+}
+
+/// Split a repository directory name of the form `<name>-<version>`.
+///
+/// Paclet names can contain `-`, so the version is the text after the last
+/// `-`, and must start with a digit.
+fn parse_repository_directory_name(dir_name: &str) -> Option<(&str, &str)> {
+    let (name, version) = dir_name.rsplit_once('-')?;
+
+    if name.is_empty() || !version.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+
+    Some((name, version))
+}
+
+//======================================
+// Tests
+//======================================
+
+#[test]
+fn test_parse_repository_directory_name() {
+    assert_eq!(
+        parse_repository_directory_name("WolframAlphaClient-1.2.3"),
+        Some(("WolframAlphaClient", "1.2.3"))
+    );
+    assert_eq!(
+        parse_repository_directory_name("Wolfram__Example-Paclet-0.1"),
+        Some(("Wolfram__Example-Paclet", "0.1"))
+    );
+
+    assert_eq!(parse_repository_directory_name("NoVersion"), None);
+    assert_eq!(parse_repository_directory_name("Name-latest"), None);
+    assert_eq!(parse_repository_directory_name("-1.0"), None);
+}
+
+#[test]
+fn test_paclets_in_repository() {
+    let root = crate::test_support::temp_dir("paclet-repository");
+
+    for (dir, info, contents) in [
+        ("Beta-2.0", "PacletInfo.wl", ""),
+        ("Alpha-1.0.1", "PacletInfo.m", ""),
+        ("Gamma-1.0", "README.md", ""),
+        (
+            "Delta__Paclet-3.0",
+            "PacletInfo.wl",
+            r#"PacletObject[<|"Name" -> "Delta/Paclet", "Version" -> "3.0"|>]"#,
+        ),
+    ] {
+        std::fs::create_dir_all(root.join(dir)).unwrap();
+        std::fs::write(root.join(dir).join(info), contents).unwrap();
+    }
+
+    let paclets = paclets_in_repository(&root).unwrap();
+
+    assert_eq!(
+        paclets
+            .iter()
+            .map(|paclet| (paclet.name(), paclet.version()))
+            .collect::<Vec<_>>(),
+        vec![("Alpha", "1.0.1"), ("Beta", "2.0"), ("Delta/Paclet", "3.0")]
+    );
+    assert_eq!(paclets[0].location(), &root.join("Alpha-1.0.1"));
+    assert!(paclets[0].info().is_none());
+    assert_eq!(paclets[2].info().unwrap().name(), "Delta/Paclet");
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn test_parse_legacy_paclet_info() {
+    let info = PacletInfo::parse(
+        r#"
+        (* Legacy paclet info file *)
+        Paclet[
+            Name -> "Legacy",
+            Version -> "0.9.1",
+            MathematicaVersion -> "10+",
+            Extensions -> {
+                {"Kernel", Context -> "Legacy`"},
+                {"LibraryLink"},
+                "Invalid"
+            }
+        ]
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(info.name(), "Legacy");
+    assert_eq!(info.version(), "0.9.1");
+    assert_eq!(info.wolfram_version(), Some("10+"));
+    assert_eq!(info.extensions().len(), 2);
+    assert_eq!(info.extensions()[0].option("Context"), Some("Legacy`"));
+    assert_eq!(info.extensions()[1].kind(), "LibraryLink");
+    assert_eq!(info.extensions()[1].option("Context"), None);
+
+    assert!(PacletInfo::parse("Paclet[Version -> \"1.0\"]").is_err());
+    assert!(PacletInfo::parse("{1, 2}").is_err());
+}
@@ -0,0 +1,168 @@
+//! Generate [pkg-config](https://www.freedesktop.org/wiki/Software/pkg-config/)
+//! files for the Wolfram development SDKs.
+//!
+//! This allows non-Rust build systems that support `pkg-config` (e.g. autotools
+//! and Meson) to use the SDKs of a discovered Wolfram app:
+//!
+//! ```shell
+//! $ wolfram-app-discovery pkg-config --out-dir ./pkgconfig
+//! $ PKG_CONFIG_PATH=./pkgconfig pkg-config --cflags --libs wstp
+//! ```
+//!
+//! SDK locations are determined using the functions in [`build_scripts`], so
+//! the environment variables they support are respected.
+
+use std::fmt::{self, Display};
+
+use crate::{artifacts, build_scripts, Error, WolframApp};
+
+/// Contents of a pkg-config `.pc` file.
+///
+/// Use the [`Display`] implementation to get the file contents.
+#[derive(Debug, Clone)]
+pub struct PkgConfigFile {
+    name: &'static str,
+    description: &'static str,
+    version: String,
+    variables: Vec<(&'static str, String)>,
+    cflags: String,
+    libs: String,
+}
+
+/// Get the pkg-config file for the
+/// [WSTP](https://reference.wolfram.com/language/guide/WSTPAPI.html) static
+/// library.
+///
+/// The returned file is named `wstp.pc`.
+pub fn wstp(app: Option<&WolframApp>) -> Result<PkgConfigFile, Error> {
+    let target_system_id = match app {
+        Some(app) => app.target_system_id()?,
+        None => crate::resolve_target_system_id(None)?,
+    };
+
+    let include_dir =
+        build_scripts::wstp_compiler_additions_directory(app)?.into_path_buf();
+    let library = build_scripts::wstp_static_library_path(app)?.into_path_buf();
+
+    let mut libs = vec!["${library}"];
+    libs.extend(artifacts::wstp_static_library_link_flags(target_system_id)?);
+
+    Ok(PkgConfigFile {
+        name: "wstp",
+        description: "Wolfram Symbolic Transfer Protocol (WSTP) static library",
+        version: version(app),
+        variables: vec![
+            ("includedir", escape(&include_dir.display().to_string())),
+            ("library", escape(&library.display().to_string())),
+        ],
+        cflags: "-I${includedir}".to_owned(),
+        libs: libs.join(" "),
+    })
+}
+
+/// Get the pkg-config file for the
+/// [Wolfram *LibraryLink*](https://reference.wolfram.com/language/guide/LibraryLink.html)
+/// C header files.
+///
+/// The returned file is named `wolfram-librarylink.pc`. *LibraryLink* libraries
+/// are not linked against any Wolfram library, so the `Libs:` field is empty.
+pub fn library_link(app: Option<&WolframApp>) -> Result<PkgConfigFile, Error> {
+    let include_dir =
+        build_scripts::library_link_c_includes_directory(app)?.into_path_buf();
+
+    Ok(PkgConfigFile {
+        name: "wolfram-librarylink",
+        description: "Wolfram LibraryLink C header files",
+        version: version(app),
+        variables: vec![("includedir", escape(&include_dir.display().to_string()))],
+        cflags: "-I${includedir}".to_owned(),
+        libs: String::new(),
+    })
+}
+
+impl PkgConfigFile {
+    /// Package name, e.g. `"wstp"`, as passed to `pkg-config`.
+    pub fn name(&self) -> &str {
+        self.name
+    }
+
+    /// File name of this file, e.g. `"wstp.pc"`.
+    pub fn file_name(&self) -> String {
+        format!("{}.pc", self.name)
+    }
+}
+
+impl Display for PkgConfigFile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let PkgConfigFile {
+            name,
+            description,
+            version,
+            variables,
+            cflags,
+            libs,
+        } = self;
+
+        writeln!(
+            f,
+            "# Generated by wolfram-app-discovery {}. Do not edit.",
+            env!("CARGO_PKG_VERSION")
+        )?;
+        writeln!(f)?;
+
+        for (variable, value) in variables {
+            writeln!(f, "{variable}={value}")?;
+        }
+        writeln!(f)?;
+
+        writeln!(f, "Name: {name}")?;
+        writeln!(f, "Description: {description}")?;
+        writeln!(f, "Version: {version}")?;
+        writeln!(f, "Cflags: {cflags}")?;
+        writeln!(f, "Libs: {libs}")
+    }
+}
+
+/// The `Version:` of the SDKs in `app`.
+///
+/// pkg-config requires that every package has a version, so `0` is used if
+/// the SDKs were not located in an app.
+fn version(app: Option<&WolframApp>) -> String {
+    match app.map(WolframApp::wolfram_version) {
+        Some(Ok(version)) => version.to_string(),
+        Some(Err(_)) | None => "0".to_owned(),
+    }
+}
+
+/// Escape `value` so that it is interpreted as a single argument when
+/// pkg-config splits the `Cflags:` and `Libs:` fields.
+fn escape(value: &str) -> String {
+    let mut escaped = String::new();
+
+    for char in value.chars() {
+        match char {
+            ' ' | '\t' | '\\' | '"' | '\'' => {
+                escaped.push('\\');
+                escaped.push(char);
+            },
+            '$' => escaped.push_str("$$"),
+            char => escaped.push(char),
+        }
+    }
+
+    escaped
+}
+
+//======================================
+// Tests
+//======================================
+
+#[test]
+fn test_escape() {
+    assert_eq!(escape("/usr/local/Wolfram"), "/usr/local/Wolfram");
+    assert_eq!(
+        escape(r"C:\Program Files\Wolfram"),
+        r"C:\\Program\ Files\\Wolfram"
+    );
+    assert_eq!(escape("/a/$b"), "/a/$$b");
+}
@@ -0,0 +1,320 @@
+//! Project-level selection of the Wolfram app to use.
+//!
+//! A project can commit a [`wolfram-app.toml`][FILE_NAME] file to specify the
+//! Wolfram app used by [`WolframApp::try_default()`] in that directory and its
+//! subdirectories, similar to a `rust-toolchain.toml` file:
+//!
+//! ```toml
+//! [app]
+//! # Use the app installed at this location.
+//! directory = "/Applications/Wolfram/Mathematica-14.1.app"
+//!
+//! # Only use apps of these types. Either a single type or an array.
+//! type = ["Mathematica", "Engine"]
+//!
+//! # Only use apps with at least this Wolfram Language version.
+//! min-version = "13.1"
+//! ```
+//!
+//! Every key is optional. A relative `directory` is resolved relative to the
+//! directory containing the `wolfram-app.toml` file. If `directory` is not
+//! specified, `type` and `min-version` restrict the apps that the later
+//! discovery steps of [`WolframApp::try_default()`] may select.
+//!
+//! The file is found by searching the current directory and each of its
+//! ancestors. The `WOLFRAM_APP_DIRECTORY` environment variable takes
+//! precedence over a project configuration file.
+//!
+//! [`WolframApp::try_default()`]: crate::WolframApp::try_default()
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{config, toml, Error, Filter, WolframApp, WolframAppType, WolframVersion};
+
+/// Name of the project configuration file.
+pub const FILE_NAME: &str = "wolfram-app.toml";
+
+/// Contents of a [`wolfram-app.toml`][FILE_NAME] project configuration file.
+#[derive(Debug, Clone)]
+pub struct ProjectConfig {
+    path: PathBuf,
+    app_directory: Option<PathBuf>,
+    app_types: Option<Vec<WolframAppType>>,
+    min_version: Option<WolframVersion>,
+}
+
+//======================================
+// Impls
+//======================================
+
+impl ProjectConfig {
+    /// Find the project configuration file that applies to the current
+    /// directory, if any.
+    pub fn find() -> Result<Option<Self>, Error> {
+        match std::env::current_dir() {
+            Ok(dir) => ProjectConfig::find_from(&dir),
+            Err(_) => Ok(None),
+        }
+    }
+
+    /// Find the project configuration file that applies to `dir`, by searching
+    /// `dir` and each of its ancestors.
+    pub fn find_from(dir: &Path) -> Result<Option<Self>, Error> {
+        for dir in dir.ancestors() {
+            let path = dir.join(FILE_NAME);
+
+            if path.is_file() {
+                return ProjectConfig::from_file(path).map(Some);
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Read the project configuration file at `path`.
+    pub fn from_file(path: PathBuf) -> Result<Self, Error> {
+        config::print_cargo_directive(&format!(
+            "cargo:rerun-if-changed={}",
+            path.display()
+        ));
+
+        let contents = fs::read_to_string(&path)?;
+
+        ProjectConfig::parse(path, &contents)
+    }
+
+    /// Write a project configuration file into `dir` that selects `app`,
+    /// replacing any existing configuration file in `dir`.
+    pub fn pin(dir: &Path, app: &WolframApp) -> Result<Self, Error> {
+        let config = ProjectConfig {
+            path: dir.join(FILE_NAME),
+            app_directory: Some(app.app_directory()),
+            app_types: None,
+            min_version: None,
+        };
+
+        fs::write(&config.path, config.to_toml())?;
+
+        Ok(config)
+    }
+
+    /// Location of the configuration file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The app directory specified by this configuration, if any.
+    ///
+    /// A relative path in the file is resolved relative to the directory
+    /// containing the configuration file.
+    pub fn app_directory(&self) -> Option<&Path> {
+        self.app_directory.as_deref()
+    }
+
+    /// The app types allowed by this configuration, if restricted.
+    pub fn app_types(&self) -> Option<&[WolframAppType]> {
+        self.app_types.as_deref()
+    }
+
+    /// The minimum Wolfram Language version allowed by this configuration, if
+    /// any.
+    pub fn min_version(&self) -> Option<&WolframVersion> {
+        self.min_version.as_ref()
+    }
+
+    /// Combine the restrictions specified by this configuration with `filter`.
+    pub(crate) fn restrict(&self, filter: &Filter) -> Filter {
+        let mut filter = filter.clone();
+
+        if let Some(app_types) = &self.app_types {
+            filter.app_types = Some(match filter.app_types {
+                Some(existing) => existing
+                    .into_iter()
+                    .filter(|app_type| app_types.contains(app_type))
+                    .collect(),
+                None => app_types.clone(),
+            });
+        }
+
+        if let Some(min_version) = &self.min_version {
+            filter.min_version = Some(match filter.min_version {
+                Some(existing) => existing.max(min_version.clone()),
+                None => min_version.clone(),
+            });
+        }
+
+        filter
+    }
+
+    fn parse(path: PathBuf, contents: &str) -> Result<Self, Error> {
+        let invalid = |message: String| {
+            Error::other(format!("invalid {}: {message}", path.display()))
+        };
+
+        let document =
+            toml::Document::parse(contents).map_err(|err| invalid(err.to_string()))?;
+
+        for (table, key) in document.keys() {
+            if !matches!((table, key), ("app", "directory" | "type" | "min-version")) {
+                crate::warning(&format!(
+                    "ignoring unrecognized key in {}: {table}.{key}",
+                    path.display()
+                ));
+            }
+        }
+
+        let app_directory = match document.get("app", "directory") {
+            Some(value) => {
+                let dir = value.as_str().ok_or_else(|| {
+                    invalid("expected string value for app.directory".to_owned())
+                })?;
+
+                let base = path.parent().unwrap_or(Path::new(""));
+
+                Some(base.join(dir))
+            },
+            None => None,
+        };
+
+        let app_types = match document.get("app", "type") {
+            Some(value) => {
+                let names: Vec<&str> = match value {
+                    toml::Value::String(name) => vec![name],
+                    toml::Value::Array(array) => array
+                        .iter()
+                        .map(toml::Value::as_str)
+                        .collect::<Option<_>>()
+                        .ok_or_else(|| {
+                            invalid("expected array of strings for app.type".to_owned())
+                        })?,
+                    _ => {
+                        return Err(invalid(
+                            "expected string or array value for app.type".to_owned(),
+                        ))
+                    },
+                };
+
+                let app_types = names
+                    .into_iter()
+                    .map(|name| {
+                        parse_app_type(name).ok_or_else(|| {
+                            invalid(format!("unrecognized app type: {name:?}"))
+                        })
+                    })
+                    .collect::<Result<_, _>>()?;
+
+                Some(app_types)
+            },
+            None => None,
+        };
+
+        let min_version = match document.get("app", "min-version") {
+            Some(value) => {
+                let version = value.as_str().ok_or_else(|| {
+                    invalid("expected string value for app.min-version".to_owned())
+                })?;
+
+                let version = WolframVersion::parse(version).ok_or_else(|| {
+                    invalid(format!("invalid Wolfram Language version: {version:?}"))
+                })?;
+
+                Some(version)
+            },
+            None => None,
+        };
+
+        Ok(ProjectConfig {
+            path,
+            app_directory,
+            app_types,
+            min_version,
+        })
+    }
+
+    fn to_toml(&self) -> String {
+        let mut contents = String::from(
+            "# Wolfram app used by wolfram-app-discovery in this directory tree.\n\
+            # Written by `wolfram-app-discovery pin`.\n",
+        );
+
+        if let Some(dir) = &self.app_directory {
+            contents.push_str("\n[app]\n");
+            contents.push_str(&format!(
+                "directory = {}\n",
+                toml::quote(&dir.display().to_string())
+            ));
+        }
+
+        contents
+    }
+}
+
+/// Parse an app type name like `Mathematica` or `Engine`, ignoring case.
+fn parse_app_type(name: &str) -> Option<WolframAppType> {
+    WolframAppType::variants()
+        .into_iter()
+        .find(|app_type| format!("{app_type:?}").eq_ignore_ascii_case(name))
+}
+
+//======================================
+// Tests
+//======================================
+
+#[test]
+fn test_parse_project_config() {
+    let base = Path::new("/projects/example");
+
+    let config = ProjectConfig::parse(
+        base.join(FILE_NAME),
+        "[app]\ndirectory = \"../Mathematica/14.1\"\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+        config.app_directory(),
+        Some(base.join("../Mathematica/14.1").as_path())
+    );
+
+    let config = ProjectConfig::parse(base.join(FILE_NAME), "# empty\n").unwrap();
+    assert_eq!(config.app_directory(), None);
+
+    assert!(
+        ProjectConfig::parse(base.join(FILE_NAME), "[app]\ndirectory = 1\n").is_err()
+    );
+
+    let config = ProjectConfig::parse(
+        base.join(FILE_NAME),
+        "[app]\ntype = [\"Mathematica\", \"engine\"]\nmin-version = \"13.1\"\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+        config.app_types(),
+        Some(&[WolframAppType::Mathematica, WolframAppType::Engine][..])
+    );
+    assert_eq!(config.min_version(), Some(&WolframVersion::new(13, 1, 0)));
+
+    let filter = config.restrict(&Filter {
+        app_types: Some(vec![WolframAppType::Engine, WolframAppType::Player]),
+        min_version: Some(WolframVersion::new(12, 0, 0)),
+        ..Filter::allow_all()
+    });
+    assert_eq!(filter.app_types, Some(vec![WolframAppType::Engine]));
+    assert_eq!(filter.min_version, Some(WolframVersion::new(13, 1, 0)));
+
+    assert!(
+        ProjectConfig::parse(base.join(FILE_NAME), "[app]\ntype = \"Foo\"\n").is_err()
+    );
+
+    let written = ProjectConfig {
+        path: base.join(FILE_NAME),
+        app_directory: Some(PathBuf::from("/usr/local/Wolfram/Mathematica/14.1")),
+        app_types: None,
+        min_version: None,
+    };
+    let parsed = ProjectConfig::parse(base.join(FILE_NAME), &written.to_toml()).unwrap();
+    assert_eq!(parsed.app_directory(), written.app_directory());
+}
@@ -0,0 +1,249 @@
+//! Properties of a Wolfram app that can be queried by the
+//! `wolfram-app-discovery` command-line tool.
+//!
+//! Each [`Property`] has a stable machine name (e.g. `"app-type"`), which is
+//! the name accepted by the `--property` and `--raw-value` options of the
+//! command-line tool. Use [`Property::iter()`] to enumerate the supported
+//! properties.
+
+use std::{
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+use crate::Error;
+
+/// A property of a Wolfram installation that can be discovered.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum Property {
+    /// [`WolframAppType`] value describing the installation.
+    ///
+    /// [`WolframAppType`]: crate::WolframAppType
+    AppType,
+
+    /// Product name of the installation, as reported by the operating system.
+    AppName,
+
+    /// Full [`AppVersion`] value of the installation, including the minor
+    /// revision and build code components, if known.
+    ///
+    /// [`AppVersion`]: crate::AppVersion
+    AppVersion,
+
+    /// Application directory of the installation.
+    AppDirectory,
+
+    /// Location of the application's main executable.
+    AppExecutablePath,
+
+    /// [`WolframVersion`] value of the installation.
+    ///
+    /// [`WolframVersion`]: crate::WolframVersion
+    WolframVersion,
+
+    /// [`$InstallationDirectory`] value of the installation.
+    ///
+    /// [`$InstallationDirectory`]: https://reference.wolfram.com/language/ref/$InstallationDirectory
+    InstallationDirectory,
+
+    /// [`SystemID`] of the installation.
+    ///
+    /// [`SystemID`]: crate::SystemID
+    SystemId,
+
+    /// Wolfram *LibraryLink* C includes directory
+    LibraryLinkCIncludesDirectory,
+
+    /// Location of the [`WolframKernel`] executable.
+    ///
+    /// [`WolframKernel`]: https://reference.wolfram.com/language/ref/program/WolframKernel.html
+    KernelExecutablePath,
+
+    /// Location of the [`wolframscript`] executable.
+    ///
+    /// [`wolframscript`]: https://reference.wolfram.com/language/ref/program/wolframscript.html
+    WolframScriptExecutablePath,
+
+    /// Location of the WSTP SDK 'CompilerAdditions' directory.
+    WstpCompilerAdditionsDirectory,
+
+    /// Location of the WSTP SDK `wstp.h` C header file.
+    WstpCHeaderPath,
+
+    /// Location of the WSTP SDK static library.
+    WstpStaticLibraryPath,
+
+    /// Date on which the earliest expiring license of the installation
+    /// expires, or `never` if the installed licenses do not expire.
+    ///
+    /// See [`license_expiration()`].
+    ///
+    /// [`license_expiration()`]: crate::license::license_expiration
+    LicenseExpiration,
+    // NOTE: When adding a new variant here, be sure to update VARIANTS.
+}
+
+/// All [`Property`] values, in the order they are shown by the command-line
+/// tool.
+const VARIANTS: &[Property] = &[
+    Property::AppType,
+    Property::AppName,
+    Property::AppVersion,
+    Property::WolframVersion,
+    Property::AppDirectory,
+    Property::AppExecutablePath,
+    Property::InstallationDirectory,
+    Property::SystemId,
+    Property::KernelExecutablePath,
+    Property::WolframScriptExecutablePath,
+    Property::WstpCompilerAdditionsDirectory,
+    Property::WstpCHeaderPath,
+    Property::WstpStaticLibraryPath,
+    Property::LibraryLinkCIncludesDirectory,
+    Property::LicenseExpiration,
+];
+
+impl Property {
+    /// Get all [`Property`] values, in the order they are shown by the
+    /// command-line tool.
+    pub const fn variants() -> &'static [Property] {
+        VARIANTS
+    }
+
+    /// Iterate over all [`Property`] values, in [`Property::variants()`]
+    /// order.
+    pub fn iter() -> impl Iterator<Item = Property> {
+        Property::variants().iter().copied()
+    }
+
+    /// Stable kebab-case machine name of this property.
+    ///
+    /// ```
+    /// use wolfram_app_discovery::property::Property;
+    ///
+    /// assert_eq!(Property::AppType.name(), "app-type");
+    /// ```
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Property::AppType => "app-type",
+            Property::AppName => "app-name",
+            Property::AppVersion => "app-version",
+            Property::AppDirectory => "app-directory",
+            Property::AppExecutablePath => "app-executable-path",
+            Property::WolframVersion => "wolfram-version",
+            Property::InstallationDirectory => "installation-directory",
+            Property::SystemId => "system-id",
+            Property::LibraryLinkCIncludesDirectory => {
+                "library-link-c-includes-directory"
+            },
+            Property::KernelExecutablePath => "kernel-executable-path",
+            Property::WolframScriptExecutablePath => "wolfram-script-executable-path",
+            Property::WstpCompilerAdditionsDirectory => {
+                "wstp-compiler-additions-directory"
+            },
+            Property::WstpCHeaderPath => "wstp-c-header-path",
+            Property::WstpStaticLibraryPath => "wstp-static-library-path",
+            Property::LicenseExpiration => "license-expiration",
+        }
+    }
+
+    /// Get the [`Property`] with the specified [machine name][Property::name].
+    pub fn from_name(name: &str) -> Option<Property> {
+        Property::iter().find(|property| property.name() == name)
+    }
+
+    /// Human-readable label for this property, e.g. `"App type"`.
+    ///
+    /// This is the same as the [`Display`] representation of this property.
+    pub const fn label(&self) -> &'static str {
+        match self {
+            Property::AppType => "App type",
+            Property::AppName => "App name",
+            Property::AppVersion => "App version",
+            Property::WolframVersion => "Wolfram Language version",
+            Property::AppDirectory => "Application directory",
+            Property::AppExecutablePath => "Application executable",
+            Property::InstallationDirectory => "$InstallationDirectory",
+            Property::SystemId => "SystemID",
+            Property::KernelExecutablePath => "WolframKernel executable",
+            Property::WolframScriptExecutablePath => "wolframscript executable",
+            Property::WstpCompilerAdditionsDirectory => {
+                "WSTP CompilerAdditions directory"
+            },
+            Property::WstpCHeaderPath => "WSTP C header",
+            Property::WstpStaticLibraryPath => "WSTP static library",
+            Property::LibraryLinkCIncludesDirectory => "LibraryLink C includes directory",
+            Property::LicenseExpiration => "License expiration",
+        }
+    }
+}
+
+impl FromStr for Property {
+    type Err = Error;
+
+    /// Parse a property [machine name][Property::name].
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Property::from_name(name)
+            .ok_or_else(|| Error::other(format!("unknown property name: {name:?}")))
+    }
+}
+
+impl Display for Property {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+//======================================
+// Tests
+//======================================
+
+#[test]
+fn test_property_names() {
+    for property in Property::iter() {
+        assert_eq!(Property::from_name(property.name()), Some(property));
+    }
+
+    assert_eq!(Property::iter().count(), Property::variants().len());
+    assert_eq!(
+        "wstp-compiler-additions-directory".parse(),
+        Ok(Property::WstpCompilerAdditionsDirectory)
+    );
+    assert_eq!(Property::from_name("AppType"), None);
+    assert_eq!(
+        "AppType".parse::<Property>().unwrap_err().to_string(),
+        "Wolfram app error: unknown property name: \"AppType\""
+    );
+}
+
+#[test]
+fn test_property_variants() {
+    // NOTE: Whenever this match causes a compile time failure because a
+    //       variant has been added, add the new variant to VARIANTS.
+    for property in VARIANTS {
+        match property {
+            Property::AppType
+            | Property::AppName
+            | Property::AppVersion
+            | Property::WolframVersion
+            | Property::AppDirectory
+            | Property::AppExecutablePath
+            | Property::InstallationDirectory
+            | Property::SystemId
+            | Property::KernelExecutablePath
+            | Property::WolframScriptExecutablePath
+            | Property::WstpCompilerAdditionsDirectory
+            | Property::WstpCHeaderPath
+            | Property::WstpStaticLibraryPath
+            | Property::LibraryLinkCIncludesDirectory
+            | Property::LicenseExpiration => (),
+        }
+    }
+
+    // VARIANTS does not contain duplicates.
+    let unique: std::collections::HashSet<&Property> = VARIANTS.iter().collect();
+
+    assert_eq!(unique.len(), VARIANTS.len());
+}
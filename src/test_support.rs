@@ -0,0 +1,206 @@
+//! Utilities for testing discovery code paths without a Wolfram installation.
+//!
+//! Tests run in parallel within a single process, so these utilities never
+//! modify process-wide state:
+//!
+//! * [`EnvVarOverride`] changes the value of an environment variable as seen by
+//!   this library on the current thread only.
+//! * [`temp_dir()`] creates a directory that is unique to the calling test.
+//!
+//! [`FakeWolframScript`] relies on `/bin/sh`, and so is only available on Unix
+//! platforms.
+
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    ffi::{OsStr, OsString},
+    path::PathBuf,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+//======================================
+// Environment variables
+//======================================
+
+thread_local! {
+    /// Environment variable values set by [`EnvVarOverride`] on this thread.
+    ///
+    /// A value of `None` means the variable is treated as unset.
+    static ENV_OVERRIDES: RefCell<HashMap<&'static str, Option<OsString>>> =
+        RefCell::new(HashMap::new());
+}
+
+/// Get the value of `var` set by an [`EnvVarOverride`] on the current thread.
+///
+/// Returns `None` if `var` has not been overridden, and `Some(None)` if it has
+/// been overridden to be unset.
+pub(crate) fn env_var_override(var: &str) -> Option<Option<OsString>> {
+    ENV_OVERRIDES.with(|overrides| overrides.borrow().get(var).cloned())
+}
+
+/// Override of the value of an environment variable, as seen by
+/// [`config::env_var_os()`][crate::config::env_var_os], on the current thread.
+///
+/// When this value is dropped, the previous override (if any) is restored.
+pub(crate) struct EnvVarOverride {
+    var: &'static str,
+    previous: Option<Option<OsString>>,
+}
+
+impl EnvVarOverride {
+    /// Override `var` to be unset.
+    pub fn unset(var: &'static str) -> Self {
+        let previous =
+            ENV_OVERRIDES.with(|overrides| overrides.borrow_mut().insert(var, None));
+
+        EnvVarOverride { var, previous }
+    }
+
+    /// Override `var` to have the specified value.
+    pub fn with_value<V: AsRef<OsStr>>(var: &'static str, value: V) -> Self {
+        let mut this = EnvVarOverride::unset(var);
+        this.set(value);
+        this
+    }
+
+    /// Change the overridden value of this variable.
+    pub fn set<V: AsRef<OsStr>>(&mut self, value: V) {
+        let value = value.as_ref().to_owned();
+
+        ENV_OVERRIDES
+            .with(|overrides| overrides.borrow_mut().insert(self.var, Some(value)));
+    }
+}
+
+impl Drop for EnvVarOverride {
+    fn drop(&mut self) {
+        ENV_OVERRIDES.with(|overrides| {
+            let mut overrides = overrides.borrow_mut();
+
+            match self.previous.take() {
+                Some(previous) => overrides.insert(self.var, previous),
+                None => overrides.remove(self.var),
+            };
+        })
+    }
+}
+
+//======================================
+// Temporary directories
+//======================================
+
+/// Create a new, empty temporary directory for use by a single test.
+///
+/// The directory name includes `name`, the current process ID, and a counter,
+/// so that tests running concurrently in this or another process never share a
+/// directory.
+pub(crate) fn temp_dir(name: &str) -> PathBuf {
+    static COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+    let dir = std::env::temp_dir().join(format!(
+        "wolfram-app-discovery-test-{name}-{}-{}",
+        std::process::id(),
+        COUNTER.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+
+    dir
+}
+
+//======================================
+// Fake wolframscript
+//======================================
+
+/// How a fake `wolframscript` responds to `-code` evaluation requests.
+#[cfg(unix)]
+#[derive(Debug, Clone)]
+pub(crate) enum FakeBehavior {
+    /// Print the specified `$InstallationDirectory` value and exit successfully.
+    InstallationDirectory(PathBuf),
+    /// Exit with the specified exit code, without printing anything.
+    ExitCode(i32),
+    /// Never exit.
+    Hang,
+}
+
+/// A fake `wolframscript` executable that has been prepended to `PATH`.
+///
+/// While this value is alive, the `PATH` seen by this library on the current
+/// thread begins with a directory containing the fake `wolframscript`. The
+/// `PATH` of the process itself is not modified.
+#[cfg(unix)]
+pub(crate) struct FakeWolframScript {
+    dir: PathBuf,
+    _path: EnvVarOverride,
+}
+
+#[cfg(unix)]
+impl FakeWolframScript {
+    /// Write a fake `wolframscript` with the specified behavior, and prepend
+    /// its directory to `PATH`.
+    ///
+    /// `name` is included in the name of the temporary directory containing
+    /// the fake.
+    pub fn install(name: &str, behavior: FakeBehavior) -> Self {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = temp_dir(&format!("fake-wolframscript-{name}"));
+
+        let script = dir.join("wolframscript");
+        std::fs::write(&script, script_contents(&behavior)).unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755))
+            .unwrap();
+
+        let mut paths = vec![dir.clone()];
+        if let Some(original) = crate::config::env_var_os("PATH") {
+            paths.extend(std::env::split_paths(&original));
+        }
+
+        let path =
+            EnvVarOverride::with_value("PATH", std::env::join_paths(paths).unwrap());
+
+        FakeWolframScript { dir, _path: path }
+    }
+
+    /// Location of the fake `wolframscript` executable.
+    pub fn path(&self) -> PathBuf {
+        self.dir.join("wolframscript")
+    }
+}
+
+#[cfg(unix)]
+impl Drop for FakeWolframScript {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// Contents of a `/bin/sh` script that behaves like `wolframscript`.
+///
+/// `wolframscript -h` always succeeds, so that the fake is detected as being
+/// present on `PATH`.
+#[cfg(unix)]
+fn script_contents(behavior: &FakeBehavior) -> String {
+    let code_action = match behavior {
+        FakeBehavior::InstallationDirectory(dir) => {
+            format!("echo '{}'\n    exit 0", dir.display())
+        },
+        FakeBehavior::ExitCode(code) => format!("exit {code}"),
+        FakeBehavior::Hang => "exec sleep 1000".to_owned(),
+    };
+
+    format!(
+        "#!/bin/sh
+if [ \"$1\" = \"-h\" ]; then
+    echo 'usage: wolframscript [options]'
+    exit 0
+fi
+if [ \"$1\" = \"-code\" ]; then
+    {code_action}
+fi
+exit 1
+"
+    )
+}
@@ -1,4 +1,39 @@
-use crate::WolframVersion;
+use std::path::PathBuf;
+
+use crate::{AppVersion, WolframApp, WolframAppType, WolframVersion};
+
+/// Construct a [`WolframApp`] for testing purposes, without requiring that any
+/// files exist at `app_directory`.
+pub(crate) fn test_app(
+    app_type: WolframAppType,
+    version: (u32, u32, u32),
+    app_directory: &str,
+) -> WolframApp {
+    let (major, minor, revision) = version;
+
+    WolframApp {
+        app_name: app_type.app_name().to_owned(),
+        app_type,
+        app_version: AppVersion {
+            major,
+            minor,
+            revision,
+            minor_revision: None,
+            build_code: None,
+        },
+        app_directory: PathBuf::from(app_directory),
+        app_executable: None,
+        embedded_player: None,
+        windows_caps: None,
+        installation_id: None,
+        target_system_id: None,
+        front_end_preference: None,
+        timeout: None,
+        offline: false,
+        wolframscript_invocation: None,
+        registration: None,
+    }
+}
 
 #[test]
 fn test_wolfram_version_ordering() {
@@ -15,3 +50,1651 @@ fn test_wolfram_version_ordering() {
     assert!(v13_3_0 > v13_2_0);
     assert!(v13_3_0 > v13_2_1);
 }
+
+#[test]
+fn test_wolfram_version_from_str() {
+    let parse = |version: &str| version.parse::<WolframVersion>().ok();
+
+    assert_eq!(parse("13.3.1"), Some(WolframVersion::new(13, 3, 1)));
+    assert_eq!(parse("14.0"), Some(WolframVersion::new(14, 0, 0)));
+    assert_eq!(parse("14"), Some(WolframVersion::new(14, 0, 0)));
+
+    assert_eq!(parse(""), None);
+    assert_eq!(parse("14."), None);
+    assert_eq!(parse("14.0.0.1"), None);
+    assert_eq!(parse("v14"), None);
+}
+
+#[test]
+fn test_wolfram_version_from_version_number() {
+    let from = WolframVersion::from_version_number;
+
+    assert_eq!(from(13.3, 1), Some(WolframVersion::new(13, 3, 1)));
+    assert_eq!(from(13.0, 2), Some(WolframVersion::new(13, 0, 2)));
+    assert_eq!(from(12.1, 0), Some(WolframVersion::new(12, 1, 0)));
+
+    assert_eq!(from(-13.3, 0), None);
+    assert_eq!(from(f64::NAN, 0), None);
+    assert_eq!(from(f64::INFINITY, 0), None);
+
+    let parse = WolframVersion::parse_version_number;
+
+    assert_eq!(parse("13.", 0), Some(WolframVersion::new(13, 0, 0)));
+    assert_eq!(parse("13", 0), Some(WolframVersion::new(13, 0, 0)));
+    assert_eq!(parse("13.3", 1), Some(WolframVersion::new(13, 3, 1)));
+    assert_eq!(parse(".3", 0), None);
+}
+
+#[test]
+fn test_app_version_parse() {
+    let version = AppVersion::parse("13.3.0.202302011100").unwrap();
+    assert_eq!(version.build_code(), Some(202302011100));
+    assert_eq!(version.minor_revision(), None);
+
+    let version = AppVersion::parse("13.2.1.0.5829781").unwrap();
+    assert_eq!(version.minor_revision(), Some(0));
+    assert_eq!(version.build_code(), Some(5829781));
+
+    assert_eq!(AppVersion::parse("14.0.0").unwrap().build_code(), None);
+    assert!(AppVersion::parse("14.0.0.x").is_err());
+}
+
+#[test]
+fn test_app_version_display_and_ordering() {
+    let parse = |version: &str| version.parse::<AppVersion>().unwrap();
+
+    for version in ["13.2.0", "13.3.0.202302011100", "13.2.1.0.5829781"] {
+        assert_eq!(parse(version).to_string(), version);
+    }
+
+    assert!(parse("13.2.0") < parse("13.2.1"));
+    assert!(parse("13.2.1") < parse("13.3.0"));
+    assert!(parse("13.2.0") < parse("13.2.0.5829781"));
+    assert!(parse("13.2.0.0.5829781") < parse("13.2.0.1.5829781"));
+    assert_eq!(parse("14.0.0"), parse("14.0.0"));
+
+    assert!("14.0".parse::<AppVersion>().is_err());
+}
+
+#[test]
+fn test_sort_strategy() {
+    use crate::SortStrategy;
+
+    let mut apps = [
+        test_app(WolframAppType::Engine, (13, 3, 0), "/E-13"),
+        test_app(WolframAppType::Player, (14, 0, 0), "/P-14"),
+        test_app(WolframAppType::Engine, (14, 0, 0), "/E-14"),
+        test_app(WolframAppType::Mathematica, (13, 3, 0), "/M-13"),
+    ];
+
+    let mut ranked = |strategy: SortStrategy| -> Vec<String> {
+        apps.sort_by(|a, b| strategy.compare(a, b));
+        apps.iter()
+            .map(|app| app.app_directory().to_str().unwrap().to_owned())
+            .collect()
+    };
+
+    assert_eq!(
+        ranked(SortStrategy::PreferNewest),
+        ["/E-14", "/P-14", "/M-13", "/E-13"]
+    );
+
+    assert_eq!(
+        ranked(SortStrategy::PreferFrontEnd),
+        ["/P-14", "/M-13", "/E-14", "/E-13"]
+    );
+
+    assert_eq!(
+        ranked(SortStrategy::PreferEngine),
+        ["/E-14", "/E-13", "/P-14", "/M-13"]
+    );
+
+    assert_eq!(
+        ranked(SortStrategy::Custom(|a, b| {
+            a.app_directory().cmp(&b.app_directory())
+        })),
+        ["/E-13", "/E-14", "/M-13", "/P-14"]
+    );
+}
+
+#[test]
+fn test_is_prerelease() {
+    use crate::SortStrategy;
+
+    let released = test_app(WolframAppType::Mathematica, (14, 0, 0), "/M-14");
+    let prototype = test_app(WolframAppType::Mathematica, (14, 0, 0), "/M-14-Prototype");
+    let nightly = test_app(
+        WolframAppType::Engine,
+        (14, 1, 0),
+        "/Wolfram Engine Nightly",
+    );
+    let zero = test_app(WolframAppType::Mathematica, (0, 0, 0), "/M-0");
+
+    assert!(!released.is_prerelease());
+    assert!(prototype.is_prerelease());
+    assert!(nightly.is_prerelease());
+    assert!(zero.is_prerelease());
+
+    let mut apps = [prototype, released, nightly];
+
+    apps.sort_by(|a, b| SortStrategy::PreferNewest.compare(a, b));
+    assert_eq!(
+        apps.iter()
+            .map(|app| app.app_directory())
+            .collect::<Vec<_>>(),
+        ["/Wolfram Engine Nightly", "/M-14", "/M-14-Prototype"].map(PathBuf::from)
+    );
+
+    apps.sort_by(|a, b| SortStrategy::PreferReleased.compare(a, b));
+    assert_eq!(
+        apps.iter()
+            .map(|app| app.app_directory())
+            .collect::<Vec<_>>(),
+        ["/M-14", "/Wolfram Engine Nightly", "/M-14-Prototype"].map(PathBuf::from)
+    );
+}
+
+#[test]
+fn test_app_display() {
+    let app = test_app(WolframAppType::Engine, (13, 3, 0), "/opt/Wolfram/Engine");
+
+    assert_eq!(
+        app.to_string(),
+        "Wolfram Engine 13.3.0 at /opt/Wolfram/Engine"
+    );
+
+    assert_eq!(
+        app.summary(),
+        "Wolfram Engine 13.3.0 at /opt/Wolfram/Engine\n\
+        App type:                 Wolfram Engine\n\
+        App name:                 Wolfram Engine\n\
+        App version:              13.3.0\n\
+        Wolfram Language version: 13.3.0\n\
+        Application directory:    /opt/Wolfram/Engine"
+    );
+}
+
+#[test]
+fn test_discovery_warnings() {
+    use crate::{discovery_warnings, record_platform_unimplemented_warning};
+
+    record_platform_unimplemented_warning("test operation");
+    record_platform_unimplemented_warning("test operation");
+
+    let count = discovery_warnings()
+        .iter()
+        .filter(|warning| warning.to_string().contains("'test operation'"))
+        .count();
+
+    assert_eq!(count, 1);
+}
+
+#[test]
+fn test_windows_arm_system_ids() {
+    use crate::SystemID;
+
+    for (system_id, string, rust_target) in [
+        (
+            SystemID::Windows_ARM64,
+            "Windows-ARM64",
+            "aarch64-pc-windows-msvc",
+        ),
+        (
+            SystemID::Windows_ARM,
+            "Windows-ARM",
+            "thumbv7a-pc-windows-msvc",
+        ),
+    ] {
+        assert_eq!(system_id.as_str(), string);
+        assert_eq!(string.parse::<SystemID>(), Ok(system_id));
+        assert_eq!(
+            SystemID::try_from_rust_target(rust_target).unwrap(),
+            system_id
+        );
+    }
+
+    assert_eq!(
+        crate::artifacts::wstp_static_library_name(SystemID::Windows_ARM64).unwrap(),
+        "wstp64i4s.lib"
+    );
+}
+
+#[test]
+fn test_system_id_all() {
+    use crate::{OperatingSystem, SystemID};
+
+    for (index, system_id) in SystemID::all().iter().enumerate() {
+        assert_eq!(system_id.as_str().parse::<SystemID>(), Ok(*system_id));
+        assert!(!SystemID::all()[..index].contains(system_id));
+    }
+
+    for (rust_target, arch, os) in [
+        ("x86_64-unknown-linux-gnu", "x86_64", OperatingSystem::Linux),
+        ("aarch64-apple-darwin", "aarch64", OperatingSystem::MacOS),
+        ("i686-pc-windows-msvc", "x86", OperatingSystem::Windows),
+        (
+            "armv7-unknown-linux-gnueabihf",
+            "arm",
+            OperatingSystem::Linux,
+        ),
+        ("aarch64-linux-android", "aarch64", OperatingSystem::Other),
+    ] {
+        let system_id = SystemID::try_from_rust_target(rust_target).unwrap();
+
+        assert_eq!(system_id.arch(), arch);
+        assert_eq!(system_id.operating_system(), os);
+    }
+}
+
+#[test]
+fn test_resolve_target_system_id() {
+    use crate::{resolve_target_system_id, test_support::EnvVarOverride, SystemID};
+
+    let mut target = EnvVarOverride::unset("WOLFRAM_TARGET_SYSTEM_ID");
+
+    assert_eq!(
+        resolve_target_system_id(None).unwrap(),
+        SystemID::current_rust_target()
+    );
+
+    target.set("Linux-ARM");
+
+    assert_eq!(resolve_target_system_id(None).unwrap(), SystemID::Linux_ARM);
+
+    // An explicitly specified SystemID takes precedence over the environment.
+    assert_eq!(
+        resolve_target_system_id(Some(SystemID::MacOSX_ARM64)).unwrap(),
+        SystemID::MacOSX_ARM64
+    );
+
+    target.set("Not-A-SystemID");
+
+    assert!(resolve_target_system_id(None).is_err());
+}
+
+#[test]
+fn test_run_backends_preserves_order() {
+    use crate::os::run_backends;
+
+    let backends = || {
+        (0..8).map(|index| {
+            move || {
+                let dir = format!("/Wolfram/{index}");
+                vec![test_app(WolframAppType::Engine, (13, index, 0), &dir)]
+            }
+        })
+    };
+
+    let sequential = run_backends(false, backends());
+    let parallel = run_backends(true, backends());
+
+    let dirs = |apps: &[WolframApp]| -> Vec<PathBuf> {
+        apps.iter().map(WolframApp::app_directory).collect()
+    };
+
+    assert_eq!(sequential.len(), 8);
+    assert_eq!(dirs(&sequential), dirs(&parallel));
+}
+
+#[test]
+#[cfg(unix)]
+fn test_command_output_timeout() {
+    use std::{process::Command, time::Duration};
+
+    let mut command = Command::new("sleep");
+    command.arg("10");
+
+    let err = crate::command_output(command, Some(Duration::from_millis(100)))
+        .expect_err("expected command to time out");
+
+    assert_eq!(err.kind(), std::io::ErrorKind::TimedOut);
+
+    let mut command = Command::new("echo");
+    command.arg("hello");
+
+    let output = crate::command_output(command, Some(Duration::from_secs(10))).unwrap();
+
+    assert_eq!(output.stdout, b"hello\n");
+}
+
+#[test]
+fn test_dedup_apps() {
+    let root = crate::test_support::temp_dir("dedup-apps");
+
+    let app_dir = root.join("Mathematica");
+    std::fs::create_dir_all(&app_dir).unwrap();
+
+    let mathematica = app_dir.to_str().unwrap();
+    // A non-canonical path to the same directory.
+    let mathematica_2 = root.join(".").join("Mathematica");
+    let mathematica_2 = mathematica_2.to_str().unwrap();
+    let engine = root.join("WolframEngine");
+    let engine = engine.to_str().unwrap();
+
+    let mut apps = vec![
+        test_app(WolframAppType::Mathematica, (13, 3, 0), mathematica),
+        test_app(WolframAppType::Engine, (13, 3, 0), engine),
+        test_app(WolframAppType::Mathematica, (13, 3, 0), mathematica_2),
+    ];
+
+    assert_eq!(apps[0], apps[2]);
+    assert_ne!(apps[0], apps[1]);
+
+    crate::dedup_apps(&mut apps);
+
+    assert_eq!(
+        apps.iter()
+            .map(WolframApp::app_directory)
+            .collect::<Vec<_>>(),
+        vec![PathBuf::from(mathematica), PathBuf::from(engine)]
+    );
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn test_windows_caps() {
+    use crate::WindowsCaps;
+
+    let caps = WindowsCaps::from_bits(0b1010_0001);
+
+    assert_eq!(caps.bits(), 0b1010_0001);
+    assert_eq!(caps.set_bits().collect::<Vec<u32>>(), [0, 5, 7]);
+    assert!(caps.contains(WindowsCaps::from_bits(0b1000_0001)));
+    assert!(!caps.contains(WindowsCaps::from_bits(0b0000_0010)));
+    assert!(caps.contains(WindowsCaps::default()));
+}
+
+#[test]
+fn test_registration_removal_command() {
+    use crate::Registration;
+
+    let registration = Registration::WindowsRegistryKey {
+        key: r"HKEY_LOCAL_MACHINE\Software\Wolfram Research\Installations\1234"
+            .to_owned(),
+        wow64_32: true,
+    };
+
+    assert_eq!(
+        registration.removal_command(),
+        [
+            "reg",
+            "delete",
+            r"HKEY_LOCAL_MACHINE\Software\Wolfram Research\Installations\1234",
+            "/f",
+            "/reg:32"
+        ]
+    );
+
+    let registration =
+        Registration::LaunchServices(PathBuf::from("/Applications/Mathematica.app"));
+
+    assert_eq!(
+        registration.removal_command()[1..],
+        ["-u", "/Applications/Mathematica.app"]
+    );
+}
+
+#[test]
+fn test_registration_report() {
+    use crate::{Registration, RegistrationReport};
+
+    let root = crate::test_support::temp_dir("registrations");
+
+    let app_dir = root.join("Mathematica");
+    std::fs::create_dir_all(&app_dir).unwrap();
+
+    let mathematica = app_dir.to_str().unwrap();
+    let mathematica_2 = root.join(".").join("Mathematica");
+    let mathematica_2 = mathematica_2.to_str().unwrap();
+    let missing = root.join("Missing");
+    let missing = missing.to_str().unwrap();
+
+    // A registration left behind after its app was deleted.
+    let mut dangling =
+        WolframApp::orphaned(WolframAppType::Engine, PathBuf::from(missing));
+    dangling.registration = Some(Registration::LaunchServices(PathBuf::from(missing)));
+
+    let report = RegistrationReport::from_registrations(vec![
+        test_app(WolframAppType::Mathematica, (13, 3, 0), mathematica),
+        dangling,
+        test_app(WolframAppType::Mathematica, (13, 3, 0), mathematica_2),
+    ]);
+
+    assert!(!report.is_clean());
+    assert_eq!(report.duplicates().len(), 1);
+    assert_eq!(report.duplicates()[0].len(), 2);
+    assert_eq!(report.orphaned().len(), 1);
+
+    let orphan = &report.orphaned()[0];
+
+    assert_eq!(orphan.app_directory(), PathBuf::from(missing));
+    assert_eq!(orphan.app_type(), WolframAppType::Engine);
+    assert_eq!(
+        orphan.registration().unwrap().removal_command()[1..],
+        ["-u", missing]
+    );
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_front_end_executable_preference() {
+    use crate::{DiscoveryOptions, FrontEndRole};
+
+    let root = crate::test_support::temp_dir("front-end");
+
+    let executables = root.join("Executables");
+    std::fs::create_dir_all(&executables).unwrap();
+
+    let mut app = test_app(
+        WolframAppType::Mathematica,
+        (14, 1, 0),
+        root.to_str().unwrap(),
+    );
+
+    // No front end launchers present.
+    assert!(app.front_end_executables().unwrap().is_empty());
+    assert!(app.front_end_executable_path().is_err());
+
+    std::fs::write(executables.join("Mathematica"), "").unwrap();
+
+    assert_eq!(
+        app.front_end_executable_path().unwrap(),
+        executables.join("Mathematica")
+    );
+
+    std::fs::write(executables.join("Wolfram"), "").unwrap();
+
+    assert_eq!(
+        app.front_end_executables()
+            .unwrap()
+            .iter()
+            .map(|exe| exe.role())
+            .collect::<Vec<_>>(),
+        vec![FrontEndRole::Wolfram, FrontEndRole::Mathematica]
+    );
+    assert_eq!(
+        app.front_end_executable_path().unwrap(),
+        executables.join("Wolfram")
+    );
+
+    app.apply_options(&DiscoveryOptions {
+        front_end_preference: Some(vec![FrontEndRole::Mathematica]),
+        ..Default::default()
+    });
+
+    assert_eq!(
+        app.front_end_executable_path().unwrap(),
+        executables.join("Mathematica")
+    );
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_requires_front_end_filter() {
+    use crate::Filter;
+
+    let root = crate::test_support::temp_dir("front-end-filter");
+
+    let executables = root.join("Executables");
+    std::fs::create_dir_all(&executables).unwrap();
+
+    let filter = Filter {
+        app_types: None,
+        min_version: None,
+        kernel_system_id: None,
+        requires_front_end: true,
+        requires_activation: false,
+        wstp_sdk_system_id: None,
+    };
+
+    let root = root.to_str().unwrap();
+    let mathematica = test_app(WolframAppType::Mathematica, (14, 0, 0), root);
+    let engine = test_app(WolframAppType::Engine, (14, 0, 0), root);
+
+    // Front end executable is missing.
+    assert!(filter.check_app(&mathematica).is_err());
+
+    std::fs::write(executables.join("Mathematica"), "").unwrap();
+
+    assert!(filter.check_app(&mathematica).is_ok());
+    assert!(filter.check_app(&engine).is_err());
+
+    std::fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn test_kernel_system_id_filter() {
+    use crate::{Filter, SystemID};
+
+    let root = crate::test_support::temp_dir("kernel-filter");
+
+    let app = test_app(WolframAppType::Engine, (14, 0, 0), root.to_str().unwrap());
+
+    let binaries = app
+        .installation_directory()
+        .join("SystemFiles")
+        .join("Kernel")
+        .join("Binaries");
+
+    for dir in ["MacOSX-x86-64", "Linux-x86-64", "NotASystemID"] {
+        std::fs::create_dir_all(binaries.join(dir)).unwrap();
+    }
+
+    assert_eq!(
+        app.supported_system_ids().unwrap(),
+        [SystemID::MacOSX_x86_64, SystemID::Linux_x86_64]
+    );
+
+    let filter = |system_id| Filter {
+        app_types: None,
+        min_version: None,
+        kernel_system_id: Some(system_id),
+        requires_front_end: false,
+        requires_activation: false,
+        wstp_sdk_system_id: None,
+    };
+
+    assert!(filter(SystemID::Linux_x86_64).check_app(&app).is_ok());
+    assert!(filter(SystemID::MacOSX_ARM64).check_app(&app).is_err());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn test_min_version_filter() {
+    use crate::Filter;
+
+    let app = test_app(WolframAppType::Mathematica, (14, 1, 0), "/nonexistent");
+
+    let filter = |major, minor| Filter {
+        app_types: None,
+        min_version: Some(WolframVersion::new(major, minor, 0)),
+        kernel_system_id: None,
+        requires_front_end: false,
+        requires_activation: false,
+        wstp_sdk_system_id: None,
+    };
+
+    assert!(filter(13, 1).check_app(&app).is_ok());
+    assert!(filter(14, 1).check_app(&app).is_ok());
+    assert!(filter(14, 2).check_app(&app).is_err());
+}
+
+#[test]
+fn test_runner_up_reason() {
+    use crate::RunnerUpReason;
+
+    let mathematica = test_app(WolframAppType::Mathematica, (14, 0, 0), "/a");
+
+    let reason = |app_type, version| {
+        RunnerUpReason::compare(&mathematica, &test_app(app_type, version, "/b"))
+    };
+
+    assert_eq!(
+        reason(WolframAppType::Mathematica, (13, 3, 0)),
+        RunnerUpReason::OlderVersion
+    );
+    assert_eq!(
+        reason(WolframAppType::Engine, (14, 0, 0)),
+        RunnerUpReason::LacksFrontEnd
+    );
+    assert_eq!(
+        reason(WolframAppType::Player, (14, 0, 0)),
+        RunnerUpReason::RankedLower
+    );
+}
+
+/// Check that no library code writes to stdout, except for the function that
+/// prints `cargo:` build script directives.
+///
+/// Stray output on stdout could be misinterpreted by Cargo when this library is
+/// used from a build script.
+#[test]
+fn test_no_stdout_output() {
+    fn visit(dir: &std::path::Path, files: &mut Vec<PathBuf>) {
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+
+            if path.is_dir() {
+                // The command-line executable is allowed to write to stdout.
+                if path.file_name().unwrap() != "bin" {
+                    visit(&path, files);
+                }
+            } else if path.extension().is_some_and(|ext| ext == "rs") {
+                files.push(path);
+            }
+        }
+    }
+
+    // Whether `code` uses `print!`, `println!`, or `stdout()`, ignoring
+    // `eprint!` and `eprintln!`.
+    fn writes_to_stdout(code: &str) -> bool {
+        ["print!(", "println!(", "stdout()"].iter().any(|pattern| {
+            code.match_indices(pattern).any(|(index, _)| {
+                let prev = code[..index].chars().next_back();
+
+                !prev.is_some_and(|c| c.is_alphanumeric() || c == '_')
+            })
+        })
+    }
+
+    let src = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("src");
+
+    let mut files = Vec::new();
+    visit(&src, &mut files);
+
+    // Skip this file, which contains the patterns being searched for.
+    files.retain(|file| !file.ends_with("tests.rs"));
+
+    let mut writes = Vec::new();
+
+    for file in files {
+        let contents = std::fs::read_to_string(&file).unwrap();
+
+        for (index, line) in contents.lines().enumerate() {
+            let code = line.trim_start();
+
+            if code.starts_with("//") {
+                continue;
+            }
+
+            if writes_to_stdout(code) {
+                let file = file.strip_prefix(&src).unwrap().display().to_string();
+                writes.push(format!("{file}:{}: {code}", index + 1));
+            }
+        }
+    }
+
+    assert_eq!(writes.len(), 1, "unexpected writes to stdout: {writes:#?}");
+    assert!(writes[0].starts_with("config.rs:"), "{writes:#?}");
+}
+
+#[test]
+fn test_wstp_sdk_system_id_filter() {
+    use crate::{Filter, SystemID};
+
+    let root = crate::test_support::temp_dir("wstp-filter");
+
+    let app = test_app(WolframAppType::Engine, (14, 0, 0), root.to_str().unwrap());
+
+    let compiler_additions = app
+        .installation_directory()
+        .join("SystemFiles/Links/WSTP/DeveloperKit/Linux-x86-64/CompilerAdditions");
+
+    std::fs::create_dir_all(&compiler_additions).unwrap();
+    std::fs::write(compiler_additions.join("wstp.h"), "").unwrap();
+    std::fs::write(compiler_additions.join("libWSTP64i4.a"), "").unwrap();
+
+    let filter = |system_id| Filter {
+        app_types: None,
+        min_version: None,
+        kernel_system_id: None,
+        requires_front_end: false,
+        requires_activation: false,
+        wstp_sdk_system_id: Some(system_id),
+    };
+
+    assert!(filter(SystemID::Linux_x86_64).check_app(&app).is_ok());
+    assert!(filter(SystemID::Linux_ARM64).check_app(&app).is_err());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn test_wstp_sdk_tools() {
+    use crate::{SystemID, WstpSdk};
+
+    let root = crate::test_support::temp_dir("wstp-tools");
+
+    let sdk_dir = root.join("Linux-x86-64");
+    let compiler_additions = sdk_dir.join("CompilerAdditions");
+
+    std::fs::create_dir_all(&compiler_additions).unwrap();
+    std::fs::write(compiler_additions.join("wstp.h"), "").unwrap();
+    std::fs::write(compiler_additions.join("libWSTP64i4.a"), "").unwrap();
+
+    let sdk = WstpSdk::try_from_directory_with_system_id(sdk_dir, SystemID::Linux_x86_64)
+        .unwrap();
+
+    assert!(sdk.wsprep_executable_path().is_err());
+    assert!(sdk.wscc_script_path().is_err());
+
+    std::fs::write(compiler_additions.join("wsprep"), "").unwrap();
+    std::fs::write(compiler_additions.join("mcc"), "").unwrap();
+
+    assert_eq!(
+        sdk.wsprep_executable_path().unwrap(),
+        compiler_additions.join("wsprep")
+    );
+    // Falls back to the legacy `mcc` script.
+    assert_eq!(
+        sdk.wscc_script_path().unwrap(),
+        compiler_additions.join("mcc")
+    );
+
+    std::fs::write(compiler_additions.join("wscc"), "").unwrap();
+
+    assert_eq!(
+        sdk.wscc_script_path().unwrap(),
+        compiler_additions.join("wscc")
+    );
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn test_wstp_sdk_interface() {
+    use crate::{SystemID, WstpSdk};
+
+    let root = crate::test_support::temp_dir("wstp-interface");
+
+    let sdk_dir = root.join("Linux-x86-64");
+    let compiler_additions = sdk_dir.join("CompilerAdditions");
+
+    std::fs::create_dir_all(&compiler_additions).unwrap();
+    std::fs::write(
+        compiler_additions.join("wstp.h"),
+        "#define WSINTERFACE 5\n#define WSREVISION 1\n",
+    )
+    .unwrap();
+    std::fs::write(compiler_additions.join("libWSTP64i4.a"), "").unwrap();
+
+    // The interface 5 library is required.
+    assert!(WstpSdk::try_from_directory_with_system_id(
+        sdk_dir.clone(),
+        SystemID::Linux_x86_64
+    )
+    .is_err());
+
+    std::fs::write(compiler_additions.join("libWSTP64i5.a"), "").unwrap();
+
+    let sdk = WstpSdk::try_from_directory_with_system_id(sdk_dir, SystemID::Linux_x86_64)
+        .unwrap();
+
+    assert_eq!(sdk.wstp_interface(), 5);
+    assert_eq!(sdk.wstp_revision(), Some(1));
+    assert_eq!(
+        sdk.wstp_static_library_path(),
+        compiler_additions.join("libWSTP64i5.a")
+    );
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn test_wstp_sdk_shared_library() {
+    use crate::{SystemID, WstpSdk};
+
+    let root = crate::test_support::temp_dir("wstp-shared");
+
+    let sdk_dir = root.join("SystemFiles/Links/WSTP/DeveloperKit/Linux-x86-64");
+    let compiler_additions = sdk_dir.join("CompilerAdditions");
+    let libraries = root.join("SystemFiles/Libraries/Linux-x86-64");
+
+    std::fs::create_dir_all(&compiler_additions).unwrap();
+    std::fs::create_dir_all(&libraries).unwrap();
+    std::fs::write(compiler_additions.join("wstp.h"), "").unwrap();
+    std::fs::write(compiler_additions.join("libWSTP64i4.a"), "").unwrap();
+
+    let sdk = WstpSdk::try_from_directory_with_system_id(sdk_dir, SystemID::Linux_x86_64)
+        .unwrap();
+
+    assert!(sdk.wstp_shared_library_path().is_err());
+
+    // The redistributable copy is used if the SDK doesn't contain one.
+    std::fs::write(libraries.join("libWSTP64i4.so"), "").unwrap();
+
+    assert_eq!(
+        sdk.wstp_shared_library_path().unwrap(),
+        libraries.join("libWSTP64i4.so")
+    );
+
+    std::fs::write(compiler_additions.join("libWSTP64i4.so"), "").unwrap();
+
+    assert_eq!(
+        sdk.wstp_shared_library_path().unwrap(),
+        compiler_additions.join("libWSTP64i4.so")
+    );
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn test_verify_missing_components() {
+    use crate::verify::{Component, ComponentStatus};
+
+    let root = crate::test_support::temp_dir("verify");
+
+    let engine = test_app(WolframAppType::Engine, (14, 0, 0), root.to_str().unwrap());
+
+    let report = engine.verify();
+
+    assert!(!report.is_healthy());
+
+    // Wolfram Engine does not provide a front end, so it isn't reported as
+    // missing.
+    assert!(report
+        .missing()
+        .all(|check| check.component() != Component::FrontEnd));
+    assert!(report.checks().iter().any(|check| {
+        check.component() == Component::FrontEnd
+            && matches!(check.status(), ComponentStatus::NotApplicable)
+    }));
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_layout_resolve() {
+    use crate::{layout::LayoutEntry, SystemID};
+
+    let root = crate::test_support::temp_dir("layout");
+
+    let mut app = test_app(WolframAppType::Engine, (14, 0, 0), root.to_str().unwrap());
+    app.target_system_id = Some(SystemID::Linux_ARM64);
+
+    assert_eq!(
+        app.relative_path(LayoutEntry::KernelExecutable).unwrap(),
+        PathBuf::from("Executables/WolframKernel")
+    );
+    assert_eq!(
+        app.relative_path(LayoutEntry::LibrariesDirectory).unwrap(),
+        PathBuf::from("SystemFiles/Libraries/Linux-ARM64")
+    );
+
+    // Older and unknown versions use the layout of that version, and the
+    // current layout, respectively.
+    let older = test_app(WolframAppType::Mathematica, (9, 0, 1), "/M-9");
+    let unknown = test_app(WolframAppType::Mathematica, (0, 0, 0), "/M-0");
+
+    assert_eq!(
+        older.relative_path(LayoutEntry::KernelExecutable).unwrap(),
+        PathBuf::from("Executables/MathKernel")
+    );
+    assert_eq!(
+        unknown
+            .relative_path(LayoutEntry::KernelExecutable)
+            .unwrap(),
+        PathBuf::from("Executables/WolframKernel")
+    );
+
+    // Entries are only resolved if they are present.
+    assert!(app.resolve(LayoutEntry::KernelExecutable).is_err());
+
+    std::fs::create_dir_all(root.join("Executables")).unwrap();
+    std::fs::write(root.join("Executables/WolframKernel"), "").unwrap();
+
+    assert_eq!(
+        app.resolve(LayoutEntry::KernelExecutable).unwrap(),
+        root.join("Executables/WolframKernel")
+    );
+    assert!(app
+        .resolve(LayoutEntry::FrontEndExecutablesDirectory)
+        .is_ok());
+
+    std::fs::create_dir_all(root.join("SystemFiles/IncludeFiles/C")).unwrap();
+
+    assert!(app
+        .resolve(LayoutEntry::LibraryLinkIncludesDirectory)
+        .is_ok());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_library_link_sdk() {
+    use crate::SystemID;
+
+    let root = crate::test_support::temp_dir("library-link");
+
+    let app = test_app(WolframAppType::Engine, (14, 0, 0), root.to_str().unwrap());
+
+    std::fs::create_dir_all(root.join("SystemFiles/IncludeFiles/C")).unwrap();
+    std::fs::write(
+        root.join("SystemFiles/IncludeFiles/C/WolframLibrary.h"),
+        "#define WolframLibraryVersion 7\n",
+    )
+    .unwrap();
+
+    assert!(app.libraries_directory(SystemID::Linux_ARM64).is_err());
+    assert!(app.library_link_sdk(SystemID::Linux_ARM64).is_err());
+
+    std::fs::create_dir_all(root.join("SystemFiles/Libraries/Linux-ARM64")).unwrap();
+
+    assert_eq!(
+        app.libraries_directory(SystemID::Linux_ARM64).unwrap(),
+        root.join("SystemFiles/Libraries/Linux-ARM64")
+    );
+    assert!(app.libraries_directory(SystemID::Linux_x86_64).is_err());
+
+    let sdk = app.library_link_sdk(SystemID::Linux_ARM64).unwrap();
+
+    assert_eq!(sdk.system_id(), SystemID::Linux_ARM64);
+    assert_eq!(
+        sdk.c_includes_directory(),
+        root.join("SystemFiles/IncludeFiles/C")
+    );
+    assert_eq!(
+        sdk.libraries_directory(),
+        root.join("SystemFiles/Libraries/Linux-ARM64")
+    );
+    assert_eq!(sdk.library_version(), Some(7));
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_netlink_directory() {
+    let root = crate::test_support::temp_dir("netlink");
+
+    let app = test_app(
+        WolframAppType::Mathematica,
+        (13, 1, 0),
+        root.to_str().unwrap(),
+    );
+
+    let netlink = root.join("SystemFiles/Links/NETLink");
+
+    std::fs::create_dir_all(&netlink).unwrap();
+
+    // The directory is only valid if it contains the .NET/Link assembly.
+    assert!(app.netlink_directory().is_err());
+
+    std::fs::write(netlink.join("Wolfram.NETLink.dll"), "").unwrap();
+
+    assert_eq!(app.netlink_directory().unwrap(), netlink);
+    assert_eq!(
+        app.netlink_assembly_path().unwrap(),
+        netlink.join("Wolfram.NETLink.dll")
+    );
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_documentation_directory() {
+    let root = crate::test_support::temp_dir("documentation");
+
+    let app = test_app(WolframAppType::Engine, (14, 0, 0), root.to_str().unwrap());
+
+    std::fs::create_dir_all(&root).unwrap();
+
+    assert!(!app.is_documentation_installed());
+
+    // An empty documentation directory doesn't count.
+    std::fs::create_dir_all(root.join("Documentation")).unwrap();
+
+    assert!(!app.is_documentation_installed());
+
+    std::fs::create_dir_all(root.join("Documentation/English")).unwrap();
+
+    assert!(app.is_documentation_installed());
+    assert_eq!(
+        app.documentation_directory().unwrap(),
+        root.join("Documentation")
+    );
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_linux_engine_layout() {
+    let root = crate::test_support::temp_dir("linux-engine");
+
+    let app_dir = root.join("WolframEngine").join("14.0");
+    let binaries = app_dir
+        .join("SystemFiles/Kernel/Binaries")
+        .join(crate::SystemID::current_rust_target().as_str());
+    std::fs::create_dir_all(app_dir.join("Executables")).unwrap();
+    std::fs::create_dir_all(&binaries).unwrap();
+    std::fs::write(
+        app_dir.join("LICENSE.txt"),
+        "Free Wolfram Engine™ for Developers: Terms and Conditions of Use\n",
+    )
+    .unwrap();
+    std::fs::write(
+        app_dir.join("Executables/WolframKernel"),
+        "#!/bin/sh\n#\n#  Mathematica 14.0.0 Kernel command file\n",
+    )
+    .unwrap();
+    std::fs::write(binaries.join("wolframscript"), "").unwrap();
+
+    // The app directory of Wolfram Engine on Linux is also its
+    // $InstallationDirectory; there is no embedded Wolfram Player.
+    let app = WolframApp::from_installation_directory(app_dir.clone()).unwrap();
+
+    assert_eq!(app.app_type(), WolframAppType::Engine);
+    assert_eq!(app.app_directory(), app_dir);
+    assert_eq!(app.installation_directory(), app_dir);
+    // Linux installations have no platform identity.
+    assert_eq!(app.installation_id(), None);
+    assert_eq!(
+        app.kernel_executable_path().unwrap(),
+        app_dir.join("Executables/WolframKernel")
+    );
+    assert_eq!(
+        app.wolframscript_executable_path().unwrap(),
+        binaries.join("wolframscript")
+    );
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_legacy_linux_app() {
+    use crate::layout::LayoutEntry;
+
+    let root = crate::test_support::temp_dir("legacy-linux-app");
+
+    let app_dir = root.join("Mathematica").join("10.0");
+    std::fs::create_dir_all(app_dir.join("Executables")).unwrap();
+    std::fs::write(
+        app_dir.join("LICENSE.txt"),
+        "MATHEMATICA® LICENSE AGREEMENT\n",
+    )
+    .unwrap();
+    std::fs::write(
+        app_dir.join("Executables").join("MathKernel"),
+        "#!/bin/sh\n#\n#  Mathematica 10.0 Kernel command file\n",
+    )
+    .unwrap();
+
+    let app = WolframApp::from_app_directory(app_dir.clone()).unwrap();
+
+    assert_eq!(app.app_type(), WolframAppType::Mathematica);
+    assert_eq!(app.app_version().to_string(), "10.0.0");
+    assert_eq!(
+        app.relative_path(LayoutEntry::KernelExecutable).unwrap(),
+        PathBuf::from("Executables/WolframKernel")
+    );
+
+    // Unrecognized license titles are still an error.
+    std::fs::write(app_dir.join("LICENSE.txt"), "Some Other Product\n").unwrap();
+    assert!(WolframApp::from_app_directory(app_dir).is_err());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_from_path() {
+    let root = crate::test_support::temp_dir("from-path");
+
+    let app_dir = root.join("Mathematica").join("14.1");
+    let kernel = app_dir.join("Executables").join("WolframKernel");
+    std::fs::create_dir_all(kernel.parent().unwrap()).unwrap();
+    std::fs::write(
+        app_dir.join("LICENSE.txt"),
+        "Wolfram Mathematica License Agreement",
+    )
+    .unwrap();
+    std::fs::write(
+        &kernel,
+        "#!/bin/sh\n#\n#  Mathematica 14.1.0 Kernel command file\n",
+    )
+    .unwrap();
+
+    let app = WolframApp::from_path(app_dir.clone()).unwrap();
+    assert_eq!(app.app_directory(), app_dir);
+
+    let canonical = app_dir.canonicalize().unwrap();
+
+    for path in [kernel.clone(), app_dir.join("Executables")] {
+        let app = WolframApp::from_path(path).unwrap();
+        assert_eq!(app.app_directory(), canonical);
+        assert_eq!(app.app_type(), WolframAppType::Mathematica);
+    }
+
+    let link = root.join("wolfram");
+    std::os::unix::fs::symlink(&kernel, &link).unwrap();
+    assert_eq!(
+        WolframApp::from_path(link).unwrap().app_directory(),
+        canonical
+    );
+
+    assert!(WolframApp::from_path(root.clone()).is_err());
+    assert!(WolframApp::from_path(root.join("missing")).is_err());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn test_error_code() {
+    use std::path::Path;
+
+    use crate::{Error, ErrorCode};
+
+    let app = test_app(WolframAppType::Mathematica, (13, 1, 0), "/opt/Mathematica");
+
+    let err = Error::unexpected_app_layout(
+        "WolframKernel executable",
+        &app,
+        PathBuf::from("/missing/WolframKernel"),
+    );
+
+    assert_eq!(err.code(), ErrorCode::UnexpectedLayout);
+    assert_eq!(err.resource_name(), Some("WolframKernel executable"));
+    assert_eq!(err.path(), Some(Path::new("/missing/WolframKernel")));
+    assert_eq!(err.environment_variable(), None);
+
+    let err = Error::undiscoverable("WSTP SDK".to_owned(), Some("WSTP_SDK_DIR"));
+
+    assert_eq!(err.code(), ErrorCode::Undiscoverable);
+    assert_eq!(err.code().as_str(), "undiscoverable");
+    assert_eq!(err.environment_variable(), Some("WSTP_SDK_DIR"));
+    assert_eq!(err.path(), None);
+
+    assert_eq!(Error::other("message".to_owned()).code(), ErrorCode::Other);
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_player_executable_path() {
+    let root = crate::test_support::temp_dir("player");
+
+    let executables = root.join("Executables");
+    std::fs::create_dir_all(&executables).unwrap();
+
+    let player = test_app(WolframAppType::Player, (13, 1, 0), root.to_str().unwrap());
+    let mathematica = test_app(
+        WolframAppType::Mathematica,
+        (13, 1, 0),
+        root.to_str().unwrap(),
+    );
+
+    assert!(player.player_executable_path().is_err());
+
+    std::fs::write(executables.join("WolframPlayer"), "").unwrap();
+
+    assert_eq!(
+        player.player_executable_path().unwrap(),
+        executables.join("WolframPlayer")
+    );
+
+    // Only Player apps provide a Wolfram Player executable.
+    assert!(mathematica.player_executable_path().is_err());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_linux_launchers() {
+    use crate::LinuxLauncher;
+
+    let root = crate::test_support::temp_dir("linux-launchers");
+
+    let executables = root.join("Executables");
+    std::fs::create_dir_all(&executables).unwrap();
+
+    let app = test_app(WolframAppType::Engine, (13, 1, 0), root.to_str().unwrap());
+
+    assert_eq!(app.linux_launchers().unwrap(), vec![]);
+    assert!(app.linux_launcher_path(LinuxLauncher::Math).is_err());
+
+    for name in ["WolframKernel", "math", "wolfram"] {
+        std::fs::write(executables.join(name), "").unwrap();
+    }
+
+    assert_eq!(
+        app.linux_launchers().unwrap(),
+        vec![
+            LinuxLauncher::Wolfram,
+            LinuxLauncher::Math,
+            LinuxLauncher::WolframKernel
+        ]
+    );
+    assert_eq!(
+        app.linux_launcher_path(LinuxLauncher::Math).unwrap(),
+        executables.join("math")
+    );
+    assert!(app.linux_launcher_path(LinuxLauncher::Mathematica).is_err());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_kernel_command() {
+    use crate::SystemID;
+
+    let root = crate::test_support::temp_dir("kernel-command");
+
+    let system_id = SystemID::current_rust_target().as_str();
+
+    std::fs::create_dir_all(root.join("Executables")).unwrap();
+    std::fs::create_dir_all(root.join("SystemFiles/Kernel/Binaries").join(system_id))
+        .unwrap();
+    std::fs::write(root.join("Executables/WolframKernel"), "").unwrap();
+
+    let app = test_app(WolframAppType::Engine, (14, 0, 0), root.to_str().unwrap());
+
+    let command = app.kernel_command().unwrap();
+
+    assert_eq!(
+        command.get_program(),
+        root.join("Executables/WolframKernel")
+    );
+    assert_eq!(command.get_current_dir(), Some(root.as_path()));
+    assert_eq!(command.get_args().count(), 0);
+    // No libraries directory, so the library path is not modified.
+    assert_eq!(command.get_envs().count(), 0);
+
+    let libraries = root.join("SystemFiles/Libraries").join(system_id);
+    std::fs::create_dir_all(&libraries).unwrap();
+
+    let command = app.kernel_command().unwrap();
+
+    let (key, value) = command.get_envs().next().unwrap();
+
+    assert_eq!(key, "LD_LIBRARY_PATH");
+    assert_eq!(
+        std::env::split_paths(value.unwrap()).next(),
+        Some(libraries)
+    );
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_wolframscript_command() {
+    let root = crate::test_support::temp_dir("wolframscript-command");
+
+    let executables = root.join("Executables");
+    let binaries = root
+        .join("SystemFiles/Kernel/Binaries")
+        .join(crate::SystemID::current_rust_target().as_str());
+    std::fs::create_dir_all(&executables).unwrap();
+    std::fs::create_dir_all(&binaries).unwrap();
+
+    let app = test_app(WolframAppType::Engine, (14, 0, 0), root.to_str().unwrap());
+
+    assert!(app.wolframscript_command().is_err());
+
+    std::fs::write(binaries.join("wolframscript"), "").unwrap();
+
+    let command = app.wolframscript_command().unwrap();
+
+    assert_eq!(command.get_program(), binaries.join("wolframscript"));
+    assert_eq!(command.get_args().count(), 0);
+
+    std::fs::write(executables.join("WolframKernel"), "").unwrap();
+
+    let command = app.wolframscript_command().unwrap();
+    let kernel = executables.join("WolframKernel");
+
+    assert_eq!(
+        command.get_args().collect::<Vec<_>>(),
+        vec![std::ffi::OsStr::new("-local"), kernel.as_os_str()]
+    );
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_is_activated() {
+    let root = crate::test_support::temp_dir("is-activated");
+
+    let licensing = root.join("Configuration/Licensing");
+    std::fs::create_dir_all(&licensing).unwrap();
+
+    let app = test_app(WolframAppType::Engine, (14, 0, 0), root.to_str().unwrap());
+
+    std::fs::write(
+        licensing.join("mathpass"),
+        "my-machine  1234-56789-01234  5678-901-234  1234567  Wolfram Engine\n",
+    )
+    .unwrap();
+
+    assert!(app.is_activated());
+
+    let filter = crate::Filter {
+        app_types: Some(vec![WolframAppType::Engine]),
+        min_version: None,
+        kernel_system_id: None,
+        requires_front_end: false,
+        requires_activation: true,
+        wstp_sdk_system_id: None,
+    };
+
+    assert!(filter.check_app(&app).is_ok());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+#[cfg(all(target_os = "linux", feature = "kernel-properties"))]
+fn test_check_activation() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let root = crate::test_support::temp_dir("check-activation");
+
+    std::fs::create_dir_all(root.join("Executables")).unwrap();
+
+    // An unactivated kernel exits with status 255.
+    let kernel = root.join("Executables/WolframKernel");
+    std::fs::write(&kernel, "#!/bin/sh\nexit 255\n").unwrap();
+    std::fs::set_permissions(&kernel, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    let app = test_app(WolframAppType::Engine, (14, 0, 0), root.to_str().unwrap());
+
+    let err = app
+        .check_activation(Some(std::time::Duration::from_secs(10)))
+        .unwrap_err();
+
+    assert!(err.to_string().contains("may not be activated"), "{err}");
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+#[cfg(all(target_os = "linux", feature = "kernel-properties"))]
+fn test_kernel_properties() {
+    use std::os::unix::fs::PermissionsExt;
+
+    use crate::{SystemID, WolframVersion};
+
+    let root = crate::test_support::temp_dir("kernel-properties");
+
+    std::fs::create_dir_all(root.join("Executables")).unwrap();
+
+    let kernel = root.join("Executables/WolframKernel");
+    std::fs::write(
+        &kernel,
+        "#!/bin/sh\nprintf '14.0.0 for Linux\\nLinux-x86-64\\nL1234\\n14.\\n0' \\\n    > \"$WOLFRAM_APP_DISCOVERY_KERNEL_OUTPUT\"\n",
+    )
+    .unwrap();
+    std::fs::set_permissions(&kernel, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    let app = test_app(WolframAppType::Engine, (14, 0, 0), root.to_str().unwrap());
+
+    let properties = app
+        .kernel_properties(Some(std::time::Duration::from_secs(10)))
+        .unwrap();
+
+    assert_eq!(properties.version(), "14.0.0 for Linux");
+    assert_eq!(properties.wolfram_version(), &WolframVersion::new(14, 0, 0));
+    assert_eq!(properties.system_id(), SystemID::Linux_x86_64);
+    assert_eq!(properties.license_id(), "L1234");
+
+    // If the application version is unknown, `wolfram_version()` falls back to
+    // querying the kernel.
+    let unversioned = test_app(WolframAppType::Engine, (0, 0, 0), root.to_str().unwrap());
+
+    assert_eq!(
+        unversioned.wolfram_version().unwrap(),
+        WolframVersion::new(14, 0, 0)
+    );
+
+    // ... unless spawning subprocesses has been disabled.
+    let mut offline = unversioned.clone();
+    offline.apply_options(&crate::DiscoveryOptions {
+        offline: true,
+        ..Default::default()
+    });
+
+    assert!(offline.wolfram_version().is_err());
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_layout_paclets() {
+    let root = crate::test_support::temp_dir("layout-paclets");
+
+    let app = test_app(WolframAppType::Engine, (14, 0, 0), root.to_str().unwrap());
+
+    std::fs::create_dir_all(&root).unwrap();
+
+    assert_eq!(app.layout_paclets().unwrap(), vec![]);
+
+    for (dir, name) in [
+        (
+            "SystemFiles/Paclets/WolframAlphaClient",
+            "WolframAlphaClient",
+        ),
+        ("SystemFiles/Links/JLink", "JLink"),
+    ] {
+        std::fs::create_dir_all(root.join(dir)).unwrap();
+        std::fs::write(
+            root.join(dir).join("PacletInfo.wl"),
+            format!(r#"PacletObject[<|"Name" -> "{name}", "Version" -> "1.0"|>]"#),
+        )
+        .unwrap();
+    }
+
+    // Directories without a valid paclet info file are skipped.
+    std::fs::create_dir_all(root.join("SystemFiles/Links/WSTP")).unwrap();
+
+    let paclets = app.layout_paclets().unwrap();
+
+    assert_eq!(
+        paclets
+            .iter()
+            .map(|paclet| paclet.name())
+            .collect::<Vec<_>>(),
+        vec!["JLink", "WolframAlphaClient"]
+    );
+    assert_eq!(paclets[0].location(), &root.join("SystemFiles/Links/JLink"));
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_add_ons_applications_directory() {
+    let root = crate::test_support::temp_dir("add-ons");
+
+    let app = test_app(
+        WolframAppType::Mathematica,
+        (13, 3, 0),
+        root.to_str().unwrap(),
+    );
+
+    std::fs::create_dir_all(&root).unwrap();
+
+    assert!(app.add_ons_applications_directory().is_err());
+
+    std::fs::create_dir_all(root.join("AddOns/Applications")).unwrap();
+
+    assert_eq!(
+        app.add_ons_applications_directory().unwrap(),
+        root.join("AddOns/Applications")
+    );
+
+    std::fs::remove_dir_all(&root).unwrap();
+}
+
+#[cfg(unix)]
+mod wolframscript {
+    use std::{path::PathBuf, time::Duration};
+
+    use crate::{
+        test_support::{FakeBehavior, FakeWolframScript},
+        try_wolframscript_installation_directory, DiscoveryOptions,
+    };
+
+    #[test]
+    fn test_wolframscript_installation_directory() {
+        let dir = PathBuf::from("/opt/Wolfram/Mathematica/13.3");
+
+        let _fake = FakeWolframScript::install(
+            "installation-directory",
+            FakeBehavior::InstallationDirectory(dir.clone()),
+        );
+
+        let invocation =
+            try_wolframscript_installation_directory(&DiscoveryOptions::default())
+                .unwrap()
+                .expect("fake wolframscript was not found on PATH");
+
+        assert_eq!(PathBuf::from(invocation.output()), dir);
+        assert!(invocation.command_line()[0].ends_with("wolframscript"));
+        assert_eq!(
+            invocation.command_line()[1..],
+            ["-code", "$InstallationDirectory"]
+        );
+    }
+
+    #[test]
+    fn test_wolframscript_failure() {
+        let _fake = FakeWolframScript::install("failure", FakeBehavior::ExitCode(1));
+
+        let err = try_wolframscript_installation_directory(&DiscoveryOptions::default())
+            .unwrap_err();
+
+        assert!(err
+            .to_string()
+            .contains("wolframscript exited with non-success status code"));
+    }
+
+    #[test]
+    fn test_wolframscript_offline() {
+        let _fake = FakeWolframScript::install(
+            "offline",
+            FakeBehavior::InstallationDirectory(PathBuf::from("/unused")),
+        );
+
+        let options = DiscoveryOptions {
+            offline: true,
+            ..Default::default()
+        };
+
+        assert_eq!(try_wolframscript_installation_directory(&options), Ok(None));
+    }
+
+    #[test]
+    fn test_wolframscript_skipped() {
+        let _fake = FakeWolframScript::install(
+            "skipped",
+            FakeBehavior::InstallationDirectory(PathBuf::from("/unused")),
+        );
+
+        let options = DiscoveryOptions {
+            skip_wolframscript: true,
+            ..Default::default()
+        };
+
+        assert_eq!(try_wolframscript_installation_directory(&options), Ok(None));
+    }
+
+    #[test]
+    fn test_wolframscript_timeout() {
+        let _fake = FakeWolframScript::install("hang", FakeBehavior::Hang);
+
+        let options = DiscoveryOptions {
+            timeout: Some(Duration::from_millis(200)),
+            ..Default::default()
+        };
+
+        assert!(try_wolframscript_installation_directory(&options).is_err());
+    }
+
+    #[test]
+    fn test_wolframscript_nonexistent_installation_directory() {
+        use crate::{ErrorKind, WolframApp};
+
+        let _fake = FakeWolframScript::install(
+            "nonexistent-installation-directory",
+            FakeBehavior::InstallationDirectory(PathBuf::from(
+                "/nonexistent/remote/Mathematica",
+            )),
+        );
+
+        // Discovery should fall back to searching the operating system
+        // applications folder, instead of failing with a layout error.
+        match WolframApp::try_default() {
+            Ok(app) => assert!(app.wolframscript_invocation().is_none()),
+            Err(err) => assert!(
+                matches!(err.0, ErrorKind::Undiscoverable { .. }),
+                "unexpected error: {err}"
+            ),
+        }
+    }
+
+    #[test]
+    #[cfg(all(target_os = "linux", feature = "evaluate"))]
+    fn test_evaluate() {
+        use std::os::unix::fs::PermissionsExt;
+
+        use crate::{tests::test_app, SystemID, WolframAppType};
+
+        let root = crate::test_support::temp_dir("evaluate");
+
+        let binaries = root
+            .join("SystemFiles/Kernel/Binaries")
+            .join(SystemID::current_rust_target().as_str());
+        std::fs::create_dir_all(&binaries).unwrap();
+
+        let app = test_app(WolframAppType::Engine, (14, 0, 0), root.to_str().unwrap());
+
+        let script = binaries.join("wolframscript");
+
+        let write_script = |body: &str| {
+            std::fs::write(&script, format!("#!/bin/sh\n{body}\n")).unwrap();
+            std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755))
+                .unwrap();
+        };
+
+        write_script("echo \"$2\"");
+        assert_eq!(app.evaluate("1 + 1", None).unwrap(), "1 + 1");
+
+        write_script("echo 'error' >&2\nexit 1");
+        let err = app.evaluate("1 + 1", None).unwrap_err();
+        assert!(err.to_string().contains("non-success status code"), "{err}");
+
+        write_script("printf '\\377\\n'");
+        let err = app.evaluate("1 + 1", None).unwrap_err();
+        assert!(err.to_string().contains("not valid UTF-8"), "{err}");
+
+        write_script("exec sleep 1000");
+        let err = app
+            .evaluate("1 + 1", Some(Duration::from_millis(200)))
+            .unwrap_err();
+        assert!(err.to_string().contains("timed out"), "{err}");
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
+
+#[test]
+fn test_io_error_source() {
+    use std::error::Error as _;
+
+    let io_err = std::io::Error::from_raw_os_error(2);
+    let message = io_err.to_string();
+
+    let err = crate::Error::from(io_err);
+
+    assert_eq!(err.code(), crate::ErrorCode::Io);
+    assert_eq!(
+        err.to_string(),
+        format!("Wolfram app error: IO error during discovery: {message}")
+    );
+
+    let source = err
+        .source()
+        .and_then(|source| source.downcast_ref::<std::io::Error>())
+        .expect("IO error source");
+
+    assert_eq!(source.kind(), std::io::ErrorKind::NotFound);
+    assert_eq!(source.raw_os_error(), Some(2));
+
+    // The source is preserved by clones.
+    let cloned = err.clone();
+    assert!(cloned.source().is_some());
+}
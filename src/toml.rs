@@ -0,0 +1,351 @@
+//! Parser for the subset of [TOML](https://toml.io) used by the configuration
+//! files read by this library.
+//!
+//! Supported syntax:
+//!
+//! * `# comments`
+//! * `[table]` headers (not nested tables or arrays of tables)
+//! * `key = value` pairs with bare or quoted keys, where `value` is a basic or
+//!   literal string, a boolean, an integer, or a single-line array of these.
+
+use std::fmt::{self, Display};
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Value {
+    String(String),
+    Integer(i64),
+    Boolean(bool),
+    Array(Vec<Value>),
+}
+
+/// A parsed TOML document.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Document {
+    /// `(table, key, value)` entries, in the order they appear in the source.
+    /// Keys that appear before any `[table]` header have the table name `""`.
+    entries: Vec<(String, String, Value)>,
+}
+
+/// Syntax error in a TOML document.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ParseError {
+    line: usize,
+    message: String,
+}
+
+//======================================
+// Impls
+//======================================
+
+impl Document {
+    pub fn parse(text: &str) -> Result<Document, ParseError> {
+        let mut entries: Vec<(String, String, Value)> = Vec::new();
+        let mut table = String::new();
+
+        for (index, line) in text.lines().enumerate() {
+            let error = |message: &str| ParseError {
+                line: index + 1,
+                message: message.to_owned(),
+            };
+
+            let mut cursor = Cursor(line.trim());
+
+            if cursor.is_end() {
+                continue;
+            }
+
+            if cursor.eat('[') {
+                table = cursor.key().map_err(|msg| error(&msg))?;
+
+                if !cursor.eat(']') || !cursor.is_end() {
+                    return Err(error("expected ']' at end of table header"));
+                }
+
+                continue;
+            }
+
+            let key = cursor.key().map_err(|msg| error(&msg))?;
+
+            if !cursor.eat('=') {
+                return Err(error("expected '=' after key"));
+            }
+
+            let value = cursor.value().map_err(|msg| error(&msg))?;
+
+            if !cursor.is_end() {
+                return Err(error("unexpected characters after value"));
+            }
+
+            if entries.iter().any(|(t, k, _)| *t == table && *k == key) {
+                return Err(error(&format!("duplicate key: {key}")));
+            }
+
+            entries.push((table.clone(), key, value));
+        }
+
+        Ok(Document { entries })
+    }
+
+    /// Get the value of `key` in `table`. Use `""` for keys that are not in a
+    /// table.
+    pub fn get(&self, table: &str, key: &str) -> Option<&Value> {
+        self.entries
+            .iter()
+            .find(|(t, k, _)| t == table && k == key)
+            .map(|(_, _, value)| value)
+    }
+
+    /// Iterate over the `(table, key)` pairs of every entry in this document.
+    pub fn keys(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries
+            .iter()
+            .map(|(table, key, _)| (table.as_str(), key.as_str()))
+    }
+}
+
+impl Value {
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            Value::Boolean(bool) => Some(*bool),
+            _ => None,
+        }
+    }
+
+    pub fn as_array(&self) -> Option<&[Value]> {
+        match self {
+            Value::Array(array) => Some(array),
+            _ => None,
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let ParseError { line, message } = self;
+
+        write!(f, "line {line}: {message}")
+    }
+}
+
+/// Format `string` as a TOML basic string.
+pub(crate) fn quote(string: &str) -> String {
+    let mut quoted = String::from("\"");
+
+    for char in string.chars() {
+        match char {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            '\r' => quoted.push_str("\\r"),
+            char if char.is_control() => {
+                quoted.push_str(&format!("\\u{:04X}", u32::from(char)))
+            },
+            char => quoted.push(char),
+        }
+    }
+
+    quoted.push('"');
+    quoted
+}
+
+//======================================
+// Helpers
+//======================================
+
+/// Remaining unparsed text of a line.
+struct Cursor<'a>(&'a str);
+
+impl<'a> Cursor<'a> {
+    fn skip_whitespace(&mut self) {
+        self.0 = self.0.trim_start_matches([' ', '\t']);
+    }
+
+    /// Returns `true` if only whitespace or a comment remains.
+    fn is_end(&mut self) -> bool {
+        self.skip_whitespace();
+        self.0.is_empty() || self.0.starts_with('#')
+    }
+
+    fn eat(&mut self, char: char) -> bool {
+        self.skip_whitespace();
+
+        match self.0.strip_prefix(char) {
+            Some(rest) => {
+                self.0 = rest;
+                true
+            },
+            None => false,
+        }
+    }
+
+    fn key(&mut self) -> Result<String, String> {
+        self.skip_whitespace();
+
+        if self.0.starts_with(['"', '\'']) {
+            return self.string();
+        }
+
+        let end = self
+            .0
+            .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+            .unwrap_or(self.0.len());
+
+        if end == 0 {
+            return Err("expected key".to_owned());
+        }
+
+        let (key, rest) = self.0.split_at(end);
+        self.0 = rest;
+
+        Ok(key.to_owned())
+    }
+
+    fn value(&mut self) -> Result<Value, String> {
+        self.skip_whitespace();
+
+        if self.0.starts_with(['"', '\'']) {
+            return self.string().map(Value::String);
+        }
+
+        if self.eat('[') {
+            let mut array = Vec::new();
+
+            loop {
+                if self.eat(']') {
+                    return Ok(Value::Array(array));
+                }
+
+                array.push(self.value()?);
+
+                if !self.eat(',') {
+                    if !self.eat(']') {
+                        return Err("expected ',' or ']' in array".to_owned());
+                    }
+                    return Ok(Value::Array(array));
+                }
+            }
+        }
+
+        let end = self
+            .0
+            .find([' ', '\t', ',', ']', '#'])
+            .unwrap_or(self.0.len());
+
+        let (token, rest) = self.0.split_at(end);
+
+        let value = match token {
+            "true" => Value::Boolean(true),
+            "false" => Value::Boolean(false),
+            _ => match token.replace('_', "").parse::<i64>() {
+                Ok(int) => Value::Integer(int),
+                Err(_) => return Err(format!("invalid value: {token}")),
+            },
+        };
+
+        self.0 = rest;
+
+        Ok(value)
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        let mut chars = self.0.char_indices();
+
+        let quote = match chars.next() {
+            Some((_, quote @ ('"' | '\''))) => quote,
+            _ => return Err("expected string".to_owned()),
+        };
+
+        let mut string = String::new();
+
+        while let Some((index, char)) = chars.next() {
+            match char {
+                _ if char == quote => {
+                    self.0 = &self.0[index + 1..];
+                    return Ok(string);
+                },
+                '\\' if quote == '"' => {
+                    let escaped = match chars.next() {
+                        Some((_, '"')) => '"',
+                        Some((_, '\\')) => '\\',
+                        Some((_, 'n')) => '\n',
+                        Some((_, 't')) => '\t',
+                        Some((_, 'r')) => '\r',
+                        Some((_, 'u')) => {
+                            let hex: String =
+                                chars.by_ref().take(4).map(|(_, c)| c).collect();
+
+                            u32::from_str_radix(&hex, 16)
+                                .ok()
+                                .and_then(char::from_u32)
+                                .ok_or_else(|| format!("invalid escape: \\u{hex}"))?
+                        },
+                        _ => return Err("invalid escape sequence in string".to_owned()),
+                    };
+                    string.push(escaped);
+                },
+                _ => string.push(char),
+            }
+        }
+
+        Err("unterminated string".to_owned())
+    }
+}
+
+//======================================
+// Tests
+//======================================
+
+#[test]
+fn test_parse() {
+    let doc = Document::parse(
+        r#"
+        # Comment
+        top = 1
+
+        [app]
+        directory = "C:\\Program Files\\Wolfram" # Trailing comment
+        'literal' = 'C:\Temp'
+        enabled = false
+        roots = ["/a", '/b', ]
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(doc.get("", "top"), Some(&Value::Integer(1)));
+    assert_eq!(
+        doc.get("app", "directory").and_then(Value::as_str),
+        Some(r"C:\Program Files\Wolfram")
+    );
+    assert_eq!(
+        doc.get("app", "literal").and_then(Value::as_str),
+        Some(r"C:\Temp")
+    );
+    assert_eq!(doc.get("app", "enabled"), Some(&Value::Boolean(false)));
+    assert_eq!(
+        doc.get("app", "roots"),
+        Some(&Value::Array(vec![
+            Value::String("/a".to_owned()),
+            Value::String("/b".to_owned())
+        ]))
+    );
+    assert_eq!(doc.get("", "directory"), None);
+
+    let error = |text| Document::parse(text).unwrap_err().to_string();
+
+    assert_eq!(error("a = \"b"), "line 1: unterminated string");
+    assert_eq!(error("\na = 1\na = 2"), "line 3: duplicate key: a");
+    assert_eq!(error("[app"), "line 1: expected ']' at end of table header");
+    assert_eq!(error("a = b"), "line 1: invalid value: b");
+
+    let string = "a \"b\" \\ c\n";
+    let doc = Document::parse(&format!("key = {}", quote(string))).unwrap();
+    assert_eq!(doc.get("", "key").and_then(Value::as_str), Some(string));
+}
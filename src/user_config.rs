@@ -0,0 +1,314 @@
+//! Per-user configuration file.
+//!
+//! The user configuration file provides persistent alternatives to the
+//! environment variables that configure discovery:
+//!
+//! ```toml
+//! [app]
+//! # Preferred default app.
+//! directory = "/opt/Wolfram/Mathematica/14.1"
+//!
+//! [discovery]
+//! # Additional directories to search for installed apps.
+//! search-roots = ["/data/Wolfram"]
+//!
+//! # Whether `wolframscript` may be used to locate the default app.
+//! wolframscript = false
+//! ```
+//!
+//! Every key is optional. The file is located at:
+//!
+//! Operating system | Location
+//! -----------------|---------
+//! Windows          | `%APPDATA%\wolfram-app-discovery\config.toml`
+//! Other            | `$XDG_CONFIG_HOME/wolfram-app-discovery/config.toml`, or `~/.config/wolfram-app-discovery/config.toml` if `XDG_CONFIG_HOME` is not set
+//!
+//! Settings from environment variables and from a
+//! [project configuration file][crate::project_config] take precedence over
+//! the user configuration file.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    config,
+    os::{is_allowed, OperatingSystem},
+    toml, DiscoveryOptions, Error, ScanLimit, WolframApp, WolframAppType,
+};
+
+/// Contents of the user configuration file.
+#[derive(Debug, Clone)]
+pub struct UserConfig {
+    path: PathBuf,
+    app_directory: Option<PathBuf>,
+    search_roots: Vec<PathBuf>,
+    allow_wolframscript: bool,
+}
+
+//======================================
+// API
+//======================================
+
+/// Location of the user configuration file.
+///
+/// The file may not exist. Returns `None` if the user's configuration
+/// directory can't be determined.
+pub fn user_config_file() -> Option<PathBuf> {
+    let env_dir = |var: &str| std::env::var_os(var).map(PathBuf::from);
+
+    let dir = match OperatingSystem::target_os() {
+        OperatingSystem::Windows => env_dir("APPDATA")?,
+        OperatingSystem::MacOS | OperatingSystem::Linux | OperatingSystem::Other => {
+            env_dir("XDG_CONFIG_HOME")
+                .or_else(|| Some(env_dir("HOME")?.join(".config")))?
+        },
+    };
+
+    Some(dir.join("wolfram-app-discovery").join("config.toml"))
+}
+
+impl UserConfig {
+    /// Read the user configuration file, if it exists.
+    pub fn load() -> Result<Option<Self>, Error> {
+        match user_config_file() {
+            Some(path) if path.is_file() => UserConfig::from_file(path).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// Read the user configuration file at `path`.
+    pub fn from_file(path: PathBuf) -> Result<Self, Error> {
+        config::print_cargo_directive(&format!(
+            "cargo:rerun-if-changed={}",
+            path.display()
+        ));
+
+        let contents = fs::read_to_string(&path)?;
+
+        UserConfig::parse(path, &contents)
+    }
+
+    /// Location of the configuration file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// The preferred default app directory, if any.
+    pub fn app_directory(&self) -> Option<&Path> {
+        self.app_directory.as_deref()
+    }
+
+    /// Additional directories searched for installed apps by [`discover()`][crate::discover].
+    ///
+    /// Each directory is searched for app directories up to
+    /// [`DiscoveryOptions::max_scan_depth`] levels deep.
+    pub fn search_roots(&self) -> &[PathBuf] {
+        &self.search_roots
+    }
+
+    /// Whether `wolframscript` may be used to locate the default app.
+    ///
+    /// Defaults to `true`.
+    pub fn allow_wolframscript(&self) -> bool {
+        self.allow_wolframscript
+    }
+}
+
+//======================================
+// Helpers
+//======================================
+
+impl UserConfig {
+    /// Read the user configuration file, printing a warning if it exists but
+    /// can't be read.
+    pub(crate) fn load_or_warn() -> Option<Self> {
+        match UserConfig::load() {
+            Ok(config) => config,
+            Err(err) => {
+                crate::warning(&format!("ignoring user configuration file: {err}"));
+                None
+            },
+        }
+    }
+
+    /// Find the apps in [`UserConfig::search_roots()`].
+    pub(crate) fn discover_in_search_roots(
+        &self,
+        options: &DiscoveryOptions,
+        app_types: Option<&[WolframAppType]>,
+    ) -> Vec<WolframApp> {
+        let (max_depth, max_entries) = options.scan_limits();
+
+        let mut apps = Vec::new();
+
+        for root in &self.search_roots {
+            let mut entries = 0;
+
+            if let Some(limit) =
+                find_apps_in_dir(root, max_depth, max_entries, &mut entries, &mut apps)
+            {
+                crate::print_scan_truncated_warning(root, limit);
+            }
+        }
+
+        apps.retain(|app| is_allowed(app_types, &app.app_type()));
+
+        apps
+    }
+
+    fn parse(path: PathBuf, contents: &str) -> Result<Self, Error> {
+        let invalid = |message: String| {
+            Error::other(format!("invalid {}: {message}", path.display()))
+        };
+
+        let document =
+            toml::Document::parse(contents).map_err(|err| invalid(err.to_string()))?;
+
+        for (table, key) in document.keys() {
+            if !matches!(
+                (table, key),
+                ("app", "directory") | ("discovery", "search-roots" | "wolframscript")
+            ) {
+                crate::warning(&format!(
+                    "ignoring unrecognized key in {}: {table}.{key}",
+                    path.display()
+                ));
+            }
+        }
+
+        let app_directory = match document.get("app", "directory") {
+            Some(value) => Some(PathBuf::from(value.as_str().ok_or_else(|| {
+                invalid("expected string value for app.directory".to_owned())
+            })?)),
+            None => None,
+        };
+
+        let search_roots = match document.get("discovery", "search-roots") {
+            Some(value) => value
+                .as_array()
+                .and_then(|array| {
+                    array
+                        .iter()
+                        .map(|root| root.as_str().map(PathBuf::from))
+                        .collect::<Option<Vec<_>>>()
+                })
+                .ok_or_else(|| {
+                    invalid(
+                        "expected array of strings for discovery.search-roots".to_owned(),
+                    )
+                })?,
+            None => Vec::new(),
+        };
+
+        let allow_wolframscript = match document.get("discovery", "wolframscript") {
+            Some(value) => value.as_bool().ok_or_else(|| {
+                invalid("expected boolean value for discovery.wolframscript".to_owned())
+            })?,
+            None => true,
+        };
+
+        Ok(UserConfig {
+            path,
+            app_directory,
+            search_roots,
+            allow_wolframscript,
+        })
+    }
+}
+
+/// Find the app directories in `dir`, searching up to `depth` levels deep.
+///
+/// Directories that are apps are not searched further. If the search stopped
+/// early because `max_entries` directory entries were read, the limit is
+/// returned.
+fn find_apps_in_dir(
+    dir: &Path,
+    depth: usize,
+    max_entries: usize,
+    entries: &mut usize,
+    apps: &mut Vec<WolframApp>,
+) -> Option<ScanLimit> {
+    if depth == 0 {
+        return None;
+    }
+
+    let read_dir = match fs::read_dir(dir) {
+        Ok(read_dir) => read_dir,
+        Err(err) => {
+            crate::warning(&format!(
+                "error looking for Wolfram apps in '{}': {err}",
+                dir.display()
+            ));
+            return None;
+        },
+    };
+
+    for entry in read_dir.flatten() {
+        if *entries == max_entries {
+            return Some(ScanLimit::Entries(max_entries));
+        }
+        *entries += 1;
+
+        let path = entry.path();
+
+        if !path.is_dir() {
+            continue;
+        }
+
+        match WolframApp::from_app_directory(path.clone()) {
+            Ok(app) => apps.push(app),
+            Err(_) => {
+                let limit =
+                    find_apps_in_dir(&path, depth - 1, max_entries, entries, apps);
+
+                if limit.is_some() {
+                    return limit;
+                }
+            },
+        }
+    }
+
+    None
+}
+
+//======================================
+// Tests
+//======================================
+
+#[test]
+fn test_parse_user_config() {
+    let path = PathBuf::from("config.toml");
+
+    let config = UserConfig::parse(
+        path.clone(),
+        "[app]\n\
+        directory = \"/opt/Wolfram/Mathematica/14.1\"\n\
+        [discovery]\n\
+        search-roots = [\"/data/Wolfram\", \"/mnt/Wolfram\"]\n\
+        wolframscript = false\n",
+    )
+    .unwrap();
+
+    assert_eq!(
+        config.app_directory(),
+        Some(Path::new("/opt/Wolfram/Mathematica/14.1"))
+    );
+    assert_eq!(
+        config.search_roots(),
+        [
+            PathBuf::from("/data/Wolfram"),
+            PathBuf::from("/mnt/Wolfram")
+        ]
+    );
+    assert!(!config.allow_wolframscript());
+
+    let config = UserConfig::parse(path.clone(), "").unwrap();
+    assert_eq!(config.app_directory(), None);
+    assert!(config.search_roots().is_empty());
+    assert!(config.allow_wolframscript());
+
+    assert!(UserConfig::parse(path.clone(), "[discovery]\nwolframscript = 1\n").is_err());
+    assert!(UserConfig::parse(path, "[discovery]\nsearch-roots = \"/a\"\n").is_err());
+}
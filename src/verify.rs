@@ -0,0 +1,169 @@
+//! Installation health checks.
+//!
+//! Use [`WolframApp::verify()`] to check whether the components expected to be
+//! present in a Wolfram installation can be located. This can be used to
+//! distinguish partially uninstalled or corrupted installations from healthy
+//! ones.
+
+use std::{
+    fmt::{self, Display},
+    path::PathBuf,
+};
+
+use crate::{Error, WolframApp};
+
+/// Result of [`WolframApp::verify()`].
+#[derive(Debug, Clone)]
+pub struct VerificationReport {
+    checks: Vec<ComponentCheck>,
+}
+
+/// Result of checking for a single [`Component`] of an installation.
+#[derive(Debug, Clone)]
+pub struct ComponentCheck {
+    component: Component,
+    status: ComponentStatus,
+}
+
+/// A component that is expected to be present in a Wolfram installation.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Component {
+    /// See [`WolframApp::kernel_executable_path()`].
+    KernelExecutable,
+    /// See [`WolframApp::wolframscript_executable_path()`].
+    WolframScriptExecutable,
+    /// See [`WolframApp::target_wstp_sdk()`].
+    WstpSdk,
+    /// See [`WolframApp::library_link_c_includes_directory()`].
+    LibraryLinkHeaders,
+    /// See [`WolframApp::front_end_executable_path()`].
+    FrontEnd,
+}
+
+/// Whether a [`Component`] was found.
+#[derive(Debug, Clone)]
+pub enum ComponentStatus {
+    /// The component was found at the specified location.
+    Present(PathBuf),
+    /// The component was expected, but could not be found.
+    Missing(Error),
+    /// The component is not provided by this type of application.
+    NotApplicable,
+}
+
+//======================================
+// API
+//======================================
+
+impl WolframApp {
+    /// Check whether the components expected to be present in this
+    /// installation can be located.
+    ///
+    /// The following components are checked:
+    ///
+    /// * [`Component::KernelExecutable`]
+    /// * [`Component::WolframScriptExecutable`]
+    /// * [`Component::WstpSdk`] (for [`WolframApp::target_system_id()`])
+    /// * [`Component::LibraryLinkHeaders`]
+    /// * [`Component::FrontEnd`] (only for application types that provide a
+    ///   notebook front end)
+    pub fn verify(&self) -> VerificationReport {
+        let front_end = if self.app_type().has_front_end() {
+            match self.app_executable() {
+                Some(path) if path.is_file() => Ok(path),
+                _ => self.front_end_executable_path(),
+            }
+            .into()
+        } else {
+            ComponentStatus::NotApplicable
+        };
+
+        let checks = vec![
+            ComponentCheck {
+                component: Component::KernelExecutable,
+                status: self.kernel_executable_path().into(),
+            },
+            ComponentCheck {
+                component: Component::WolframScriptExecutable,
+                status: self.wolframscript_executable_path().into(),
+            },
+            ComponentCheck {
+                component: Component::WstpSdk,
+                status: self.target_wstp_sdk().map(|sdk| sdk.sdk_dir()).into(),
+            },
+            ComponentCheck {
+                component: Component::LibraryLinkHeaders,
+                status: self.library_link_c_includes_directory().into(),
+            },
+            ComponentCheck {
+                component: Component::FrontEnd,
+                status: front_end,
+            },
+        ];
+
+        VerificationReport { checks }
+    }
+}
+
+impl VerificationReport {
+    /// Results of each component check.
+    pub fn checks(&self) -> &[ComponentCheck] {
+        &self.checks
+    }
+
+    /// Checks for components that were expected but could not be found.
+    pub fn missing(&self) -> impl Iterator<Item = &ComponentCheck> {
+        self.checks
+            .iter()
+            .filter(|check| matches!(check.status, ComponentStatus::Missing(_)))
+    }
+
+    /// Returns `true` if every expected component was found.
+    pub fn is_healthy(&self) -> bool {
+        self.missing().next().is_none()
+    }
+}
+
+impl ComponentCheck {
+    /// The component that was checked.
+    pub fn component(&self) -> Component {
+        self.component
+    }
+
+    /// Whether the component was found.
+    pub fn status(&self) -> &ComponentStatus {
+        &self.status
+    }
+}
+
+//======================================
+// Conversion Impls
+//======================================
+
+impl From<Result<PathBuf, Error>> for ComponentStatus {
+    fn from(result: Result<PathBuf, Error>) -> Self {
+        match result {
+            Ok(path) => ComponentStatus::Present(path),
+            Err(err) => ComponentStatus::Missing(err),
+        }
+    }
+}
+
+//======================================
+// Formatting Impls
+//======================================
+
+impl Display for Component {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Component::KernelExecutable => "kernel executable",
+            Component::WolframScriptExecutable => "wolframscript executable",
+            Component::WstpSdk => "WSTP SDK",
+            Component::LibraryLinkHeaders => "LibraryLink headers",
+            Component::FrontEnd => "front end",
+        };
+
+        write!(f, "{name}")
+    }
+}
@@ -0,0 +1,339 @@
+//! Parser for the subset of Wolfram Language expression syntax used in
+//! `PacletInfo.wl` and `PacletInfo.m` files.
+//!
+//! Supported syntax:
+//!
+//! * `(* comments *)`, which may be nested
+//! * `"strings"` with `\"`, `\\`, `\n`, `\t`, and `\r` escapes
+//! * symbols, including `` ` `` context marks, and numbers
+//! * normal expressions `head[arg, ...]`
+//! * lists `{elem, ...}` and associations `<|key -> value, ...|>`
+//! * rules `lhs -> rhs` and delayed rules `lhs :> rhs`
+
+use std::fmt::{self, Display};
+
+/// A parsed Wolfram Language expression.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Expr {
+    String(String),
+    Symbol(String),
+    /// Number, stored as written in the source.
+    Number(String),
+    Normal(Box<Expr>, Vec<Expr>),
+    List(Vec<Expr>),
+    /// Association, whose elements are usually [`Expr::Rule`]s.
+    Association(Vec<Expr>),
+    /// `lhs -> rhs` or `lhs :> rhs`.
+    Rule(Box<Expr>, Box<Expr>),
+}
+
+/// Syntax error in a Wolfram Language expression.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ParseError {
+    line: usize,
+    message: String,
+}
+
+//======================================
+// Impls
+//======================================
+
+impl Expr {
+    /// Parse `text`, which must contain exactly one expression.
+    pub fn parse(text: &str) -> Result<Expr, ParseError> {
+        let mut cursor = Cursor { text, offset: 0 };
+
+        let expr = cursor.expr().map_err(|msg| cursor.error(msg))?;
+
+        cursor.skip_whitespace().map_err(|msg| cursor.error(msg))?;
+
+        if cursor.offset != text.len() {
+            return Err(cursor.error("unexpected characters after expression".to_owned()));
+        }
+
+        Ok(expr)
+    }
+
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Expr::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    /// Get the name of a string or symbol key, like `"Name"` or `Name`.
+    pub fn as_key(&self) -> Option<&str> {
+        match self {
+            Expr::String(name) | Expr::Symbol(name) => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Get the `(lhs, rhs)` of a rule.
+    pub fn as_rule(&self) -> Option<(&Expr, &Expr)> {
+        match self {
+            Expr::Rule(lhs, rhs) => Some((lhs, rhs)),
+            _ => None,
+        }
+    }
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let ParseError { line, message } = self;
+
+        write!(f, "line {line}: {message}")
+    }
+}
+
+//======================================
+// Helpers
+//======================================
+
+/// Parsing position within the source text.
+struct Cursor<'a> {
+    text: &'a str,
+    offset: usize,
+}
+
+impl<'a> Cursor<'a> {
+    fn rest(&self) -> &'a str {
+        &self.text[self.offset..]
+    }
+
+    fn error(&self, message: String) -> ParseError {
+        let line = self.text[..self.offset].matches('\n').count() + 1;
+
+        ParseError { line, message }
+    }
+
+    /// Skip whitespace and comments.
+    fn skip_whitespace(&mut self) -> Result<(), String> {
+        loop {
+            let rest = self.rest();
+            let trimmed = rest.trim_start();
+            self.offset += rest.len() - trimmed.len();
+
+            if !trimmed.starts_with("(*") {
+                return Ok(());
+            }
+
+            let mut depth = 0;
+
+            loop {
+                let rest = self.rest();
+
+                if rest.starts_with("(*") {
+                    depth += 1;
+                    self.offset += 2;
+                } else if rest.starts_with("*)") {
+                    depth -= 1;
+                    self.offset += 2;
+
+                    if depth == 0 {
+                        break;
+                    }
+                } else {
+                    match rest.chars().next() {
+                        Some(char) => self.offset += char.len_utf8(),
+                        None => return Err("unterminated comment".to_owned()),
+                    }
+                }
+            }
+        }
+    }
+
+    fn eat(&mut self, token: &str) -> Result<bool, String> {
+        self.skip_whitespace()?;
+
+        if self.rest().starts_with(token) {
+            self.offset += token.len();
+            return Ok(true);
+        }
+
+        Ok(false)
+    }
+
+    fn expr(&mut self) -> Result<Expr, String> {
+        let lhs = self.primary()?;
+
+        if self.eat("->")? || self.eat(":>")? {
+            let rhs = self.expr()?;
+            return Ok(Expr::Rule(Box::new(lhs), Box::new(rhs)));
+        }
+
+        Ok(lhs)
+    }
+
+    fn primary(&mut self) -> Result<Expr, String> {
+        self.skip_whitespace()?;
+
+        let mut expr = if self.eat("\"")? {
+            Expr::String(self.string()?)
+        } else if self.eat("{")? {
+            Expr::List(self.sequence("}")?)
+        } else if self.eat("<|")? {
+            Expr::Association(self.sequence("|>")?)
+        } else {
+            self.atom()?
+        };
+
+        while self.rest().starts_with('[') {
+            self.offset += 1;
+            let args = self.sequence("]")?;
+            expr = Expr::Normal(Box::new(expr), args);
+        }
+
+        Ok(expr)
+    }
+
+    /// Parse comma-separated expressions, up to and including `close`.
+    fn sequence(&mut self, close: &str) -> Result<Vec<Expr>, String> {
+        let mut elements = Vec::new();
+
+        if self.eat(close)? {
+            return Ok(elements);
+        }
+
+        loop {
+            elements.push(self.expr()?);
+
+            if self.eat(close)? {
+                return Ok(elements);
+            }
+
+            if !self.eat(",")? {
+                return Err(format!("expected ',' or '{close}'"));
+            }
+        }
+    }
+
+    fn atom(&mut self) -> Result<Expr, String> {
+        let rest = self.rest();
+
+        let is_number = rest.starts_with(|c: char| c.is_ascii_digit())
+            || (rest.starts_with('-')
+                && rest[1..].starts_with(|c: char| c.is_ascii_digit()));
+
+        let end = rest
+            .char_indices()
+            .skip(usize::from(is_number && rest.starts_with('-')))
+            .find(|&(_, c)| match is_number {
+                true => !(c.is_ascii_digit() || c == '.'),
+                false => !(c.is_alphanumeric() || c == '$' || c == '`'),
+            })
+            .map(|(index, _)| index)
+            .unwrap_or(rest.len());
+
+        if end == 0 {
+            return match rest.chars().next() {
+                Some(char) => Err(format!("unexpected character: '{char}'")),
+                None => Err("unexpected end of input".to_owned()),
+            };
+        }
+
+        self.offset += end;
+
+        let token = rest[..end].to_owned();
+
+        Ok(match is_number {
+            true => Expr::Number(token),
+            false => Expr::Symbol(token),
+        })
+    }
+
+    /// Parse the rest of a string, after the opening `"`.
+    fn string(&mut self) -> Result<String, String> {
+        let mut string = String::new();
+        let mut chars = self.rest().char_indices();
+
+        while let Some((index, char)) = chars.next() {
+            match char {
+                '"' => {
+                    self.offset += index + 1;
+                    return Ok(string);
+                },
+                '\\' => {
+                    let escaped = match chars.next() {
+                        Some((_, '"')) => '"',
+                        Some((_, '\\')) => '\\',
+                        Some((_, 'n')) => '\n',
+                        Some((_, 't')) => '\t',
+                        Some((_, 'r')) => '\r',
+                        _ => return Err("invalid escape sequence in string".to_owned()),
+                    };
+                    string.push(escaped);
+                },
+                _ => string.push(char),
+            }
+        }
+
+        Err("unterminated string".to_owned())
+    }
+}
+
+//======================================
+// Tests
+//======================================
+
+#[test]
+fn test_parse() {
+    let expr = Expr::parse(
+        r#"(* Comment (* nested *) *)
+        PacletObject[<|
+            "Name" -> "Example",
+            Version -> 1.0,
+            "Context" :> {"Example`", "Example`Private`"},
+            "Text" -> "a \"quoted\" word"
+        |>]
+        "#,
+    )
+    .unwrap();
+
+    let rule = |key: &str, value: Expr| {
+        Expr::Rule(Box::new(Expr::String(key.to_owned())), Box::new(value))
+    };
+
+    assert_eq!(
+        expr,
+        Expr::Normal(
+            Box::new(Expr::Symbol("PacletObject".to_owned())),
+            vec![Expr::Association(vec![
+                rule("Name", Expr::String("Example".to_owned())),
+                Expr::Rule(
+                    Box::new(Expr::Symbol("Version".to_owned())),
+                    Box::new(Expr::Number("1.0".to_owned()))
+                ),
+                rule(
+                    "Context",
+                    Expr::List(vec![
+                        Expr::String("Example`".to_owned()),
+                        Expr::String("Example`Private`".to_owned()),
+                    ])
+                ),
+                rule("Text", Expr::String("a \"quoted\" word".to_owned())),
+            ])]
+        )
+    );
+
+    assert_eq!(
+        Expr::parse("f[]"),
+        Ok(Expr::Normal(Box::new(Expr::Symbol("f".to_owned())), vec![]))
+    );
+    assert_eq!(Expr::parse("-12"), Ok(Expr::Number("-12".to_owned())));
+}
+
+#[test]
+fn test_parse_errors() {
+    assert_eq!(
+        Expr::parse("{1, 2").unwrap_err().to_string(),
+        "line 1: expected ',' or '}'"
+    );
+    assert_eq!(
+        Expr::parse("f[\n\"unterminated]").unwrap_err().to_string(),
+        "line 2: unterminated string"
+    );
+    assert!(Expr::parse("(* unterminated").is_err());
+    assert!(Expr::parse("a b").is_err());
+    assert!(Expr::parse("").is_err());
+}
@@ -6,6 +6,28 @@ fn test_try_default() {
         .expect("WolframApp::try_default() could not locate any apps");
 }
 
+#[test]
+fn test_default_cached() {
+    let first: &WolframApp = WolframApp::default_cached()
+        .expect("WolframApp::default_cached() could not locate any apps");
+
+    let second: &WolframApp = WolframApp::default_cached().unwrap();
+
+    // Discovery should only be performed once.
+    assert!(std::ptr::eq(first, second));
+}
+
+#[test]
+fn test_kernel_discovery() {
+    use wolfram_app_discovery::kernel::KernelDiscovery;
+
+    let kernel = KernelDiscovery::try_default()
+        .expect("KernelDiscovery::try_default() could not locate any kernels");
+
+    assert!(kernel.kernel_executable().is_file());
+    assert_eq!(kernel.launch_args(), ["-wstp"]);
+}
+
 #[test]
 fn macos_default_wolframscript_path() {
     if cfg!(not(target_os = "macos")) {